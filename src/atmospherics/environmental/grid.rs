@@ -0,0 +1,282 @@
+use crate::atmospherics::environmental::{Direction, Position, Turf};
+use crate::byond::Real;
+use crate::errors::AtmosError;
+use std::ops::{Index, IndexMut};
+
+/// A box of turfs, `width` by `height` on each of `depth` z-levels. Turfs are stored row by row,
+/// z-level by z-level, so `index_of()` and `position_of()` are plain arithmetic. z-levels start
+/// out separate; link them with `set_z_linked()` to stack them into one multi-z map.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Direction, Position, Turf, TurfGrid};
+/// use libatmos::atmospherics::gases::GasMixture;
+/// // a 3x3 room with a wall in the middle
+/// let mut grid = TurfGrid::new(3, 3, 1, Turf::floor(GasMixture::from_empty(293.15, 2500)));
+/// grid.replace(Position::new(1, 1, 0), Turf::wall()).unwrap();
+///
+/// assert_eq!(grid.neighbours(Position::new(1, 0, 0)).len(), 3);
+/// assert_eq!(grid.adjacent(Position::new(1, 0, 0)).len(), 2); // the wall's in the way
+/// assert_eq!(grid.step(Position::new(0, 0, 0), Direction::West), None);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct TurfGrid<'a> {
+    width: usize,
+    height: usize,
+    depth: usize,
+    turfs: Vec<Turf<'a>>,
+    /// Whether each z-level connects to the one above it, `ZTRAIT_UP` in tgstation.
+    z_links: Vec<bool>,
+    /// Goes up every time a turf gets replaced or (un)blocked, so anything caching the layout
+    /// (zones, say) knows to rebuild.
+    revision: u64
+}
+
+impl<'a> TurfGrid<'a> {
+    /// Creates a grid with every turf a copy of `turf`.
+    pub fn new(width: usize, height: usize, depth: usize, turf: Turf<'a>) -> TurfGrid<'a> {
+        TurfGrid::from_fn(width, height, depth, |_| turf.clone())
+    }
+
+    /// Creates a grid, calling `f` with each position to get the turf that goes there.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Position, Turf, TurfGrid};
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// // a room with space on the east edge
+    /// let grid = TurfGrid::from_fn(4, 4, 1, |pos| {
+    ///     if pos.x == 3 { Turf::space() } else { Turf::floor(GasMixture::from_empty(293.15, 2500)) }
+    /// });
+    /// assert!(grid[Position::new(3, 2, 0)].is_space());
+    /// ```
+    pub fn from_fn<F: FnMut(Position) -> Turf<'a>>(width: usize, height: usize, depth: usize, mut f: F) -> TurfGrid<'a> {
+        let mut turfs = Vec::with_capacity(width * height * depth);
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    turfs.push(f(Position::new(x, y, z)));
+                }
+            }
+        }
+        TurfGrid {
+            width,
+            height,
+            depth,
+            turfs,
+            z_links: vec![false; depth],
+            revision: 0
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of z-levels.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of turfs.
+    pub fn len(&self) -> usize {
+        self.turfs.len()
+    }
+
+    /// Returns true if the grid has no turfs at all.
+    pub fn is_empty(&self) -> bool {
+        self.turfs.is_empty()
+    }
+
+    /// Returns true if `pos` is on the grid.
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x < self.width && pos.y < self.height && pos.z < self.depth
+    }
+
+    /// Where the turf at `pos` is in the grid's storage, or `None` if it's off the grid.
+    pub fn index_of(&self, pos: Position) -> Option<usize> {
+        if self.contains(pos) {
+            Some((pos.z * self.height + pos.y) * self.width + pos.x)
+        } else {
+            None
+        }
+    }
+
+    /// The position of the turf at `index` in the grid's storage. Panics if `index` is out of range.
+    pub fn position_of(&self, index: usize) -> Position {
+        assert!(index < self.turfs.len(), "turf index {} out of range for a grid of {}", index, self.turfs.len());
+        Position::new(index % self.width, (index / self.width) % self.height, index / (self.width * self.height))
+    }
+
+    /// The turf at `pos`, if it's on the grid.
+    pub fn get(&self, pos: Position) -> Option<&Turf<'a>> {
+        self.index_of(pos).map(|index| &self.turfs[index])
+    }
+
+    /// The turf at `pos`, mutably, if it's on the grid. Fine for touching its air; use
+    /// `replace()` or `set_blocked()` to change the layout.
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut Turf<'a>> {
+        match self.index_of(pos) {
+            Some(index) => Some(&mut self.turfs[index]),
+            None => None
+        }
+    }
+
+    /// Both turfs at `a` and `b`, mutably, so air can move between them. `None` if either is
+    /// off the grid, or they're the same turf.
+    pub fn pair_mut(&mut self, a: Position, b: Position) -> Option<(&mut Turf<'a>, &mut Turf<'a>)> {
+        let (first, second) = (self.index_of(a)?, self.index_of(b)?);
+        if first == second {
+            return None;
+        }
+        let (low, high) = (first.min(second), first.max(second));
+        let (left, right) = self.turfs.split_at_mut(high);
+        let (low_turf, high_turf) = (&mut left[low], &mut right[0]);
+        if first < second { Some((low_turf, high_turf)) } else { Some((high_turf, low_turf)) }
+    }
+
+    /// Puts `turf` at `pos`, returning the turf that was there. Errors if `pos` is off the grid.
+    pub fn replace(&mut self, pos: Position, turf: Turf<'a>) -> Result<Turf<'a>, AtmosError> {
+        let index = self.checked_index(pos)?;
+        self.revision += 1;
+        Ok(std::mem::replace(&mut self.turfs[index], turf))
+    }
+
+    /// Blocks or unblocks the `direction` side of the turf at `pos`. Errors if `pos` is off the grid.
+    pub fn set_blocked(&mut self, pos: Position, direction: Direction, blocked: bool) -> Result<(), AtmosError> {
+        let index = self.checked_index(pos)?;
+        self.revision += 1;
+        self.turfs[index].set_blocked(direction, blocked);
+        Ok(())
+    }
+
+    /// Makes ZAS zones stop at the turf at `pos` or not. Errors if `pos` is off the grid.
+    pub fn set_blocks_zones(&mut self, pos: Position, blocks_zones: bool) -> Result<(), AtmosError> {
+        let index = self.checked_index(pos)?;
+        self.revision += 1;
+        self.turfs[index].set_blocks_zones(blocks_zones);
+        Ok(())
+    }
+
+    /// Links z-level `z` to the one above it or not, like a station's decks. Only linked levels
+    /// get vertical neighbours, so air can fall through open space between them. Errors if
+    /// there's no level above `z`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Direction, Position, Turf, TurfGrid};
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// // a floor with a hole above it
+    /// let mut grid = TurfGrid::from_fn(1, 1, 2, |pos| match pos.z {
+    ///     0 => Turf::floor(GasMixture::from_empty(293.15, 2500)),
+    ///     _ => Turf::open_space(GasMixture::from_empty(293.15, 2500))
+    /// });
+    /// let (bottom, top) = (Position::new(0, 0, 0), Position::new(0, 0, 1));
+    /// assert!(grid.adjacent(bottom).is_empty());
+    ///
+    /// grid.set_z_linked(0, true).unwrap();
+    /// assert_eq!(grid.adjacent(bottom), vec![(Direction::Up, top)]);
+    /// assert_eq!(grid.adjacent(top), vec![(Direction::Down, bottom)]);
+    /// assert!(grid.set_z_linked(1, true).is_err()); // nothing above the top
+    /// ```
+    pub fn set_z_linked(&mut self, z: usize, linked: bool) -> Result<(), AtmosError> {
+        if z + 1 >= self.depth {
+            return Err(AtmosError::OutOfBounds { position: Position::new(0, 0, z + 1), width: self.width, height: self.height, depth: self.depth });
+        }
+        self.revision += 1;
+        self.z_links[z] = linked;
+        Ok(())
+    }
+
+    /// Returns true if z-level `z` connects to the one above it.
+    pub fn is_z_linked(&self, z: usize) -> bool {
+        self.z_links.get(z).copied().unwrap_or(false)
+    }
+
+    /// How many times the layout has changed since the grid was made.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// The position one step from `pos` in `direction`, or `None` if that's off the grid, or
+    /// on a z-level that isn't linked to this one.
+    pub fn step(&self, pos: Position, direction: Direction) -> Option<Position> {
+        let linked = match direction {
+            Direction::Up => self.is_z_linked(pos.z),
+            Direction::Down => pos.z > 0 && self.is_z_linked(pos.z - 1),
+            _ => true
+        };
+        pos.step(direction).filter(|next| linked && self.contains(*next))
+    }
+
+    /// Every turf next to `pos` that's on the grid, air-tight or not, in `Direction::ALL` order.
+    pub fn neighbours(&self, pos: Position) -> Vec<(Direction, Position)> {
+        Direction::ALL.iter()
+                      .filter_map(|&direction| self.step(pos, direction).map(|next| (direction, next)))
+                      .collect()
+    }
+
+    /// Every turf next to `pos` that air can move to, `atmos_adjacent_turfs` in tgstation. Both
+    /// turfs have to be open towards each other, and air only moves between z-levels through
+    /// open space, into whatever's under it.
+    pub fn adjacent(&self, pos: Position) -> Vec<(Direction, Position)> {
+        let turf = match self.get(pos) {
+            Some(turf) => turf,
+            None => return Vec::new()
+        };
+        self.neighbours(pos)
+            .into_iter()
+            .filter(|(direction, next)| {
+                let next_turf = &self[*next];
+                let through_floor = match direction {
+                    Direction::Up => next_turf.is_open_space(),
+                    Direction::Down => turf.is_open_space(),
+                    _ => true
+                };
+                through_floor && turf.is_open(*direction) && next_turf.is_open(direction.reverse())
+            })
+            .collect()
+    }
+
+    /// Every position on the grid, in storage order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.turfs.len()).map(move |index| self.position_of(index))
+    }
+
+    /// Every turf with its position, in storage order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &Turf<'a>)> + '_ {
+        self.turfs.iter().enumerate().map(move |(index, turf)| (self.position_of(index), turf))
+    }
+
+    /// Total mols of gas on the whole grid.
+    pub fn total_moles(&self) -> Real {
+        self.turfs.iter().map(|turf| turf.air.total_moles()).sum()
+    }
+
+    fn checked_index(&self, pos: Position) -> Result<usize, AtmosError> {
+        self.index_of(pos).ok_or(AtmosError::OutOfBounds { position: pos, width: self.width, height: self.height, depth: self.depth })
+    }
+}
+
+impl<'a> Index<Position> for TurfGrid<'a> {
+    type Output = Turf<'a>;
+
+    /// The turf at `pos`. Panics if it's off the grid; use `get()` if it might be.
+    fn index(&self, pos: Position) -> &Turf<'a> {
+        match self.get(pos) {
+            Some(turf) => turf,
+            None => panic!("{} is off a {}x{}x{} grid", pos, self.width, self.height, self.depth)
+        }
+    }
+}
+
+impl<'a> IndexMut<Position> for TurfGrid<'a> {
+    fn index_mut(&mut self, pos: Position) -> &mut Turf<'a> {
+        let (width, height, depth) = (self.width, self.height, self.depth);
+        match self.get_mut(pos) {
+            Some(turf) => turf,
+            None => panic!("{} is off a {}x{}x{} grid", pos, width, height, depth)
+        }
+    }
+}
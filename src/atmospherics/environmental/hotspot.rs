@@ -0,0 +1,255 @@
+use crate::atmospherics::environmental::{Position, TurfGrid};
+use crate::atmospherics::environmental::linda::Linda;
+use crate::atmospherics::gases::{GasMixture, GasType};
+use crate::byond::Real;
+use crate::constants::*;
+
+// Fires on turfs, ported from LINDA_fire.dm. Something hot enough touching air with plasma or
+// tritium and oxygen in it makes a hotspot, which reacts part of the turf's air (its volume) at
+// its own temperature every cycle, growing with how much burned. Once a hotspot covers the whole
+// turf it "bypasses": it stops reacting anything itself, follows the turf's air (which reacts
+// like any other active turf) and radiates heat at its neighbours, which can set them alight
+// too. Hotspots go out when it's too cold, they get too small, or they run out of fuel.
+
+/// A fire on one turf, `/obj/effect/hotspot`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Hotspot {
+    /// How hot the fire is, in kelvin.
+    pub temperature: Real,
+    /// How much of the turf's air is on fire, in liters.
+    pub volume: Real,
+    /// Whether the fire covers the whole turf, so it just follows the turf's air.
+    pub bypassing: bool,
+    /// Hotspots sit out the first cycle after they spawn, unless their turf's air already got
+    /// processed that cycle.
+    just_spawned: bool
+}
+
+impl Hotspot {
+    /// Returns true if the hotspot hasn't been through a cycle yet.
+    pub fn just_spawned(&self) -> bool {
+        self.just_spawned
+    }
+}
+
+/// A turf that was on fire at the end of a cycle.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BurningTurf {
+    pub position: Position,
+    /// The hotspot's temperature.
+    pub temperature: Real,
+    /// The hotspot's volume.
+    pub volume: Real,
+    /// Whether the hotspot covers the whole turf.
+    pub bypassing: bool
+}
+
+/// What one `Hotspots::process()` did.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct FireStats {
+    /// Every turf still on fire afterwards, in the order the fires started.
+    pub burning: Vec<BurningTurf>,
+    /// Fires that spread to a new turf.
+    pub ignited: usize,
+    /// Fires that went out.
+    pub extinguished: usize
+}
+
+/// Every hotspot on a `TurfGrid`, `SSair.hotspots`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Position, Turf, TurfGrid};
+/// use libatmos::atmospherics::environmental::hotspot::Hotspots;
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::constants::gases;
+/// let air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![20.0, 80.0], 293.15, 2500).unwrap();
+/// let mut grid = TurfGrid::new(3, 3, 1, Turf::floor(air));
+/// let mut hotspots = Hotspots::new(&grid);
+///
+/// // a welder's worth of heat is plenty
+/// assert!(hotspots.expose(&mut grid, Position::new(1, 1, 0), 700.0, 50.0));
+/// assert!(hotspots.is_burning(&grid, Position::new(1, 1, 0)));
+/// // and the fire's already burning some of the plasma
+/// assert!(grid[Position::new(1, 1, 0)].air.get_moles(&gases::PLASMA).unwrap() < 20.0);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Hotspots {
+    hotspots: Vec<Option<Hotspot>>,
+    /// Indices of the turfs on fire, in the order they caught fire.
+    order: Vec<usize>
+}
+
+impl Hotspots {
+    /// Creates an empty hotspot list for `grid`.
+    pub fn new(grid: &TurfGrid) -> Hotspots {
+        Hotspots {
+            hotspots: vec![None; grid.len()],
+            order: Vec::new()
+        }
+    }
+
+    /// The hotspot on the turf at `pos`, if there is one.
+    pub fn get(&self, grid: &TurfGrid, pos: Position) -> Option<&Hotspot> {
+        grid.index_of(pos).and_then(|index| self.hotspots[index].as_ref())
+    }
+
+    /// Returns true if the turf at `pos` is on fire.
+    pub fn is_burning(&self, grid: &TurfGrid, pos: Position) -> bool {
+        self.get(grid, pos).is_some()
+    }
+
+    /// Every turf on fire, in the order they caught fire.
+    pub fn burning(&self, grid: &TurfGrid) -> Vec<Position> {
+        self.order.iter().map(|index| grid.position_of(*index)).collect()
+    }
+
+    /// Number of turfs on fire.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns true if nothing's on fire.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// `hotspot_expose()`: something `exposed_temperature` hot touches `exposed_volume` liters of
+    /// the air on the turf at `pos`. Starts a fire if it's hot enough and there's at least half
+    /// a mol each of oxygen and plasma or tritium; like tgstation's, the fire starts out 25 times
+    /// `exposed_volume`. Returns true if the turf's on fire afterwards. Meant for exposures
+    /// between cycles, so a new fire always sits out the next one.
+    pub fn expose(&mut self, grid: &mut TurfGrid, pos: Position, exposed_temperature: Real, exposed_volume: Real) -> bool {
+        self.expose_impl(grid, pos, exposed_temperature, exposed_volume, false, true)
+    }
+
+    /// Same as `expose()`, but an existing fire gets hotter and bigger if the exposure's hotter
+    /// or bigger than it, the `soh` ("share other heat") argument in tgstation.
+    pub fn expose_and_feed(&mut self, grid: &mut TurfGrid, pos: Position, exposed_temperature: Real, exposed_volume: Real) -> bool {
+        self.expose_impl(grid, pos, exposed_temperature, exposed_volume, true, true)
+    }
+
+    fn expose_impl(&mut self, grid: &mut TurfGrid, pos: Position, exposed_temperature: Real, exposed_volume: Real, soh: bool, just_spawned: bool) -> bool {
+        let index = match (grid.index_of(pos), grid.get(pos)) {
+            (Some(index), Some(turf)) if !turf.blocks_air() && !turf.is_space() => index,
+            _ => return false
+        };
+        let air = &grid[pos].air;
+        if moles(air, &gases::O2) < 0.5 {
+            return false;
+        }
+        let fuel = moles(air, &gases::PLASMA) > 0.5 || moles(air, &gases::TRIT) > 0.5;
+        if let Some(hotspot) = self.hotspots[index].as_mut() {
+            if soh && fuel {
+                hotspot.temperature = hotspot.temperature.max(exposed_temperature);
+                hotspot.volume = hotspot.volume.max(exposed_volume);
+            }
+            return true;
+        }
+        if exposed_temperature <= num::PLASMA_MINIMUM_BURN_TEMPERATURE || !fuel {
+            return false;
+        }
+        self.hotspots[index] = Some(Hotspot {
+            temperature: exposed_temperature,
+            volume: exposed_volume * 25.0,
+            bypassing: false,
+            just_spawned
+        });
+        self.order.push(index);
+        self.perform_exposure(grid, index, true);
+        true
+    }
+
+    /// Puts out the fire on the turf at `pos`, like a fire extinguisher would. Returns false if
+    /// there wasn't one.
+    pub fn extinguish(&mut self, grid: &TurfGrid, pos: Position) -> bool {
+        match grid.index_of(pos) {
+            Some(index) if self.hotspots[index].is_some() => {
+                self.remove(index);
+                true
+            },
+            _ => false
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        self.hotspots[index] = None;
+        self.order.retain(|burning| *burning != index);
+    }
+
+    /// `perform_exposure()`: reacts the part of the turf's air the hotspot covers at the
+    /// hotspot's temperature, and grows or shrinks the hotspot to match how much burned. A
+    /// hotspot that's `spawning` always reacts its own air, however big it starts out.
+    fn perform_exposure(&mut self, grid: &mut TurfGrid, index: usize, spawning: bool) {
+        let pos = grid.position_of(index);
+        let air = &mut grid[pos].air;
+        let hotspot = self.hotspots[index].as_mut().unwrap(); // only called on hotspots that exist
+        hotspot.bypassing = !spawning && hotspot.volume > num::CELL_VOLUME as Real * 0.95;
+        if hotspot.bypassing {
+            hotspot.volume = air.last_fire() * num::FIRE_GROWTH_RATE as Real;
+            hotspot.temperature = air.temperature;
+        } else {
+            let mut affected = air.remove_ratio(hotspot.volume / air.volume as Real).unwrap(); // volume's never negative
+            affected.temperature = hotspot.temperature;
+            affected.react();
+            hotspot.temperature = affected.temperature;
+            hotspot.volume = affected.last_fire() * num::FIRE_GROWTH_RATE as Real;
+            air.merge(affected);
+        }
+    }
+
+    /// Runs every hotspot for cycle `fire_count` (`process_hotspots()`): puts out the ones that
+    /// can't keep going, burns the rest, and lets the ones covering their whole turf spread.
+    /// Fires that spread this cycle wait for the next one, and sit that one out too unless
+    /// `linda` already processed their turf this cycle (`current_cycle < SSair.times_fired`).
+    pub fn process(&mut self, grid: &mut TurfGrid, linda: &Linda, fire_count: usize) -> FireStats {
+        let mut stats = FireStats::default();
+        for index in self.order.clone() {
+            let hotspot = match self.hotspots[index].as_mut() {
+                Some(hotspot) => hotspot,
+                None => continue
+            };
+            if hotspot.just_spawned {
+                hotspot.just_spawned = false;
+                continue;
+            }
+            let pos = grid.position_of(index);
+            let turf = &grid[pos];
+            let fuel = moles(&turf.air, &gases::PLASMA) >= 0.5 || moles(&turf.air, &gases::TRIT) >= 0.5;
+            if turf.blocks_air() || turf.is_space()
+                || hotspot.temperature < num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST || hotspot.volume <= 1.0
+                || !fuel || moles(&turf.air, &gases::O2) < 0.5 {
+                self.remove(index);
+                stats.extinguished += 1;
+                continue;
+            }
+
+            self.perform_exposure(grid, index, false);
+            // possible spread due to radiated heat
+            let air_temperature = grid[pos].air.temperature;
+            if self.hotspots[index].is_some_and(|hotspot| hotspot.bypassing) && air_temperature > num::FIRE_MINIMUM_TEMPERATURE_TO_SPREAD {
+                let radiated_temperature = air_temperature * num::FIRE_SPREAD_RADIOSITY_SCALE;
+                for (_, next) in grid.adjacent(pos) {
+                    let just_spawned = linda.state(grid, next).is_none_or(|state| state.current_cycle < fire_count);
+                    if !self.is_burning(grid, next)
+                        && self.expose_impl(grid, next, radiated_temperature, num::CELL_VOLUME as Real / 4.0, false, just_spawned) {
+                        stats.ignited += 1;
+                    }
+                }
+            }
+        }
+        stats.burning = self.order.iter().map(|index| {
+            let hotspot = self.hotspots[*index].unwrap(); // everything in order is burning
+            BurningTurf {
+                position: grid.position_of(*index),
+                temperature: hotspot.temperature,
+                volume: hotspot.volume,
+                bypassing: hotspot.bypassing
+            }
+        }).collect();
+        stats
+    }
+}
+
+fn moles<'a>(air: &GasMixture<'a>, gas: &'a GasType) -> Real {
+    air.get_moles(gas).unwrap_or(0.0)
+}
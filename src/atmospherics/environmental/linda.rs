@@ -0,0 +1,463 @@
+use crate::atmospherics::environmental::{Position, TurfGrid};
+use crate::atmospherics::environmental::superconduction::Superconductivity;
+use crate::atmospherics::environmental::wind::SpaceWind;
+use crate::atmospherics::gases::GasMixture;
+#[cfg(feature = "invariants")]
+use crate::atmospherics::gases::invariants;
+use crate::byond::Real;
+use crate::constants::*;
+use std::collections::BTreeMap;
+
+// LINDA, tgstation's turf air processing (SSair's active turfs). Only turfs that have something
+// going on are "active"; each cycle every active turf archives itself, shares air with each
+// neighbour that's different enough (waking the neighbour up), and reacts. Turfs that share end
+// up in an excited group, which every few cycles averages out all of its turfs' air at once, and
+// dismantles itself (putting its turfs to sleep) once nothing's moved for a while. A turf that
+// isn't in a group goes to sleep as soon as it's processed. Space never gets processed itself;
+// turfs next to it share with a vacuum instead.
+
+/// LINDA's bookkeeping for one turf.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TurfState {
+    /// Last cycle the turf's air got archived.
+    pub archived_cycle: usize,
+    /// Last cycle the turf got processed.
+    pub current_cycle: usize,
+    /// Cycles since the turf last moved a meaningful amount of air.
+    pub atmos_cooldown: usize,
+    /// Whether the turf is on the active list.
+    pub excited: bool,
+    /// Id of the excited group the turf is in, if it's in one.
+    pub excited_group: Option<usize>
+}
+
+/// A bunch of neighbouring active turfs, `/datum/excited_group`.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ExcitedGroup {
+    /// Cycles since the group last averaged its air. Breaks down at `EXCITED_GROUP_BREAKDOWN_CYCLES`.
+    pub breakdown_cooldown: usize,
+    /// Cycles since anything in the group moved a meaningful amount of air. Dismantles at
+    /// `EXCITED_GROUP_DISMANTLE_CYCLES`.
+    pub dismantle_cooldown: usize,
+    /// Indices of the turfs in the group, in the order they joined.
+    turfs: Vec<usize>
+}
+
+impl ExcitedGroup {
+    /// Every turf in the group, in the order they joined.
+    pub fn turfs(&self, grid: &TurfGrid) -> Vec<Position> {
+        self.turfs.iter().map(|index| grid.position_of(*index)).collect()
+    }
+
+    /// Number of turfs in the group.
+    pub fn len(&self) -> usize {
+        self.turfs.len()
+    }
+
+    /// Returns true if the group has no turfs. Only ever true for a group that's being thrown away.
+    pub fn is_empty(&self) -> bool {
+        self.turfs.is_empty()
+    }
+
+    fn reset_cooldowns(&mut self) {
+        self.breakdown_cooldown = 0;
+        self.dismantle_cooldown = 0;
+    }
+}
+
+/// What one `Linda::process()` did.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LindaStats {
+    /// Turfs that got processed.
+    pub processed: usize,
+    /// Turfs that went to sleep on their own.
+    pub deactivated: usize,
+    /// Excited groups that averaged out their air.
+    pub breakdowns: usize,
+    /// Excited groups that dismantled, putting all of their turfs to sleep.
+    pub dismantles: usize
+}
+
+/// The active turf list, the excited groups and every turf's `TurfState`, for one `TurfGrid`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Linda {
+    states: Vec<TurfState>,
+    /// Indices of the active turfs, in the order they got activated, like `SSair.active_turfs`.
+    active: Vec<usize>,
+    /// Excited groups by id. Ids only go up, so this is also `SSair.excited_groups` order.
+    groups: BTreeMap<usize, ExcitedGroup>,
+    next_group: usize,
+    /// Turfs still to be processed this cycle, `SSair.currentrun`. Only there inside `process()`.
+    run: Option<Vec<usize>>,
+    /// Whether each turf's in `run`.
+    queued: Vec<bool>
+}
+
+impl Linda {
+    /// Creates LINDA's state for `grid`, with nothing active.
+    pub fn new(grid: &TurfGrid) -> Linda {
+        Linda {
+            states: vec![TurfState::default(); grid.len()],
+            active: Vec::new(),
+            groups: BTreeMap::new(),
+            next_group: 0,
+            run: None,
+            queued: vec![false; grid.len()]
+        }
+    }
+
+    /// Bookkeeping for the turf at `pos`, if it's on the grid.
+    pub fn state(&self, grid: &TurfGrid, pos: Position) -> Option<TurfState> {
+        grid.index_of(pos).map(|index| self.states[index])
+    }
+
+    /// Returns true if the turf at `pos` is active.
+    pub fn is_active(&self, grid: &TurfGrid, pos: Position) -> bool {
+        self.state(grid, pos).is_some_and(|state| state.excited)
+    }
+
+    /// Every active turf, in the order they got activated. They get processed back to front.
+    pub fn active_turfs(&self, grid: &TurfGrid) -> Vec<Position> {
+        self.active.iter().map(|index| grid.position_of(*index)).collect()
+    }
+
+    /// Every excited group with its id, oldest first.
+    pub fn excited_groups(&self) -> impl Iterator<Item = (usize, &ExcitedGroup)> + '_ {
+        self.groups.iter().map(|(id, group)| (*id, group))
+    }
+
+    /// The excited group with id `id`, if it's still around.
+    pub fn excited_group(&self, id: usize) -> Option<&ExcitedGroup> {
+        self.groups.get(&id)
+    }
+
+    /// The excited group the turf at `pos` is in, if it's in one.
+    pub fn group_of(&self, grid: &TurfGrid, pos: Position) -> Option<&ExcitedGroup> {
+        self.state(grid, pos)?.excited_group.and_then(|id| self.groups.get(&id))
+    }
+
+    /// Wakes up the turf at `pos`, `SSair.add_to_active()`. Something changed, so whatever group
+    /// it's in gets broken up. Walls and space never get processed, so they can't be woken up;
+    /// returns false for those, and for turfs that are off the grid.
+    pub fn activate(&mut self, grid: &TurfGrid, pos: Position) -> bool {
+        match (grid.index_of(pos), grid.get(pos)) {
+            (Some(index), Some(turf)) if !turf.blocks_air() && !turf.is_space() => {
+                self.activate_index(index);
+                if let Some(group) = self.states[index].excited_group {
+                    self.garbage_collect(group);
+                }
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Keeps the turf at `pos` going like a fire on it does: wakes it up without breaking up its
+    /// group (`add_to_active(T, 0)`), and resets the group's cooldowns so it doesn't break down
+    /// or dismantle while the fire's burning. Returns false for walls, space and turfs off the grid.
+    pub fn keep_awake(&mut self, grid: &TurfGrid, pos: Position) -> bool {
+        match (grid.index_of(pos), grid.get(pos)) {
+            (Some(index), Some(turf)) if !turf.blocks_air() && !turf.is_space() => {
+                self.activate_index(index);
+                if let Some(group) = self.states[index].excited_group {
+                    self.groups.get_mut(&group).unwrap().reset_cooldowns(); // turfs only point at live groups
+                }
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Wakes up the turf at `pos` and nothing else, `add_to_active(T, 0)`: its group and the
+    /// group's cooldowns are left alone. Superconduction wakes turfs whose air it heated up this
+    /// way. Returns false for walls, space and turfs off the grid.
+    pub fn wake(&mut self, grid: &TurfGrid, pos: Position) -> bool {
+        match (grid.index_of(pos), grid.get(pos)) {
+            (Some(index), Some(turf)) if !turf.blocks_air() && !turf.is_space() => {
+                self.activate_index(index);
+                true
+            },
+            _ => false
+        }
+    }
+
+    fn activate_index(&mut self, index: usize) {
+        if !self.states[index].excited {
+            self.states[index].excited = true;
+            self.active.push(index);
+        }
+        self.queue(index);
+    }
+
+    /// Adds a turf woken up in the middle of `process()` to this cycle's run, `currentrun |= T`.
+    /// Does nothing outside of `process()`.
+    fn queue(&mut self, index: usize) {
+        if let Some(run) = self.run.as_mut() {
+            if !self.queued[index] {
+                self.queued[index] = true;
+                run.push(index);
+            }
+        }
+    }
+
+    /// Puts the turf at `pos` to sleep, `SSair.remove_from_active()`. Breaks up its group.
+    pub fn deactivate(&mut self, grid: &TurfGrid, pos: Position) {
+        if let Some(index) = grid.index_of(pos) {
+            self.deactivate_index(index);
+        }
+    }
+
+    fn deactivate_index(&mut self, index: usize) {
+        if self.states[index].excited {
+            self.states[index].excited = false;
+            self.active.retain(|active| *active != index);
+        }
+        if let Some(group) = self.states[index].excited_group {
+            self.garbage_collect(group);
+        }
+    }
+
+    fn archive(&mut self, grid: &mut TurfGrid, index: usize, fire_count: usize) {
+        let pos = grid.position_of(index);
+        grid[pos].air.archive();
+        self.states[index].archived_cycle = fire_count;
+    }
+
+    /// Makes a new, empty excited group and returns its id.
+    fn new_group(&mut self) -> usize {
+        let id = self.next_group;
+        self.next_group += 1;
+        self.groups.insert(id, ExcitedGroup::default());
+        id
+    }
+
+    fn add_turf(&mut self, group: usize, index: usize) {
+        let excited_group = self.groups.get_mut(&group).unwrap(); // only ever called with live groups
+        excited_group.turfs.push(index);
+        excited_group.reset_cooldowns();
+        self.states[index].excited_group = Some(group);
+    }
+
+    /// Merges groups `a` and `b`, the smaller one into the bigger one (`b` on a tie), and returns
+    /// the id of the one that's left.
+    fn merge_groups(&mut self, a: usize, b: usize) -> usize {
+        let (keep, absorb) = if self.groups[&a].len() > self.groups[&b].len() { (a, b) } else { (b, a) };
+        let absorbed = self.groups.remove(&absorb).unwrap(); // both are live groups
+        for index in &absorbed.turfs {
+            self.states[*index].excited_group = Some(keep);
+        }
+        let kept = self.groups.get_mut(&keep).unwrap();
+        kept.turfs.extend(absorbed.turfs);
+        kept.reset_cooldowns();
+        keep
+    }
+
+    /// Throws group `group` away, leaving its turfs active but groupless.
+    fn garbage_collect(&mut self, group: usize) {
+        if let Some(excited_group) = self.groups.remove(&group) {
+            for index in excited_group.turfs {
+                self.states[index].excited_group = None;
+            }
+        }
+    }
+
+    /// `LAST_SHARE_CHECK`: moving a lot of air keeps the whole group going, moving a bit just
+    /// keeps it from dismantling.
+    fn last_share_check(&mut self, group: usize, last_share: Real, cooldown: &mut usize) {
+        let excited_group = self.groups.get_mut(&group).unwrap(); // only ever called with live groups
+        if last_share > num::MINIMUM_AIR_TO_SUSPEND {
+            excited_group.reset_cooldowns();
+            *cooldown = 0;
+        } else if last_share > num::MINIMUM_MOLES_DELTA_TO_MOVE {
+            excited_group.dismantle_cooldown = 0;
+            *cooldown = 0;
+        }
+    }
+
+    /// Runs one cycle: processes the active turfs (`process_active_turfs()`), then every excited
+    /// group (`process_excited_groups()`). Like SSair, the active turfs get taken off the back
+    /// of the list, and turfs that get woken up along the way go on the back to be processed
+    /// this cycle too, so air can get a long way in one cycle. Nothing gets processed twice.
+    /// `fire_count` has to go up by one each call, starting at 1. Every share's pressure
+    /// difference goes into `wind`, and hot turfs that aren't in a group start superconducting
+    /// instead of going to sleep.
+    pub fn process(&mut self, grid: &mut TurfGrid, wind: &mut SpaceWind, superconductivity: &mut Superconductivity, fire_count: usize) -> LindaStats {
+        let mut stats = LindaStats::default();
+        self.run = Some(Vec::new());
+        for index in self.active.clone() {
+            self.queue(index);
+        }
+        while let Some(index) = self.run.as_mut().and_then(|run| run.pop()) {
+            self.queued[index] = false;
+            // something earlier this cycle might've put it to sleep already, or processed it
+            if !self.states[index].excited || self.states[index].current_cycle >= fire_count {
+                continue;
+            }
+            stats.processed += 1;
+            if !self.process_cell(grid, wind, superconductivity, index, fire_count) {
+                self.deactivate_index(index);
+                stats.deactivated += 1;
+            }
+        }
+        self.run = None;
+
+        // SSair goes through its excited groups back to front
+        let ids = self.groups.keys().rev().copied().collect::<Vec<_>>();
+        for id in ids {
+            let group = match self.groups.get_mut(&id) {
+                Some(group) => group,
+                None => continue
+            };
+            group.breakdown_cooldown += 1;
+            group.dismantle_cooldown += 1;
+            if group.breakdown_cooldown >= num::EXCITED_GROUP_BREAKDOWN_CYCLES {
+                self.self_breakdown(grid, id);
+                stats.breakdowns += 1;
+            } else if group.dismantle_cooldown >= num::EXCITED_GROUP_DISMANTLE_CYCLES {
+                self.dismantle(id);
+                stats.dismantles += 1;
+            }
+        }
+        stats
+    }
+
+    /// `process_cell()` for one turf. Returns false if the turf should go to sleep: it isn't in
+    /// an excited group or hot enough to superconduct, or it's gone
+    /// `EXCITED_GROUP_DISMANTLE_CYCLES * 2` cycles without moving anything worth mentioning.
+    fn process_cell(&mut self, grid: &mut TurfGrid, wind: &mut SpaceWind, superconductivity: &mut Superconductivity, index: usize, fire_count: usize) -> bool {
+        if self.states[index].archived_cycle < fire_count {
+            self.archive(grid, index, fire_count);
+        }
+        self.states[index].current_cycle = fire_count;
+
+        let pos = grid.position_of(index);
+        let adjacent = grid.adjacent(pos);
+        let planetary = grid[pos].is_planetary();
+        let adjacent_turfs_length = adjacent.len() + planetary as usize;
+        let mut cooldown = self.states[index].atmos_cooldown + 1;
+
+        for (direction, enemy_pos) in adjacent {
+            let enemy = grid.index_of(enemy_pos).unwrap(); // adjacent() only hands out turfs on the grid
+            if fire_count <= self.states[enemy].current_cycle {
+                continue;
+            }
+            if grid[enemy_pos].is_space() {
+                if grid[pos].air.compare(&grid[enemy_pos].air).is_some() {
+                    let group = self.ensure_group(index);
+                    let our_air = &mut grid[pos].air;
+                    let difference = our_air.share_with_space(adjacent_turfs_length);
+                    self.last_share_check(group, our_air.last_share(), &mut cooldown);
+                    if difference > 0.0 {
+                        wind.consider_pressure_difference(grid, pos, direction, difference);
+                    }
+                }
+                continue;
+            }
+            self.archive(grid, enemy, fire_count);
+
+            let our_group = self.states[index].excited_group;
+            let enemy_group = self.states[enemy].excited_group;
+            let group = match (our_group, enemy_group) {
+                // groups share no matter what
+                (Some(ours), Some(theirs)) if ours == theirs => Some(ours),
+                (Some(ours), Some(theirs)) => Some(self.merge_groups(ours, theirs)),
+                _ if grid[pos].air.compare(&grid[enemy_pos].air).is_some() => {
+                    self.activate_index(enemy);
+                    let group = our_group.or(enemy_group).unwrap_or_else(|| self.new_group());
+                    if our_group.is_none() {
+                        self.add_turf(group, index);
+                    }
+                    if enemy_group.is_none() {
+                        self.add_turf(group, enemy);
+                    }
+                    Some(group)
+                },
+                _ => None
+            };
+
+            if let Some(group) = group {
+                let (our_turf, enemy_turf) = grid.pair_mut(pos, enemy_pos).unwrap(); // adjacent turfs are never the same turf
+                let difference = our_turf.air.share(&mut enemy_turf.air, adjacent_turfs_length);
+                self.last_share_check(group, our_turf.air.last_share(), &mut cooldown);
+                // the wind blows from whichever side lost air
+                if difference > 0.0 {
+                    wind.consider_pressure_difference(grid, pos, direction, difference);
+                } else if difference < 0.0 {
+                    wind.consider_pressure_difference(grid, enemy_pos, direction.reverse(), -difference);
+                }
+            }
+        }
+
+        // planetary turfs share with the planet's atmosphere "above" them
+        if planetary {
+            let mut atmosphere = grid[pos].initial_air().clone();
+            atmosphere.archive();
+            if grid[pos].air.compare(&atmosphere).is_some() {
+                let group = self.ensure_group(index);
+                let our_air = &mut grid[pos].air;
+                our_air.share(&mut atmosphere, adjacent_turfs_length);
+                self.last_share_check(group, our_air.last_share(), &mut cooldown);
+            }
+        }
+
+        grid[pos].air.react();
+
+        self.states[index].atmos_cooldown = cooldown;
+        let keep_going = self.states[index].excited_group.is_some()
+            || (grid[pos].air.temperature > num::MINIMUM_TEMPERATURE_START_SUPERCONDUCTION && superconductivity.consider(grid, pos, true));
+        keep_going && cooldown <= num::EXCITED_GROUP_DISMANTLE_CYCLES * 2
+    }
+
+    /// The turf's group, putting it in a new one of its own if it isn't in one.
+    fn ensure_group(&mut self, index: usize) -> usize {
+        match self.states[index].excited_group {
+            Some(group) => group,
+            None => {
+                let group = self.new_group();
+                self.add_turf(group, index);
+                group
+            }
+        }
+    }
+
+    /// Averages out the air of every turf in group `id`, `self_breakdown()`.
+    fn self_breakdown(&mut self, grid: &mut TurfGrid, id: usize) {
+        let turfs = self.groups[&id].turfs.clone();
+        #[cfg(feature = "invariants")]
+        let before = turfs.iter().map(|index| grid[grid.position_of(*index)].air.clone()).collect::<Vec<_>>();
+
+        let mut combined = GasMixture::from_empty(num::TCMB, num::CELL_VOLUME);
+        for index in &turfs {
+            combined.merge(grid[grid.position_of(*index)].air.clone());
+        }
+        let count = turfs.len() as Real;
+        let (gases, moles): (Vec<_>, Vec<_>) = combined.iter().map(|(gas, moles)| (gas, moles / count)).unzip();
+        let average = GasMixture::from_vecs(gases, moles, combined.temperature, num::CELL_VOLUME).unwrap(); // made from a valid mixture
+
+        for index in &turfs {
+            let pos = grid.position_of(*index);
+            grid[pos].air.copy_from(&average);
+            self.states[*index].atmos_cooldown = 0;
+        }
+        self.groups.get_mut(&id).unwrap().breakdown_cooldown = 0;
+
+        #[cfg(feature = "invariants")]
+        {
+            // merge() doesn't bother equalising temperatures that are really close, so neither does this
+            let slack = before.iter().map(|air| air.heat_capacity() * (air.temperature - average.temperature).abs()).sum::<Real>();
+            let after = turfs.iter().map(|index| &grid[grid.position_of(*index)].air).collect::<Vec<_>>();
+            invariants::enforce(invariants::check_conservation("self_breakdown", &before.iter().collect::<Vec<_>>(), &after, slack));
+        }
+    }
+
+    /// Puts every turf in group `id` to sleep and throws the group away, `dismantle()`.
+    fn dismantle(&mut self, id: usize) {
+        if let Some(group) = self.groups.remove(&id) {
+            for index in group.turfs {
+                self.states[index].excited = false;
+                self.states[index].excited_group = None;
+                self.active.retain(|active| *active != index);
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+pub use self::{
+    turf::{Direction, Position, Turf, TurfKind},
+    grid::TurfGrid,
+    hotspot::{BurningTurf, FireStats, Hotspot},
+    monstermos::EqualizationStats,
+    simulation::{Simulation, Solver, TickReport},
+    zas::{ConnectionEdge, EdgeTarget, Zone, ZasStats},
+    wind::{Movable, Movement, PressureDelta, PressureMovement, SpaceWind},
+    superconduction::SuperconductionStats,
+};
+
+/// turfs, directions and positions
+pub mod turf;
+/// the turf grid everything environmental runs on
+pub mod grid;
+/// LINDA, tgstation's active turf processing
+pub mod linda;
+/// Monstermos, equalizing whole regions at once
+pub mod monstermos;
+/// ZAS, Baystation's zones and edges
+pub mod zas;
+/// hotspots, fires on turfs
+pub mod hotspot;
+/// space wind, pressure differences pushing things around
+pub mod wind;
+/// superconduction, heat getting through walls and windows
+pub mod superconduction;
+/// running air over a turf grid, cycle by cycle
+pub mod simulation;
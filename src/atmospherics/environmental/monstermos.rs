@@ -0,0 +1,362 @@
+use crate::atmospherics::environmental::{Position, TurfGrid};
+use crate::atmospherics::environmental::linda::Linda;
+use crate::atmospherics::environmental::wind::SpaceWind;
+use crate::atmospherics::gases::GasMixture;
+use crate::byond::Real;
+use crate::constants::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::hash_map::Entry;
+
+// Monstermos, the "FEA" equalization a few servers run on top of LINDA. Before LINDA gets to the
+// active turfs, every active turf that's noticeably different from a neighbour flood fills the
+// region it's connected to, works out how many mols every turf in it has to give or take to get
+// to the region's average, and moves all of it along the shortest paths in one go. LINDA's
+// sharing only has to clean up what's left over. If the flood fill finds space, the whole region
+// gets sucked out into it instead: explosive decompression.
+
+/// What one `Monstermos::equalize()` did.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct EqualizationStats {
+    /// Regions that got equalized.
+    pub regions: usize,
+    /// Turfs that gave or took air while equalizing.
+    pub equalized: usize,
+    /// Total mols moved between turfs.
+    pub moles_moved: Real,
+    /// Regions that got sucked out into space.
+    pub decompressions: usize,
+    /// Turfs that got emptied into space.
+    pub decompressed: usize,
+    /// Total mols lost to space.
+    pub moles_lost: Real
+}
+
+/// Monstermos' bookkeeping for one `TurfGrid`: the last cycle each turf got equalized in, so no
+/// turf gets equalized twice in one cycle.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Monstermos {
+    last_cycle: Vec<usize>
+}
+
+/// One flood-filled region while it's being equalized. Turfs are referred to by their slot in
+/// `turfs`, not their grid index.
+struct Region {
+    /// Grid indices of the turfs, in flood fill order.
+    turfs: Vec<usize>,
+    /// Slots of each turf's neighbours inside the region, in `Direction::ALL` order.
+    links: Vec<Vec<usize>>,
+    /// How many mols each turf has over the average, `eq_mole_delta`. Negative if it needs gas.
+    delta: Vec<Real>,
+    /// Mols each turf sends to each neighbour, `eq_transfer_dirs`. Negative if the neighbour
+    /// sends them to it instead.
+    transfer: Vec<BTreeMap<usize, Real>>
+}
+
+impl Region {
+    /// `adjust_eq_movement()`: `from` sends `amount` more to `to`.
+    fn adjust(&mut self, from: usize, to: usize, amount: Real) {
+        *self.transfer[from].entry(to).or_insert(0.0) += amount;
+        *self.transfer[to].entry(from).or_insert(0.0) -= amount;
+    }
+
+    fn givers(&self) -> Vec<usize> {
+        (0..self.turfs.len()).filter(|slot| self.delta[*slot] > 0.0).collect()
+    }
+
+    fn takers(&self) -> Vec<usize> {
+        (0..self.turfs.len()).filter(|slot| self.delta[*slot] < 0.0).collect()
+    }
+
+    /// The O(n log n) pass that can't always finish the job: in order from the turf with the most
+    /// to give, every giver splits what it has over its neighbours that haven't had their turn.
+    fn fast_pass(&mut self) {
+        let mut order = (0..self.turfs.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| self.delta[*b].partial_cmp(&self.delta[*a]).unwrap_or(std::cmp::Ordering::Equal));
+        let mut done = vec![false; self.turfs.len()];
+        for slot in order {
+            done[slot] = true;
+            if self.delta[slot] <= 0.0 {
+                continue;
+            }
+            let eligible = self.links[slot].iter().copied().filter(|next| !done[*next]).collect::<Vec<_>>();
+            if eligible.is_empty() {
+                continue;
+            }
+            let moles_to_move = self.delta[slot] / eligible.len() as Real;
+            for next in eligible {
+                self.adjust(slot, next, moles_to_move);
+                self.delta[slot] -= moles_to_move;
+                self.delta[next] += moles_to_move;
+            }
+        }
+    }
+
+    /// The O(n^2) pass that always finishes the job: every turf in `sources` (all givers, or all
+    /// takers) searches outward for turfs on the other side until it's settled up, then the
+    /// transfers get passed back along the search tree.
+    fn slow_pass(&mut self, sources: Vec<usize>, giving: bool) {
+        let count = self.turfs.len();
+        let mut parent = vec![None; count];
+        let mut amount = vec![0.0; count];
+        let mut seen = vec![usize::MAX; count];
+        for source in sources {
+            parent[source] = None;
+            amount[source] = 0.0;
+            seen[source] = source;
+            let mut queue = vec![source];
+            let mut i = 0;
+            while i < queue.len() && self.needs_settling(source, giving) {
+                let current = queue[i];
+                i += 1;
+                for next in self.links[current].clone() {
+                    if !self.needs_settling(source, giving) {
+                        break;
+                    }
+                    if seen[next] == source {
+                        continue;
+                    }
+                    seen[next] = source;
+                    queue.push(next);
+                    parent[next] = Some(current);
+                    amount[next] = 0.0;
+                    if giving && self.delta[next] < 0.0 {
+                        // this turf needs gas, and the giver's got some
+                        let moved = (-self.delta[next]).min(self.delta[source]);
+                        amount[next] -= moved;
+                        self.delta[next] += moved;
+                        self.delta[source] -= moved;
+                    } else if !giving && self.delta[next] > 0.0 {
+                        // this turf has gas, and the taker wants it
+                        let moved = self.delta[next].min(-self.delta[source]);
+                        amount[next] += moved;
+                        self.delta[next] -= moved;
+                        self.delta[source] += moved;
+                    }
+                }
+            }
+            for slot in queue.into_iter().rev() {
+                if let Some(up) = parent[slot] {
+                    if amount[slot] != 0.0 {
+                        self.adjust(slot, up, amount[slot]);
+                        amount[up] += amount[slot];
+                        amount[slot] = 0.0;
+                    }
+                }
+            }
+        }
+    }
+
+    fn needs_settling(&self, source: usize, giving: bool) -> bool {
+        if giving { self.delta[source] > 0.0 } else { self.delta[source] < 0.0 }
+    }
+
+    /// `finalize_eq()`: actually moves the air turf `slot` is sending out. If it doesn't have
+    /// enough yet, the neighbours sending air to it go first. Returns the mols moved.
+    fn finalize(&mut self, grid: &mut TurfGrid, wind: &mut SpaceWind, done: &mut Vec<bool>, slot: usize) -> Real {
+        if done[slot] {
+            return 0.0;
+        }
+        done[slot] = true;
+        let mut moved = 0.0;
+        let pos = grid.position_of(self.turfs[slot]);
+        for (next, amount) in self.transfer[slot].clone() {
+            if amount <= 0.0 {
+                continue;
+            }
+            if grid[pos].air.total_moles() < amount {
+                for (giver, incoming) in self.transfer[slot].clone() {
+                    if incoming < 0.0 {
+                        moved += self.finalize(grid, wind, done, giver);
+                    }
+                }
+            }
+            self.transfer[next].insert(slot, 0.0);
+            let next_pos = grid.position_of(self.turfs[next]);
+            let (ours, theirs) = grid.pair_mut(pos, next_pos).unwrap(); // neighbours are never the same turf
+            let removed = ours.air.remove(amount).unwrap(); // amount is positive
+            moved += removed.total_moles();
+            theirs.air.merge(removed);
+            let direction = pos.direction_to(next_pos).unwrap(); // same as above
+            wind.consider_pressure_difference(grid, pos, direction, amount);
+        }
+        moved
+    }
+}
+
+impl Monstermos {
+    /// Creates Monstermos' state for `grid`.
+    pub fn new(grid: &TurfGrid) -> Monstermos {
+        Monstermos {
+            last_cycle: vec![0; grid.len()]
+        }
+    }
+
+    /// Runs one cycle of equalization (`process_turf_equalize()`), starting from every turf
+    /// that's active in `linda`. Turfs that move air get woken up so LINDA can finish the job.
+    /// Run it right before `Linda::process()` with the same `fire_count` and `wind`. The mols
+    /// every turf sends out go into `wind` as its pressure difference, same as tgstation.
+    pub fn equalize(&mut self, grid: &mut TurfGrid, linda: &mut Linda, wind: &mut SpaceWind, fire_count: usize) -> EqualizationStats {
+        let mut stats = EqualizationStats::default();
+        for pos in linda.active_turfs(grid) {
+            let index = grid.index_of(pos).unwrap(); // active turfs are always on the grid
+            if self.last_cycle[index] < fire_count {
+                self.equalize_pressure_in_zone(grid, linda, wind, index, fire_count, &mut stats);
+            }
+        }
+        stats
+    }
+
+    /// `equalize_pressure_in_zone()` for the region the turf at `index` is in.
+    fn equalize_pressure_in_zone(&mut self, grid: &mut TurfGrid, linda: &mut Linda, wind: &mut SpaceWind, index: usize, fire_count: usize, stats: &mut EqualizationStats) {
+        self.last_cycle[index] = fire_count;
+        let pos = grid.position_of(index);
+        let starting_moles = grid[pos].air.total_moles();
+        // if there's no pressure difference, there's nothing to do
+        let run_monstermos = grid.adjacent(pos)
+                                 .iter()
+                                 .any(|(_, next)| (grid[*next].air.total_moles() - starting_moles).abs() > num::MINIMUM_MOLES_DELTA_TO_MOVE);
+        if !run_monstermos {
+            return;
+        }
+
+        let mut turfs = vec![index];
+        let mut slots = HashMap::new();
+        slots.insert(index, 0);
+        let mut i = 0;
+        while i < turfs.len() && i < num::MONSTERMOS_HARD_TURF_LIMIT {
+            for (_, next) in grid.adjacent(grid.position_of(turfs[i])) {
+                if grid[next].is_space() {
+                    // someone opened an airlock to space
+                    self.explosively_depressurize(grid, linda, wind, index, fire_count, stats);
+                    return;
+                }
+                let next_index = grid.index_of(next).unwrap(); // adjacent() only hands out turfs on the grid
+                if let Entry::Vacant(slot) = slots.entry(next_index) {
+                    slot.insert(turfs.len());
+                    turfs.push(next_index);
+                }
+            }
+            i += 1;
+        }
+        // anything past the limit gets left for LINDA, or for its own region
+        if turfs.len() > num::MONSTERMOS_TURF_LIMIT {
+            for far in turfs.drain(num::MONSTERMOS_TURF_LIMIT..) {
+                slots.remove(&far);
+            }
+        }
+
+        let links = turfs.iter()
+                         .map(|turf| grid.adjacent(grid.position_of(*turf))
+                                         .iter()
+                                         .filter_map(|(_, next)| slots.get(&grid.index_of(*next).unwrap()).copied())
+                                         .collect::<Vec<_>>())
+                         .collect::<Vec<_>>();
+        let moles = turfs.iter().map(|turf| grid[grid.position_of(*turf)].air.total_moles()).collect::<Vec<_>>();
+        let average_moles = moles.iter().sum::<Real>() / turfs.len() as Real;
+        let mut region = Region {
+            delta: moles.iter().map(|turf_moles| turf_moles - average_moles).collect(),
+            transfer: vec![BTreeMap::new(); turfs.len()],
+            turfs,
+            links
+        };
+        for turf in &region.turfs {
+            self.last_cycle[*turf] = fire_count;
+        }
+
+        // try spreading it around cheaply first; even when that doesn't work, it makes the next
+        // part quicker
+        let log_n = (region.turfs.len() as Real).log2();
+        if region.givers().len() as Real > log_n && region.takers().len() as Real > log_n {
+            region.fast_pass();
+        }
+        let (givers, takers) = (region.givers(), region.takers());
+        // go from whichever side's smaller, to stay away from O(n^2) where possible
+        if givers.len() < takers.len() {
+            region.slow_pass(givers, true);
+        } else {
+            region.slow_pass(takers, false);
+        }
+
+        let moving = (0..region.turfs.len()).filter(|slot| region.transfer[*slot].values().any(|amount| *amount != 0.0))
+                                            .collect::<Vec<_>>();
+        let mut done = vec![false; region.turfs.len()];
+        for slot in 0..region.turfs.len() {
+            stats.moles_moved += region.finalize(grid, wind, &mut done, slot);
+        }
+        stats.regions += 1;
+        stats.equalized += moving.len();
+        for slot in moving {
+            linda.activate(grid, grid.position_of(region.turfs[slot]));
+        }
+    }
+
+    /// `explosively_depressurize()`: empties every turf connected to the turf at `index` into
+    /// the space it's connected to. Every turf's pressure difference is all the mols that go
+    /// through it on their way out, pointing at the next turf along and the space turf at the end.
+    fn explosively_depressurize(&mut self, grid: &mut TurfGrid, linda: &mut Linda, wind: &mut SpaceWind, index: usize, fire_count: usize, stats: &mut EqualizationStats) {
+        let mut turfs = vec![index];
+        let mut in_region = HashSet::new();
+        in_region.insert(index);
+        let mut space_turfs = Vec::new();
+        let mut i = 0;
+        while i < turfs.len() {
+            let turf = turfs[i];
+            i += 1;
+            self.last_cycle[turf] = fire_count;
+            let pos = grid.position_of(turf);
+            if grid[pos].is_space() {
+                space_turfs.push(turf);
+                continue;
+            }
+            if i > num::MONSTERMOS_HARD_TURF_LIMIT {
+                continue;
+            }
+            for (_, next) in grid.adjacent(pos) {
+                let next_index = grid.index_of(next).unwrap(); // adjacent() only hands out turfs on the grid
+                if in_region.insert(next_index) {
+                    turfs.push(next_index);
+                }
+            }
+        }
+
+        // work backwards from space, so everything gets sucked out along the shortest way there.
+        // Each turf remembers the turf it was reached from and the space turf it leads to.
+        let mut progression_order = space_turfs.iter()
+                                               .map(|turf| (grid.position_of(*turf), None, grid.position_of(*turf)))
+                                               .collect::<Vec<(Position, Option<Position>, Position)>>();
+        let mut queued = space_turfs.into_iter().collect::<HashSet<_>>();
+        let mut i = 0;
+        while i < progression_order.len() {
+            let (pos, _, space) = progression_order[i];
+            for (_, next) in grid.adjacent(pos) {
+                let next_index = grid.index_of(next).unwrap(); // adjacent() only hands out turfs on the grid
+                if in_region.contains(&next_index) && !grid[next].is_space() && queued.insert(next_index) {
+                    progression_order.push((next, Some(pos), space));
+                }
+            }
+            i += 1;
+        }
+
+        stats.decompressions += 1;
+        // mols coming through each turf from further in, `curr_transfer_amount`
+        let mut passing_through: HashMap<Position, Real> = HashMap::new();
+        for (pos, towards, space) in progression_order.into_iter().rev() {
+            let towards = match towards {
+                Some(towards) => towards,
+                None => continue // space itself
+            };
+            let air = &mut grid[pos].air;
+            let moles = air.total_moles();
+            stats.moles_lost += moles;
+            air.copy_from(&GasMixture::from_empty(air.temperature, air.volume));
+            stats.decompressed += 1;
+
+            let amount = passing_through.remove(&pos).unwrap_or(0.0) + moles;
+            *passing_through.entry(towards).or_insert(0.0) += amount;
+            let direction = pos.direction_to(towards).unwrap(); // turfs only get reached from their neighbours
+            wind.consider_pressure_difference(grid, pos, direction, amount);
+            wind.set_target(grid, pos, space);
+            linda.activate(grid, pos);
+        }
+    }
+}
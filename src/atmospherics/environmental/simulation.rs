@@ -0,0 +1,335 @@
+use crate::atmospherics::environmental::{Position, Turf, TurfGrid};
+use crate::atmospherics::environmental::hotspot::{FireStats, Hotspots};
+use crate::atmospherics::environmental::linda::{Linda, LindaStats};
+use crate::atmospherics::environmental::monstermos::{EqualizationStats, Monstermos};
+use crate::atmospherics::environmental::superconduction::{SuperconductionStats, Superconductivity};
+use crate::atmospherics::environmental::wind::{Movable, PressureDelta, PressureMovement, SpaceWind};
+use crate::atmospherics::environmental::zas::{Zas, ZasStats};
+#[cfg(feature = "invariants")]
+use crate::atmospherics::gases::invariants;
+use crate::byond::Real;
+use crate::constants::*;
+use crate::errors::AtmosError;
+
+/// Which algorithm moves air between turfs.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum Solver {
+    /// Plain LINDA, what tgstation runs.
+    #[default]
+    Linda,
+    /// Monstermos equalization first, then LINDA for whatever's left. What servers with
+    /// "FEA" atmos run; breaches empty whole rooms in a tick.
+    Monstermos,
+    /// Baystation's zones and edges instead of per-turf sharing. LINDA doesn't run at all.
+    Zas
+}
+
+/// What one `Simulation::tick()` did.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TickReport {
+    /// Which cycle this was, `SSair.times_fired`. The first tick is cycle 1.
+    pub cycle: usize,
+    /// What happened to the active turfs.
+    pub linda: LindaStats,
+    /// What Monstermos moved. All zeroes unless the solver's `Solver::Monstermos`.
+    pub equalization: EqualizationStats,
+    /// What ZAS did. All zeroes unless the solver's `Solver::Zas`.
+    pub zas: ZasStats,
+    /// Which turfs are on fire, and what the fires did.
+    pub fires: FireStats,
+    /// Every turf air left this tick, with the biggest pressure difference it saw. Always empty
+    /// under `Solver::Zas`.
+    pub wind: Vec<PressureDelta>,
+    /// What superconduction did.
+    pub superconduction: SuperconductionStats,
+    /// How many turfs are still active after the tick.
+    pub active_turfs: usize
+}
+
+/// A turf grid and everything needed to run air over it, one `SSair` cycle per `tick()`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Position, Simulation, Turf, TurfGrid};
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::constants::gases;
+/// // a 5x1 corridor, empty except for a tile's worth of oxygen at the west end
+/// let mut grid = TurfGrid::new(5, 1, 1, Turf::floor(GasMixture::from_empty(293.15, 2500)));
+/// grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 2500).unwrap();
+///
+/// let mut sim = Simulation::new(grid);
+/// sim.activate(Position::new(0, 0, 0));
+/// sim.run(200);
+/// // it spread out and everything went back to sleep
+/// assert!(sim.active_turfs().is_empty());
+/// assert!(sim.grid[Position::new(4, 0, 0)].air.total_moles() > 15.0);
+/// assert!((sim.grid.total_moles() - 100.0).abs() < 0.001);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Simulation<'a> {
+    /// The turfs. Changing air directly is fine, but `activate()` the turf afterwards or nothing
+    /// will notice. Layout changes should go through `replace()` so the neighbours wake up.
+    pub grid: TurfGrid<'a>,
+    solver: Solver,
+    linda: Linda,
+    monstermos: Monstermos,
+    /// Only built for `Solver::Zas`.
+    zas: Option<Zas<'a>>,
+    hotspots: Hotspots,
+    wind: SpaceWind,
+    superconductivity: Superconductivity,
+    times_fired: usize
+}
+
+impl<'a> Simulation<'a> {
+    /// Creates a simulation over `grid` running plain LINDA, with every turf asleep.
+    pub fn new(grid: TurfGrid<'a>) -> Simulation<'a> {
+        Simulation::with_solver(grid, Solver::Linda)
+    }
+
+    /// Creates a simulation over `grid` running `solver`, with every turf asleep.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Position, Simulation, Solver, Turf, TurfGrid};
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// // a 10x1 corridor with all of its air at the west end
+    /// let mut grid = TurfGrid::new(10, 1, 1, Turf::floor(GasMixture::from_empty(293.15, 2500)));
+    /// grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![1000.0], 293.15, 2500).unwrap();
+    ///
+    /// let mut sim = Simulation::with_solver(grid, Solver::Monstermos);
+    /// sim.activate(Position::new(0, 0, 0));
+    /// sim.tick();
+    /// // one tick later, the far end's already got its share
+    /// assert!((sim.grid[Position::new(9, 0, 0)].air.total_moles() - 100.0).abs() < 0.01);
+    /// ```
+    pub fn with_solver(grid: TurfGrid<'a>, solver: Solver) -> Simulation<'a> {
+        Simulation {
+            solver,
+            linda: Linda::new(&grid),
+            monstermos: Monstermos::new(&grid),
+            zas: if solver == Solver::Zas { Some(Zas::new(&grid)) } else { None },
+            hotspots: Hotspots::new(&grid),
+            wind: SpaceWind::new(&grid),
+            superconductivity: Superconductivity::new(&grid),
+            grid,
+            times_fired: 0
+        }
+    }
+
+    /// Which algorithm this simulation moves air with.
+    pub fn solver(&self) -> Solver {
+        self.solver
+    }
+
+    /// Cycles run so far.
+    pub fn times_fired(&self) -> usize {
+        self.times_fired
+    }
+
+    /// LINDA's state, for poking at.
+    pub fn linda(&self) -> &Linda {
+        &self.linda
+    }
+
+    /// The zones and edges, if the solver's `Solver::Zas`.
+    pub fn zas(&self) -> Option<&Zas<'a>> {
+        self.zas.as_ref()
+    }
+
+    /// Every fire on the grid.
+    pub fn hotspots(&self) -> &Hotspots {
+        &self.hotspots
+    }
+
+    /// Every turf conducting heat.
+    pub fn superconductivity(&self) -> &Superconductivity {
+        &self.superconductivity
+    }
+
+    /// The pressure differences from the last tick.
+    pub fn wind(&self) -> &SpaceWind {
+        &self.wind
+    }
+
+    /// What the last tick's space wind would do to `movable` on the turf at `pos`, or `None` if
+    /// it stays put. See `SpaceWind::experience_pressure_difference()`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Direction, Position, Simulation, Solver, Turf, TurfGrid};
+    /// use libatmos::atmospherics::environmental::wind::{Movable, Movement};
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// // a 10x1 corridor full of air, with space just past the east end
+    /// let air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![21.6, 81.2], 293.15, 2500).unwrap();
+    /// let mut grid = TurfGrid::new(11, 1, 1, Turf::floor(air));
+    /// grid.replace(Position::new(10, 0, 0), Turf::space()).unwrap();
+    ///
+    /// let mut sim = Simulation::with_solver(grid, Solver::Monstermos);
+    /// sim.activate(Position::new(9, 0, 0));
+    /// sim.tick();
+    /// // the east end gets everyone's air rushing past, and throws you into space
+    /// let push = sim.experience_pressure_difference(Position::new(9, 0, 0), &Movable::default()).unwrap();
+    /// assert_eq!(push.direction, Direction::East);
+    /// assert!(matches!(push.movement, Movement::Thrown { target, .. } if target == Position::new(10, 0, 0)));
+    /// ```
+    pub fn experience_pressure_difference(&self, pos: Position, movable: &Movable) -> Option<PressureMovement> {
+        self.wind.experience_pressure_difference(&self.grid, pos, movable)
+    }
+
+    /// `hotspot_expose()`: something `temperature` hot touches `volume` liters of the air on the
+    /// turf at `pos`, which might set it on fire, 25 times that big to start with. Returns true
+    /// if the turf's on fire afterwards. Under ZAS, whatever a fire heats up gets spread over its
+    /// whole zone next cycle, so it takes a lot more fuel for a fire to get going.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Position, Simulation, Turf, TurfGrid};
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// // a room full of plasma and oxygen
+    /// let air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![20.0, 80.0], 293.15, 2500).unwrap();
+    /// let mut sim = Simulation::new(TurfGrid::new(5, 5, 1, Turf::floor(air)));
+    ///
+    /// assert!(!sim.hotspot_expose(Position::new(2, 2, 0), 300.0, 5.0)); // not hot enough
+    /// assert!(sim.hotspot_expose(Position::new(2, 2, 0), 1000.0, 5.0)); // a welder
+    /// let reports = sim.run(30);
+    /// // it spread
+    /// assert!(reports.iter().any(|report| report.fires.burning.len() > 1));
+    /// assert!(sim.grid[Position::new(0, 0, 0)].air.get_moles(&gases::CO2).unwrap_or(0.0) > 0.0);
+    /// ```
+    pub fn hotspot_expose(&mut self, pos: Position, temperature: Real, volume: Real) -> bool {
+        let burning = self.hotspots.expose(&mut self.grid, pos, temperature, volume);
+        if burning {
+            self.keep_awake(pos);
+        }
+        burning
+    }
+
+    /// Puts out the fire on the turf at `pos`. Returns false if there wasn't one.
+    pub fn extinguish(&mut self, pos: Position) -> bool {
+        self.hotspots.extinguish(&self.grid, pos)
+    }
+
+    /// Keeps a burning turf from going to sleep.
+    fn keep_awake(&mut self, pos: Position) {
+        match self.zas.as_mut() {
+            Some(zas) => zas.activate(&self.grid, pos),
+            None => self.linda.keep_awake(&self.grid, pos)
+        };
+    }
+
+    /// Wakes up the turf at `pos` (under ZAS, its zone). Returns false if it's a wall, space or
+    /// off the grid.
+    pub fn activate(&mut self, pos: Position) -> bool {
+        match self.zas.as_mut() {
+            Some(zas) => zas.activate(&self.grid, pos),
+            None => self.linda.activate(&self.grid, pos)
+        }
+    }
+
+    /// Wakes up the turf at `pos` without breaking up its excited group, like superconduction
+    /// does to air it heats up.
+    fn wake(&mut self, pos: Position) {
+        match self.zas.as_mut() {
+            Some(zas) => zas.activate(&self.grid, pos),
+            None => self.linda.wake(&self.grid, pos)
+        };
+    }
+
+    /// Wakes up every turf that can be woken up.
+    pub fn activate_all(&mut self) {
+        for pos in self.grid.positions().collect::<Vec<_>>() {
+            self.activate(pos);
+        }
+    }
+
+    /// Returns true if the turf at `pos` is active (under ZAS, if its zone's awake).
+    pub fn is_active(&self, pos: Position) -> bool {
+        match self.zas.as_ref() {
+            Some(zas) => zas.is_active(&self.grid, pos),
+            None => self.linda.is_active(&self.grid, pos)
+        }
+    }
+
+    /// Every active turf, in the order they got activated (LINDA processes them back to front).
+    /// Under ZAS, every turf in an awake zone.
+    pub fn active_turfs(&self) -> Vec<Position> {
+        match self.zas.as_ref() {
+            Some(zas) => zas.active_turfs(&self.grid),
+            None => self.linda.active_turfs(&self.grid)
+        }
+    }
+
+    /// Puts `turf` at `pos` and wakes it and everything around it up, like building or
+    /// deconstructing a wall. Returns the turf that was there.
+    pub fn replace(&mut self, pos: Position, turf: Turf<'a>) -> Result<Turf<'a>, AtmosError> {
+        let old = self.grid.replace(pos, turf)?;
+        self.linda.deactivate(&self.grid, pos);
+        self.wake_around(pos);
+        Ok(old)
+    }
+
+    /// Wakes up the turf at `pos` and all of its neighbours.
+    fn wake_around(&mut self, pos: Position) {
+        self.activate(pos);
+        for (_, neighbour) in self.grid.neighbours(pos) {
+            self.activate(neighbour);
+        }
+    }
+
+    /// Runs one `SSair` cycle.
+    pub fn tick(&mut self) -> TickReport {
+        self.times_fired += 1;
+        let mut report = TickReport { cycle: self.times_fired, ..TickReport::default() };
+        self.wind.clear();
+        match self.zas.as_mut() {
+            Some(zas) => {
+                // ZAS has nothing like LINDA's active turfs, so every hot turf in a zone that was
+                // awake gets a go at superconducting
+                let awake = zas.active_turfs(&self.grid);
+                report.zas = zas.process(&mut self.grid);
+                for pos in awake {
+                    if self.grid[pos].air.temperature > num::MINIMUM_TEMPERATURE_START_SUPERCONDUCTION {
+                        self.superconductivity.consider(&self.grid, pos, true);
+                    }
+                }
+            },
+            None => {
+                if self.solver == Solver::Monstermos {
+                    report.equalization = self.monstermos.equalize(&mut self.grid, &mut self.linda, &mut self.wind, self.times_fired);
+                }
+                report.linda = self.linda.process(&mut self.grid, &mut self.wind, &mut self.superconductivity, self.times_fired);
+            }
+        }
+        report.wind = self.wind.deltas();
+        report.fires = self.hotspots.process(&mut self.grid, &self.linda, self.times_fired);
+        for burning in &report.fires.burning {
+            self.keep_awake(burning.position);
+        }
+        report.superconduction = self.superconductivity.process(&mut self.grid, self.times_fired);
+        for pos in report.superconduction.touched.clone() {
+            self.wake(pos);
+        }
+
+        #[cfg(feature = "invariants")]
+        for (pos, turf) in self.grid.iter() {
+            invariants::enforce(invariants::check_mixture(&format!("tick {} at {}", self.times_fired, pos), &turf.air));
+        }
+
+        report.active_turfs = self.active_turfs().len();
+        report
+    }
+
+    /// Runs `cycles` cycles, stopping early once every turf's asleep. Returns every tick's report.
+    pub fn run(&mut self, cycles: usize) -> Vec<TickReport> {
+        let mut reports = Vec::new();
+        for _ in 0..cycles {
+            let report = self.tick();
+            let done = report.active_turfs == 0;
+            reports.push(report);
+            if done {
+                break;
+            }
+        }
+        reports
+    }
+}
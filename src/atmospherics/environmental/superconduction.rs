@@ -0,0 +1,247 @@
+use crate::atmospherics::environmental::{Direction, Position, TurfGrid};
+use crate::byond::Real;
+use crate::constants::*;
+
+// Superconduction, ported from tgstation. Air only moves between turfs that are open towards
+// each other, but heat also gets through the sides that aren't: hot enough turfs conduct heat
+// into whatever's on their closed sides (walls, or the turf behind a window), which can heat
+// those up enough to conduct in turn. Every turf doing this also radiates some of its own heat
+// off into space and shares what's left with the air on it. Turfs stop once they cool down.
+// Walls with no `thermal_conductivity`, like tgstation's, never let anything through.
+
+/// What one `Superconductivity::process()` did.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SuperconductionStats {
+    /// Turfs that conducted heat.
+    pub conducted: usize,
+    /// Turfs that got too cold to keep going.
+    pub stopped: usize,
+    /// Open turfs whose air got heated or cooled, in the order it happened. Their air needs
+    /// waking up, `SSair.add_to_active()`.
+    pub touched: Vec<Position>
+}
+
+/// Every turf conducting heat on a `TurfGrid`, `SSair.active_super_conductivity`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Position, Turf, TurfGrid};
+/// use libatmos::atmospherics::environmental::superconduction::Superconductivity;
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::constants::gases;
+/// // a hot tile next to a wall that conducts a bit
+/// let mut grid = TurfGrid::from_fn(2, 1, 1, |pos| match pos.x {
+///     0 => Turf::floor(GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 1000.0, 2500).unwrap()),
+///     _ => { let mut wall = Turf::wall(); wall.thermal_conductivity = 0.05; wall }
+/// });
+/// let mut superconductivity = Superconductivity::new(&grid);
+///
+/// assert!(superconductivity.consider(&grid, Position::new(0, 0, 0), true));
+/// assert!(!superconductivity.consider(&grid, Position::new(1, 0, 0), true)); // still cold
+/// let stats = superconductivity.process(&mut grid, 1);
+/// assert_eq!(stats.conducted, 1);
+/// // the wall warmed up, and the air cooled down
+/// assert!(grid[Position::new(1, 0, 0)].temperature > 293.15);
+/// assert!(grid[Position::new(0, 0, 0)].air.temperature < 1000.0);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Superconductivity {
+    active: Vec<bool>,
+    /// Indices of the conducting turfs, in the order they started.
+    order: Vec<usize>,
+    /// Last cycle each turf got archived in.
+    archived_cycle: Vec<usize>
+}
+
+impl Superconductivity {
+    /// Creates an empty superconduction list for `grid`.
+    pub fn new(grid: &TurfGrid) -> Superconductivity {
+        Superconductivity {
+            active: vec![false; grid.len()],
+            order: Vec::new(),
+            archived_cycle: vec![0; grid.len()]
+        }
+    }
+
+    /// Returns true if the turf at `pos` is conducting heat.
+    pub fn is_superconducting(&self, grid: &TurfGrid, pos: Position) -> bool {
+        grid.index_of(pos).is_some_and(|index| self.active[index])
+    }
+
+    /// Every turf conducting heat, in the order they started.
+    pub fn superconducting(&self, grid: &TurfGrid) -> Vec<Position> {
+        self.order.iter().map(|index| grid.position_of(*index)).collect()
+    }
+
+    /// Number of turfs conducting heat.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns true if no turfs are conducting heat.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// `consider_superconductivity()`: starts the turf at `pos` conducting heat if it's hot
+    /// enough, `MINIMUM_TEMPERATURE_START_SUPERCONDUCTION` if it's `starting` off on its own,
+    /// `MINIMUM_TEMPERATURE_FOR_SUPERCONDUCTION` if a neighbour's passing heat on to it. Open
+    /// turfs go by their air's temperature and need enough air to matter, walls go by their own.
+    /// Space and anything that doesn't conduct never start. Returns true if the turf's
+    /// conducting afterwards.
+    pub fn consider(&mut self, grid: &TurfGrid, pos: Position, starting: bool) -> bool {
+        let (index, turf) = match (grid.index_of(pos), grid.get(pos)) {
+            (Some(index), Some(turf)) => (index, turf),
+            _ => return false
+        };
+        if turf.is_space() || turf.thermal_conductivity == 0.0 {
+            return false;
+        }
+        let threshold = if starting { num::MINIMUM_TEMPERATURE_START_SUPERCONDUCTION } else { num::MINIMUM_TEMPERATURE_FOR_SUPERCONDUCTION };
+        let hot_enough = if turf.blocks_air() {
+            turf.temperature >= threshold
+        } else {
+            turf.air.temperature >= threshold && turf.air.heat_capacity() >= num::M_CELL_WITH_RATIO
+        };
+        if !hot_enough {
+            return false;
+        }
+        if !self.active[index] {
+            self.active[index] = true;
+            self.order.push(index);
+        }
+        true
+    }
+
+    /// Runs every conducting turf for a cycle (`process_super_conductivity()`): each one
+    /// conducts through its closed sides, radiates to space, and shares with its own air, then
+    /// stops if it's gotten too cold. Turfs that start conducting along the way wait for the
+    /// next cycle. `fire_count` has to go up by one each call, starting at 1.
+    pub fn process(&mut self, grid: &mut TurfGrid, fire_count: usize) -> SuperconductionStats {
+        let mut stats = SuperconductionStats::default();
+        for index in self.order.clone() {
+            stats.conducted += 1;
+            if !self.super_conduct(grid, index, fire_count, &mut stats.touched) {
+                self.active[index] = false;
+                self.order.retain(|conducting| *conducting != index);
+                stats.stopped += 1;
+            }
+        }
+        stats
+    }
+
+    fn archive(&mut self, grid: &mut TurfGrid, pos: Position, fire_count: usize) {
+        let index = grid.index_of(pos).unwrap(); // only ever called with turfs on the grid
+        if self.archived_cycle[index] < fire_count {
+            grid[pos].archive();
+            self.archived_cycle[index] = fire_count;
+        }
+    }
+
+    /// `super_conduct()` for one turf. Returns false if it should stop conducting.
+    fn super_conduct(&mut self, grid: &mut TurfGrid, index: usize, fire_count: usize, touched: &mut Vec<Position>) -> bool {
+        let pos = grid.position_of(index);
+        self.archive(grid, pos, fire_count);
+        for (_, neighbour) in conductivity_directions(grid, pos) {
+            if grid[neighbour].thermal_conductivity == 0.0 {
+                continue;
+            }
+            self.archive(grid, neighbour, fire_count);
+            neighbour_conduct_with_src(grid, neighbour, pos, touched);
+            self.consider(grid, neighbour, false);
+        }
+        radiate_to_spess(grid, pos);
+
+        // finish_superconduction(): share with our own air, and see if we're still hot enough
+        let turf = &mut grid[pos];
+        if turf.blocks_air() {
+            return turf.temperature >= num::MINIMUM_TEMPERATURE_FOR_SUPERCONDUCTION;
+        }
+        turf.temperature = turf.air.temperature_share_solid(turf.temperature, turf.heat_capacity, turf.thermal_conductivity);
+        touch(touched, pos);
+        turf.air.temperature >= num::MINIMUM_TEMPERATURE_FOR_SUPERCONDUCTION
+    }
+}
+
+/// `conductivity_directions()`: the neighbours of the turf at `pos` that air can't get to, so
+/// heat has to conduct instead. A wall conducts to everything around it. Like tgstation, heat
+/// only conducts sideways, never between z-levels.
+fn conductivity_directions(grid: &TurfGrid, pos: Position) -> Vec<(Direction, Position)> {
+    let adjacent = if grid[pos].blocks_air() { Vec::new() } else { grid.adjacent(pos) };
+    grid.neighbours(pos)
+        .into_iter()
+        .filter(|neighbour| !neighbour.0.is_vertical() && !adjacent.contains(neighbour))
+        .collect()
+}
+
+/// `neighbor_conduct_with_src()`: the turf at `neighbour` gets heat from, or loses it to, the
+/// conducting turf at `pos`.
+fn neighbour_conduct_with_src(grid: &mut TurfGrid, neighbour: Position, pos: Position, touched: &mut Vec<Position>) {
+    let (ours, theirs) = grid.pair_mut(pos, neighbour).unwrap(); // neighbours are never the same turf
+    if theirs.is_space() {
+        // space soaks up whatever it gets without warming up
+        if ours.blocks_air() {
+            let temperature_archived = ours.temperature_archived();
+            let mut space_temperature = num::TCMB;
+            share_temperature_mutual_solid(&mut ours.temperature, temperature_archived, ours.heat_capacity,
+                                           &mut space_temperature, num::TCMB, theirs.heat_capacity, theirs.thermal_conductivity);
+        } else {
+            ours.air.temperature_share_solid(num::TCMB, theirs.heat_capacity, num::WINDOW_HEAT_TRANSFER_COEFFICIENT);
+            touch(touched, pos);
+        }
+        return;
+    }
+    match (ours.blocks_air(), theirs.blocks_air()) {
+        (false, false) => {
+            // across a window. tgstation uses WALL_HEAT_TRANSFER_COEFFICIENT here, which is 0
+            theirs.air.temperature_share(&mut ours.air, num::WINDOW_HEAT_TRANSFER_COEFFICIENT);
+            touch(touched, neighbour);
+            touch(touched, pos);
+        },
+        (true, false) => {
+            ours.temperature = theirs.air.temperature_share_solid(ours.temperature, ours.heat_capacity, ours.thermal_conductivity);
+            touch(touched, neighbour);
+        },
+        (false, true) => {
+            theirs.temperature = ours.air.temperature_share_solid(theirs.temperature, theirs.heat_capacity, ours.thermal_conductivity);
+            touch(touched, pos);
+        },
+        (true, true) => {
+            // at the conducting turf's own conductivity, `other.share_temperature_mutual_solid(src, other.thermal_conductivity)`
+            let (our_archived, their_archived) = (ours.temperature_archived(), theirs.temperature_archived());
+            share_temperature_mutual_solid(&mut ours.temperature, our_archived, ours.heat_capacity,
+                                           &mut theirs.temperature, their_archived, theirs.heat_capacity, ours.thermal_conductivity);
+        }
+    }
+}
+
+fn touch(touched: &mut Vec<Position>, pos: Position) {
+    if !touched.contains(&pos) {
+        touched.push(pos);
+    }
+}
+
+/// `share_temperature_mutual_solid()`: conducts heat between two solid things, going by their
+/// archived temperatures.
+fn share_temperature_mutual_solid(temperature: &mut Real, temperature_archived: Real, heat_capacity: Real,
+                                  sharer_temperature: &mut Real, sharer_temperature_archived: Real, sharer_heat_capacity: Real,
+                                  conduction_coefficient: Real) {
+    let delta_temperature = temperature_archived - sharer_temperature_archived;
+    if delta_temperature.abs() > num::MINIMUM_TEMP_DELTA_TO_CONSIDER && heat_capacity > 0.0 && sharer_heat_capacity > 0.0 {
+        let heat = conduction_coefficient * delta_temperature * (heat_capacity * sharer_heat_capacity / (heat_capacity + sharer_heat_capacity));
+        *temperature -= heat / heat_capacity;
+        *sharer_temperature += heat / sharer_heat_capacity;
+    }
+}
+
+/// `radiate_to_spess()`: anything above 0 °C loses some of its own heat to space.
+fn radiate_to_spess(grid: &mut TurfGrid, pos: Position) {
+    let turf = &mut grid[pos];
+    if turf.temperature > num::T0C {
+        let delta_temperature = turf.temperature_archived() - num::TCMB;
+        if turf.heat_capacity > 0.0 && delta_temperature.abs() > num::MINIMUM_TEMP_DELTA_TO_CONSIDER {
+            let heat = turf.thermal_conductivity * delta_temperature
+                * (turf.heat_capacity * num::HEAT_CAPACITY_VACUUM / (turf.heat_capacity + num::HEAT_CAPACITY_VACUUM));
+            turf.temperature -= heat / turf.heat_capacity;
+        }
+    }
+}
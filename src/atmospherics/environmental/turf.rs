@@ -0,0 +1,299 @@
+use crate::atmospherics::gases::GasMixture;
+use crate::byond::Real;
+use crate::constants::*;
+use std::fmt;
+
+/// A direction air can move in, between a turf and the one next to it.
+/// Same numbering as BYOND's direction flags, so `bit()` works as a bitfield.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    /// One z-level up.
+    Up,
+    /// One z-level down.
+    Down
+}
+
+impl Direction {
+    /// Every direction, in the order tgstation's `GLOB.cardinals_multiz` has them. Neighbours
+    /// always get visited in this order, which keeps the simulation deterministic.
+    pub const ALL: [Direction; 6] = [Direction::North, Direction::South, Direction::East, Direction::West, Direction::Up, Direction::Down];
+
+    /// Just the directions on the same z-level, `GLOB.cardinals`.
+    pub const CARDINALS: [Direction; 4] = [Direction::North, Direction::South, Direction::East, Direction::West];
+
+    /// The BYOND direction flag, `NORTH` = 1, `SOUTH` = 2, `EAST` = 4, `WEST` = 8, `UP` = 16,
+    /// `DOWN` = 32.
+    pub fn bit(self) -> u8 {
+        match self {
+            Direction::North => 1,
+            Direction::South => 2,
+            Direction::East => 4,
+            Direction::West => 8,
+            Direction::Up => 16,
+            Direction::Down => 32
+        }
+    }
+
+    /// Returns true for `Up` and `Down`.
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+
+    /// The opposite direction, `turn(dir, 180)`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::Direction;
+    /// assert_eq!(Direction::North.reverse(), Direction::South);
+    /// assert_eq!(Direction::West.reverse(), Direction::East);
+    /// ```
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up
+        }
+    }
+
+    /// How `x`, `y` and `z` change going one step this way. North is +y and up is +z, like in
+    /// BYOND.
+    pub fn offset(self) -> (isize, isize, isize) {
+        match self {
+            Direction::North => (0, 1, 0),
+            Direction::South => (0, -1, 0),
+            Direction::East => (1, 0, 0),
+            Direction::West => (-1, 0, 0),
+            Direction::Up => (0, 0, 1),
+            Direction::Down => (0, 0, -1)
+        }
+    }
+}
+
+/// Where a turf is on a `TurfGrid`. `z` is the z-level.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Default)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+    pub z: usize
+}
+
+impl Position {
+    pub fn new(x: usize, y: usize, z: usize) -> Position {
+        Position { x, y, z }
+    }
+
+    /// The position one step in `direction`, or `None` if that would go below 0. Doesn't know
+    /// how big the grid is; use `TurfGrid::step()` for that.
+    pub fn step(self, direction: Direction) -> Option<Position> {
+        let (dx, dy, dz) = direction.offset();
+        Some(Position {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+            z: self.z.checked_add_signed(dz)?
+        })
+    }
+
+    /// Which way `other` is, if it's one step away. `get_dir()` for neighbours.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Direction, Position};
+    /// let pos = Position::new(1, 1, 0);
+    /// assert_eq!(pos.direction_to(Position::new(1, 2, 0)), Some(Direction::North));
+    /// assert_eq!(pos.direction_to(Position::new(3, 1, 0)), None);
+    /// ```
+    pub fn direction_to(self, other: Position) -> Option<Direction> {
+        Direction::ALL.iter().copied().find(|direction| self.step(*direction) == Some(other))
+    }
+}
+
+impl fmt::Display for Position {
+    /// Formats the position like BYOND's coordinates, "(x, y, z)".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// What sort of turf a turf is, as far as atmos cares.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TurfKind {
+    /// A normal open turf, `/turf/open`, with air that moves around.
+    Floor,
+    /// A turf that blocks air completely, like `/turf/closed/wall`. Its air never moves.
+    Wall,
+    /// Space. Always a vacuum at `TCMB`, no matter how much gets dumped into it.
+    Space,
+    /// A turf with `planetary_atmos`, which keeps getting pulled back to its initial air by the
+    /// planet's atmosphere. Lavaland and the like.
+    Planetary,
+    /// A hole in the floor, `/turf/open/openspace`. Like a floor, except air also moves between
+    /// it and the turf below, if the grid has that z-level linked to this one.
+    OpenSpace
+}
+
+/// A single turf: its air, which sides air can get through, and how it handles heat.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Direction, Turf, TurfKind};
+/// use libatmos::atmospherics::gases::GasMixture;
+/// let mut turf = Turf::floor(GasMixture::from_empty(293.15, 2500));
+/// turf.set_blocked(Direction::North, true); // a window on the north side
+///
+/// assert_eq!(turf.kind(), TurfKind::Floor);
+/// assert!(!turf.is_open(Direction::North));
+/// assert!(turf.is_open(Direction::South));
+/// assert!(!Turf::wall().is_open(Direction::South));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Turf<'a> {
+    /// The air on the turf.
+    pub air: GasMixture<'a>,
+    /// How well heat conducts through the turf, 0 to 1. `thermal_conductivity` in tgstation.
+    pub thermal_conductivity: Real,
+    /// Heat capacity of the turf itself (the floor or wall, not the air), in J/K.
+    pub heat_capacity: Real,
+    /// Temperature of the turf itself, in kelvin. Starts out the same as the air's; only
+    /// superconduction changes it.
+    pub temperature: Real,
+    /// `temperature` as of the last `archive()`.
+    temperature_archived: Real,
+    kind: TurfKind,
+    /// Directions air can't get through, as BYOND direction flags.
+    blocked: u8,
+    /// Air gets through, but ZAS zones don't, like an open airlock. `ZONE_BLOCKED` in Baystation.
+    blocks_zones: bool,
+    /// What the air started as, `initial_gas_mix`. Planetary turfs get pulled back towards it.
+    initial_air: GasMixture<'a>
+}
+
+impl<'a> Turf<'a> {
+    /// Creates a turf of kind `kind` with air `air`, open on every side.
+    pub fn new(kind: TurfKind, air: GasMixture<'a>, thermal_conductivity: Real, heat_capacity: Real) -> Turf<'a> {
+        Turf {
+            initial_air: air.clone(),
+            temperature: air.temperature,
+            temperature_archived: air.temperature,
+            air,
+            thermal_conductivity,
+            heat_capacity,
+            kind,
+            blocked: 0,
+            blocks_zones: false
+        }
+    }
+
+    /// A floor, `/turf/open/floor`, with `air` on it.
+    pub fn floor(air: GasMixture<'a>) -> Turf<'a> {
+        Turf::new(TurfKind::Floor, air, 0.04, 10000.0)
+    }
+
+    /// A plasteel wall, `/turf/closed/wall`. Holds an empty tile's worth of nothing at `T20C`.
+    /// Like tgstation's, it doesn't conduct heat at all; give it some `thermal_conductivity` if
+    /// heat should get through.
+    pub fn wall() -> Turf<'a> {
+        // "a little over 5 cm thick, 312500 for 1 m by 2.5 m by 0.25 m plasteel wall"
+        Turf::new(TurfKind::Wall, GasMixture::from_empty(num::T20C, num::CELL_VOLUME), num::WALL_HEAT_TRANSFER_COEFFICIENT, 312_500.0)
+    }
+
+    /// Space, `/turf/open/space`.
+    pub fn space() -> Turf<'a> {
+        Turf::new(TurfKind::Space, GasMixture::from_empty(num::TCMB, num::CELL_VOLUME), num::OPEN_HEAT_TRANSFER_COEFFICIENT, 700_000.0)
+    }
+
+    /// A floor with `planetary_atmos`, which keeps drifting back to `air`.
+    pub fn planetary(air: GasMixture<'a>) -> Turf<'a> {
+        Turf { kind: TurfKind::Planetary, ..Turf::floor(air) }
+    }
+
+    /// Open space, `/turf/open/openspace`, with `air` in it.
+    pub fn open_space(air: GasMixture<'a>) -> Turf<'a> {
+        Turf { kind: TurfKind::OpenSpace, ..Turf::floor(air) }
+    }
+
+    /// What sort of turf this is. Swap the turf out with `TurfGrid::replace()` to change it.
+    pub fn kind(&self) -> TurfKind {
+        self.kind
+    }
+
+    /// Returns true if this turf is space.
+    pub fn is_space(&self) -> bool {
+        self.kind == TurfKind::Space
+    }
+
+    /// Returns true if this turf is open space, which air can fall through.
+    pub fn is_open_space(&self) -> bool {
+        self.kind == TurfKind::OpenSpace
+    }
+
+    /// Returns true if this turf has `planetary_atmos`.
+    pub fn is_planetary(&self) -> bool {
+        self.kind == TurfKind::Planetary
+    }
+
+    /// Returns true if this turf blocks air entirely, `blocks_air` in tgstation.
+    pub fn blocks_air(&self) -> bool {
+        self.kind == TurfKind::Wall
+    }
+
+    /// Returns true if air can leave this turf going `direction`. Air only moves between two
+    /// turfs if both of them are open towards each other; see `TurfGrid::adjacent()`.
+    pub fn is_open(&self, direction: Direction) -> bool {
+        !self.blocks_air() && self.blocked & direction.bit() == 0
+    }
+
+    /// Blocks or unblocks the `direction` side of the turf, like putting up or taking down a
+    /// directional window or a door. On a turf that's already on a `TurfGrid`, use
+    /// `TurfGrid::set_blocked()` instead so the grid knows its layout changed.
+    pub fn set_blocked(&mut self, direction: Direction, blocked: bool) {
+        if blocked {
+            self.blocked |= direction.bit();
+        } else {
+            self.blocked &= !direction.bit();
+        }
+    }
+
+    /// Same as `set_blocked(direction, true)`, but chains, for building turfs.
+    pub fn blocked(mut self, direction: Direction) -> Turf<'a> {
+        self.set_blocked(direction, true);
+        self
+    }
+
+    /// Returns true if ZAS zones stop at this turf, so it gets a zone of its own that connects to
+    /// its neighbours' through edges. Only ZAS cares about this.
+    pub fn blocks_zones(&self) -> bool {
+        self.blocks_zones
+    }
+
+    /// Makes ZAS zones stop at this turf or not, like a door opening. On a turf that's already on
+    /// a `TurfGrid`, use `TurfGrid::set_blocks_zones()` instead so the grid knows its layout changed.
+    pub fn set_blocks_zones(&mut self, blocks_zones: bool) {
+        self.blocks_zones = blocks_zones;
+    }
+
+    /// Same as `set_blocks_zones(true)`, but chains, for building turfs.
+    pub fn zone_blocking(mut self) -> Turf<'a> {
+        self.set_blocks_zones(true);
+        self
+    }
+
+    /// The air the turf started with.
+    pub fn initial_air(&self) -> &GasMixture<'a> {
+        &self.initial_air
+    }
+
+    /// Archives the turf's air and its own temperature, `archive()` in tgstation.
+    pub fn archive(&mut self) {
+        self.air.archive();
+        self.temperature_archived = self.temperature;
+    }
+
+    /// The turf's own temperature as of the last `archive()`.
+    pub fn temperature_archived(&self) -> Real {
+        self.temperature_archived
+    }
+}
@@ -0,0 +1,275 @@
+use crate::atmospherics::environmental::{Direction, Position, TurfGrid};
+use crate::byond::Real;
+use crate::constants::*;
+
+// Space wind, ported from tgstation. Whenever two turfs share air, the one that lost air
+// remembers the biggest pressure difference it saw that cycle and which way it went
+// (`consider_pressure_difference()`), and everything loose on it gets pushed that way
+// (`high_pressure_movements()`). Monstermos feeds it too, with the mols it moves rather than a
+// pressure, same as tgstation; that's what makes explosive decompression throw people around.
+// ZAS doesn't feed it at all, since Baystation's airflow works completely differently.
+
+/// The biggest pressure difference a turf saw in a cycle.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PressureDelta {
+    pub position: Position,
+    /// How big the difference was, `pressure_difference`. LINDA gets this from what `share()`
+    /// returns, Monstermos from how many mols it moved.
+    pub difference: Real,
+    /// Which way the air went, `pressure_direction`.
+    pub direction: Direction,
+    /// The space turf explosive decompression is sucking everything towards, if it is,
+    /// `pressure_specific_target`.
+    pub target: Option<Position>
+}
+
+impl PressureDelta {
+    /// The push as an `(x, y, z)` vector, `direction` scaled by `difference`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::environmental::{Direction, Position};
+    /// use libatmos::atmospherics::environmental::wind::PressureDelta;
+    /// let delta = PressureDelta { position: Position::new(0, 0, 0), difference: 50.0, direction: Direction::West, target: None };
+    /// assert_eq!(delta.vector(), (-50.0, 0.0, 0.0));
+    /// ```
+    pub fn vector(&self) -> (Real, Real, Real) {
+        let (dx, dy, dz) = self.direction.offset();
+        (dx as Real * self.difference, dy as Real * self.difference, dz as Real * self.difference)
+    }
+}
+
+/// Something on a turf that space wind might move: an item, a mob, a locker.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Movable {
+    /// How big a pressure difference it takes to move it at all, `pressure_resistance`. Bigger
+    /// and heavier things should get more.
+    pub pressure_resistance: Real,
+    /// How much force it takes to move it, `move_resist`. `Real::INFINITY` never moves.
+    pub move_resist: Real,
+    /// Whether it's bolted down. Anchored things never get moved by space wind.
+    pub anchored: bool
+}
+
+impl Movable {
+    pub fn new(pressure_resistance: Real, move_resist: Real, anchored: bool) -> Movable {
+        Movable { pressure_resistance, move_resist, anchored }
+    }
+}
+
+impl Default for Movable {
+    /// `/atom/movable`'s defaults: `MOVABLE_PRESSURE_RESISTANCE`, `MOVE_RESIST_DEFAULT`, and
+    /// not anchored.
+    fn default() -> Movable {
+        Movable::new(num::MOVABLE_PRESSURE_RESISTANCE, num::MOVE_RESIST_DEFAULT, false)
+    }
+}
+
+/// Where space wind moves something.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Movement {
+    /// It steps one turf over, to this one.
+    Step(Position),
+    /// It gets thrown at `target`, `range` tiles at `speed` (1 to 10). `target` is the space turf
+    /// it's getting sucked into if that's the way the wind's blowing, otherwise whatever's
+    /// `force / 2000` tiles along (`get_ranged_target_turf()`). `landing` is where it ends up:
+    /// `range` tiles along, or wherever a wall or the edge of the grid stops it first.
+    Thrown {
+        target: Position,
+        landing: Position,
+        range: Real,
+        speed: Real
+    }
+}
+
+/// What space wind would do to a `Movable`, `experience_pressure_difference()`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PressureMovement {
+    /// Which way it goes.
+    pub direction: Direction,
+    /// The chance of it moving this cycle, 0 to 1. tgstation rolls for it; we leave that to you.
+    pub probability: Real,
+    /// How hard it gets pushed.
+    pub force: Real,
+    pub movement: Movement
+}
+
+/// Every turf that saw a pressure difference this cycle, `SSair.high_pressure_delta`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Direction, Position, Turf, TurfGrid};
+/// use libatmos::atmospherics::environmental::wind::{Movable, Movement, SpaceWind};
+/// use libatmos::atmospherics::gases::GasMixture;
+/// let grid = TurfGrid::new(3, 1, 1, Turf::floor(GasMixture::from_empty(293.15, 2500)));
+/// let mut wind = SpaceWind::new(&grid);
+/// let pos = Position::new(1, 0, 0);
+///
+/// wind.consider_pressure_difference(&grid, pos, Direction::East, 40.0);
+/// wind.consider_pressure_difference(&grid, pos, Direction::West, 20.0); // smaller, so it's ignored
+/// assert_eq!(wind.get(&grid, pos).unwrap().direction, Direction::East);
+///
+/// // 40 kPa pushes a crowbar along, but not a bolted down machine
+/// let push = wind.experience_pressure_difference(&grid, pos, &Movable::default()).unwrap();
+/// assert_eq!(push.movement, Movement::Step(Position::new(2, 0, 0)));
+/// assert!(wind.experience_pressure_difference(&grid, pos, &Movable { anchored: true, ..Movable::default() }).is_none());
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct SpaceWind {
+    deltas: Vec<Option<PressureDelta>>,
+    /// Indices of the turfs with a pressure difference, in the order they first got one.
+    order: Vec<usize>
+}
+
+impl SpaceWind {
+    /// Creates a calm `SpaceWind` for `grid`.
+    pub fn new(grid: &TurfGrid) -> SpaceWind {
+        SpaceWind {
+            deltas: vec![None; grid.len()],
+            order: Vec::new()
+        }
+    }
+
+    /// The pressure difference the turf at `pos` saw, if it saw one.
+    pub fn get(&self, grid: &TurfGrid, pos: Position) -> Option<&PressureDelta> {
+        grid.index_of(pos).and_then(|index| self.deltas[index].as_ref())
+    }
+
+    /// Every pressure difference, in the order the turfs first saw one.
+    pub fn deltas(&self) -> Vec<PressureDelta> {
+        self.order.iter().filter_map(|index| self.deltas[*index]).collect()
+    }
+
+    /// Number of turfs with a pressure difference.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Returns true if no turf has a pressure difference.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Forgets every pressure difference, like `SSair` does after moving everything.
+    pub fn clear(&mut self) {
+        for index in self.order.drain(..) {
+            self.deltas[index] = None;
+        }
+    }
+
+    /// `consider_pressure_difference()`: air left the turf at `pos` going `direction`, with a
+    /// pressure difference of `difference`. Only the biggest difference each cycle counts.
+    /// Does nothing if `pos` is off the grid.
+    pub fn consider_pressure_difference(&mut self, grid: &TurfGrid, pos: Position, direction: Direction, difference: Real) {
+        let index = match grid.index_of(pos) {
+            Some(index) => index,
+            None => return
+        };
+        match self.deltas[index].as_mut() {
+            Some(delta) => {
+                if difference > delta.difference {
+                    delta.difference = difference;
+                    delta.direction = direction;
+                }
+            },
+            None => {
+                self.deltas[index] = Some(PressureDelta { position: pos, difference, direction, target: None });
+                self.order.push(index);
+            }
+        }
+    }
+
+    /// Points everything on the turf at `pos` at the space turf `target`. Explosive
+    /// decompression sets this after `consider_pressure_difference()`.
+    pub(crate) fn set_target(&mut self, grid: &TurfGrid, pos: Position, target: Position) {
+        if let Some(delta) = grid.index_of(pos).and_then(|index| self.deltas[index].as_mut()) {
+            delta.target = Some(target);
+        }
+    }
+
+    /// `experience_pressure_difference()`: what this cycle's space wind would do to `movable`
+    /// on the turf at `pos`, or `None` if it stays put. The push gets stronger with the square
+    /// root of the pressure difference; past `PRESSURE_THROW_FORCE` it's a throw rather than a
+    /// step. Anchored things never move, and neither does anything a wall's in the way of.
+    pub fn experience_pressure_difference(&self, grid: &TurfGrid, pos: Position, movable: &Movable) -> Option<PressureMovement> {
+        let delta = self.get(grid, pos)?;
+        if movable.anchored || movable.move_resist == Real::INFINITY {
+            return None;
+        }
+        let max_force = delta.difference.sqrt() * num::MOVE_FORCE_DEFAULT / 5.0;
+        let move_prob = if movable.pressure_resistance > 0.0 {
+            delta.difference / movable.pressure_resistance * num::PRESSURE_MOVE_PROBABILITY_BASE - num::PRESSURE_MOVE_PROBABILITY_OFFSET
+        } else {
+            100.0
+        };
+        if move_prob <= num::PRESSURE_MOVE_PROBABILITY_OFFSET || max_force < movable.move_resist * num::MOVE_FORCE_PUSH_RATIO {
+            return None;
+        }
+
+        let probability = move_prob.clamp(0.0, 100.0) / 100.0;
+        let force = max_force * probability;
+        let movement = if force > num::PRESSURE_THROW_FORCE {
+            let range = force / 3000.0;
+            // only aim for the space turf if it's (at least partly) the way the wind's blowing
+            let target = delta.target
+                .filter(|target| get_dir(pos, *target) & delta.direction.bit() != 0)
+                .unwrap_or_else(|| ranged_target(grid, pos, delta.direction, (force / 2000.0).floor() as usize));
+            Movement::Thrown {
+                target,
+                landing: fly(grid, pos, delta.direction, range as usize),
+                range,
+                speed: (force / 3000.0).floor().clamp(1.0, 10.0)
+            }
+        } else {
+            Movement::Step(step(grid, pos, delta.direction)?)
+        };
+        Some(PressureMovement { direction: delta.direction, probability, force, movement })
+    }
+}
+
+/// Where something at `pos` could step going `direction`, if it's not blocked.
+fn step(grid: &TurfGrid, pos: Position, direction: Direction) -> Option<Position> {
+    grid.adjacent(pos)
+        .into_iter()
+        .find(|(next_direction, _)| *next_direction == direction)
+        .map(|(_, next)| next)
+}
+
+/// The turf `range` tiles from `pos` going `direction`, or the edge of the grid if that's
+/// closer, `get_ranged_target_turf()`. Like tgstation's, it never changes z-level.
+fn ranged_target(grid: &TurfGrid, mut pos: Position, direction: Direction, range: usize) -> Position {
+    if direction.is_vertical() {
+        return pos;
+    }
+    for _ in 0..range {
+        match grid.step(pos, direction) {
+            Some(next) => pos = next,
+            None => break
+        }
+    }
+    pos
+}
+
+/// Which way `to` is from `from`, as BYOND direction flags, `get_dir()`. Diagonals have two.
+fn get_dir(from: Position, to: Position) -> u8 {
+    let mut dir = 0;
+    for (ours, theirs, more, less) in [(from.y, to.y, Direction::North, Direction::South),
+                                       (from.x, to.x, Direction::East, Direction::West),
+                                       (from.z, to.z, Direction::Up, Direction::Down)] {
+        if theirs > ours {
+            dir |= more.bit();
+        } else if theirs < ours {
+            dir |= less.bit();
+        }
+    }
+    dir
+}
+
+/// Where something thrown from `pos` going `direction` ends up after `range` tiles.
+fn fly(grid: &TurfGrid, mut pos: Position, direction: Direction, range: usize) -> Position {
+    for _ in 0..range {
+        match step(grid, pos, direction) {
+            Some(next) => pos = next,
+            None => break
+        }
+    }
+    pos
+}
@@ -0,0 +1,431 @@
+use crate::atmospherics::environmental::{Position, TurfGrid};
+use crate::atmospherics::gases::GasMixture;
+use crate::byond::Real;
+use crate::constants::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+// ZAS, the zone air system Baystation and its descendants run. Connected turfs get lumped
+// together into zones, which have one gas mixture between all of them, so air moves around
+// inside a zone instantly. Zones only stop at walls, blocked sides and zone-blocking turfs (open
+// doors), which get a zone of their own. Zones that touch are connected by edges, and each cycle
+// every awake edge shares a fixed ratio of air between its zones, depending on how many tiles
+// they touch by. Space isn't part of any zone; zones next to it leak into it through edges too.
+// Edges go to sleep once both sides are about the same, and wake up again when either side
+// changes enough that they aren't.
+
+/// What a `ConnectionEdge` connects a zone to.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
+pub enum EdgeTarget {
+    /// Another zone, by id. Always a higher id than the edge's own zone.
+    Zone(usize),
+    /// Space, `/connection_edge/unsimulated`.
+    Space
+}
+
+/// Where two zones (or a zone and space) touch, `/connection_edge`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ConnectionEdge {
+    /// Id of the zone on one side.
+    pub zone: usize,
+    /// What's on the other side.
+    pub target: EdgeTarget,
+    /// How many tile sides the two touch by, `coefficient`. More means faster sharing, up to 6.
+    pub connecting_tiles: usize,
+    /// Whether the edge stopped sharing because both sides are about the same.
+    pub sleeping: bool
+}
+
+/// Connected turfs that share one gas mixture, `/zone`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Zone<'a> {
+    /// Air of the whole zone. Its volume is all of the turfs' put together.
+    pub air: GasMixture<'a>,
+    /// Grid indices of the turfs, in storage order.
+    turfs: Vec<usize>
+}
+
+impl<'a> Zone<'a> {
+    /// Every turf in the zone, in storage order.
+    pub fn turfs(&self, grid: &TurfGrid) -> Vec<Position> {
+        self.turfs.iter().map(|index| grid.position_of(*index)).collect()
+    }
+
+    /// Number of turfs in the zone, `group_multiplier`.
+    pub fn len(&self) -> usize {
+        self.turfs.len()
+    }
+
+    /// Always false; zones get made around turfs.
+    pub fn is_empty(&self) -> bool {
+        self.turfs.is_empty()
+    }
+}
+
+/// What one `Zas::process()` did.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ZasStats {
+    /// Whether the zones had to be rebuilt because the layout changed.
+    pub rebuilt: bool,
+    /// Number of zones.
+    pub zones: usize,
+    /// Number of edges, sleeping or not.
+    pub edges: usize,
+    /// Edges that shared air.
+    pub shared: usize,
+    /// Edges that went to sleep.
+    pub slept: usize,
+    /// Zones whose air changed, and got copied back out to their turfs.
+    pub updated: usize
+}
+
+/// The zones and edges for one `TurfGrid`, and which zones are awake.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::environmental::{Position, Turf, TurfGrid};
+/// use libatmos::atmospherics::environmental::zas::{EdgeTarget, Zas};
+/// use libatmos::atmospherics::gases::GasMixture;
+/// // two 2x2 rooms with an open door (the middle column) between them
+/// let grid = TurfGrid::from_fn(5, 2, 1, |pos| match pos.x {
+///     2 if pos.y == 0 => Turf::floor(GasMixture::from_empty(293.15, 2500)).zone_blocking(),
+///     2 => Turf::wall(),
+///     _ => Turf::floor(GasMixture::from_empty(293.15, 2500))
+/// });
+/// let zas = Zas::new(&grid);
+///
+/// assert_eq!(zas.zones().count(), 3);
+/// assert_eq!(zas.zone(zas.zone_of(&grid, Position::new(0, 0, 0)).unwrap()).unwrap().len(), 4);
+/// assert_eq!(zas.edges().len(), 2);
+/// assert!(zas.edges().iter().all(|edge| edge.connecting_tiles == 1 && edge.target != EdgeTarget::Space));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Zas<'a> {
+    /// `TurfGrid::revision()` the zones were built for.
+    revision: u64,
+    zone_of: Vec<Option<usize>>,
+    zones: Vec<Zone<'a>>,
+    edges: Vec<ConnectionEdge>,
+    /// Zones with turfs that got touched since the last cycle, so their air has to be gathered
+    /// back up from the turfs.
+    dirty: BTreeSet<usize>,
+    /// Zones whose edges get processed next cycle, even the sleeping ones.
+    awake: BTreeSet<usize>
+}
+
+impl<'a> Zas<'a> {
+    /// Builds zones and edges for `grid`, with every zone awake.
+    pub fn new(grid: &TurfGrid<'a>) -> Zas<'a> {
+        let mut zas = Zas {
+            revision: grid.revision(),
+            zone_of: Vec::new(),
+            zones: Vec::new(),
+            edges: Vec::new(),
+            dirty: BTreeSet::new(),
+            awake: BTreeSet::new()
+        };
+        zas.rebuild(grid);
+        zas
+    }
+
+    /// Every zone with its id.
+    pub fn zones(&self) -> impl Iterator<Item = (usize, &Zone<'a>)> + '_ {
+        self.zones.iter().enumerate()
+    }
+
+    /// The zone with id `id`.
+    pub fn zone(&self, id: usize) -> Option<&Zone<'a>> {
+        self.zones.get(id)
+    }
+
+    /// Id of the zone the turf at `pos` is in. Walls and space aren't in zones, and neither is
+    /// anything off the grid.
+    pub fn zone_of(&self, grid: &TurfGrid, pos: Position) -> Option<usize> {
+        grid.index_of(pos).and_then(|index| self.zone_of.get(index).copied().flatten())
+    }
+
+    /// Every edge.
+    pub fn edges(&self) -> &[ConnectionEdge] {
+        &self.edges
+    }
+
+    /// Returns true if `grid`'s layout changed since the zones got built, so they'll get rebuilt
+    /// next cycle.
+    pub fn is_stale(&self, grid: &TurfGrid) -> bool {
+        self.revision != grid.revision()
+    }
+
+    /// Lets ZAS know the air on the turf at `pos` changed, which wakes up its zone. Returns false
+    /// for walls, space and turfs off the grid.
+    pub fn activate(&mut self, grid: &TurfGrid, pos: Position) -> bool {
+        match grid.get(pos) {
+            Some(turf) if !turf.blocks_air() && !turf.is_space() => {
+                // stale zones get gathered up from scratch anyway
+                if let Some(zone) = self.zone_of(grid, pos).filter(|_| !self.is_stale(grid)) {
+                    self.dirty.insert(zone);
+                    self.awake.insert(zone);
+                }
+                true
+            },
+            _ => false
+        }
+    }
+
+    /// Returns true if the zone the turf at `pos` is in is awake.
+    pub fn is_active(&self, grid: &TurfGrid, pos: Position) -> bool {
+        self.zone_of(grid, pos).is_some_and(|zone| self.awake.contains(&zone))
+    }
+
+    /// Every turf in an awake zone.
+    pub fn active_turfs(&self, grid: &TurfGrid) -> Vec<Position> {
+        self.awake.iter().flat_map(|zone| self.zones[*zone].turfs(grid)).collect()
+    }
+
+    /// Throws away every zone and edge and builds them again from `grid`, gathering each zone's
+    /// air up from its turfs.
+    fn rebuild(&mut self, grid: &TurfGrid<'a>) {
+        self.revision = grid.revision();
+        self.zone_of = vec![None; grid.len()];
+        self.zones.clear();
+        self.dirty.clear();
+
+        for index in 0..grid.len() {
+            let pos = grid.position_of(index);
+            if self.zone_of[index].is_some() || grid[pos].blocks_air() || grid[pos].is_space() {
+                continue;
+            }
+            let id = self.zones.len();
+            self.zone_of[index] = Some(id);
+            let mut turfs = vec![index];
+            let mut i = 0;
+            while i < turfs.len() {
+                let current = grid.position_of(turfs[i]);
+                i += 1;
+                if grid[current].blocks_zones() {
+                    continue;
+                }
+                for (_, next) in grid.adjacent(current) {
+                    let next_index = grid.index_of(next).unwrap(); // adjacent() only hands out turfs on the grid
+                    if self.zone_of[next_index].is_none() && !grid[next].is_space() && !grid[next].blocks_zones() {
+                        self.zone_of[next_index] = Some(id);
+                        turfs.push(next_index);
+                    }
+                }
+            }
+            turfs.sort_unstable();
+            self.zones.push(Zone {
+                air: gather(grid, &turfs),
+                turfs
+            });
+        }
+
+        let mut connections = BTreeMap::new();
+        for (id, zone) in self.zones.iter().enumerate() {
+            for index in &zone.turfs {
+                for (_, next) in grid.adjacent(grid.position_of(*index)) {
+                    let target = match self.zone_of[grid.index_of(next).unwrap()] {
+                        _ if grid[next].is_space() => EdgeTarget::Space,
+                        Some(other) if other > id => EdgeTarget::Zone(other),
+                        _ => continue // same zone, or counted from the other side
+                    };
+                    *connections.entry((id, target)).or_insert(0) += 1;
+                }
+            }
+        }
+        self.edges = connections.into_iter()
+                                .map(|((zone, target), connecting_tiles)| ConnectionEdge { zone, target, connecting_tiles, sleeping: false })
+                                .collect();
+        self.awake = (0..self.zones.len()).collect();
+    }
+
+    /// Runs one cycle: rebuilds the zones if the layout changed, gathers up air from the turfs
+    /// of zones that got activated, runs every edge that's awake or touches an awake zone, and
+    /// copies the air of every zone that changed back out to its turfs.
+    pub fn process(&mut self, grid: &mut TurfGrid<'a>) -> ZasStats {
+        let mut stats = ZasStats::default();
+        if self.is_stale(grid) {
+            self.rebuild(grid);
+            stats.rebuilt = true;
+        }
+        for id in std::mem::take(&mut self.dirty) {
+            self.zones[id].air = gather(grid, &self.zones[id].turfs);
+        }
+
+        let mut updated = self.awake.clone();
+        let mut awake = BTreeSet::new();
+        for edge in self.edges.iter_mut() {
+            let target_awake = match edge.target {
+                EdgeTarget::Zone(other) => self.awake.contains(&other),
+                EdgeTarget::Space => false
+            };
+            if edge.sleeping && !self.awake.contains(&edge.zone) && !target_awake {
+                continue;
+            }
+            edge.sleeping = false;
+            stats.shared += 1;
+            let equivalent = match edge.target {
+                EdgeTarget::Zone(other) => {
+                    let (low, high) = self.zones.split_at_mut(other);
+                    let (zone, other_zone) = (&mut low[edge.zone], &mut high[0]);
+                    let (size, other_size) = (zone.len() as Real, other_zone.len() as Real);
+                    let equivalent = share_ratio(&mut zone.air, size, &mut other_zone.air, other_size, edge.connecting_tiles, other_size, false);
+                    if equivalent {
+                        equalize(&mut zone.air, &mut other_zone.air);
+                    }
+                    updated.insert(other);
+                    equivalent
+                },
+                EdgeTarget::Space => {
+                    let zone = &mut self.zones[edge.zone];
+                    let size = zone.len() as Real;
+                    let mut space = GasMixture::from_empty(num::TCMB, num::CELL_VOLUME * edge.connecting_tiles);
+                    // leaks like a big empty room would, a few tiles bigger than the zone itself
+                    let share_size = (size + 3.0).max(1.0) + edge.connecting_tiles as Real;
+                    let equivalent = share_ratio(&mut zone.air, size, &mut space, edge.connecting_tiles as Real, edge.connecting_tiles, share_size, true);
+                    if equivalent {
+                        zone.air.copy_from(&GasMixture::from_empty(zone.air.temperature, zone.air.volume));
+                    }
+                    equivalent
+                }
+            };
+            updated.insert(edge.zone);
+            if equivalent {
+                edge.sleeping = true;
+                stats.slept += 1;
+            } else {
+                awake.insert(edge.zone);
+                if let EdgeTarget::Zone(other) = edge.target {
+                    awake.insert(other);
+                }
+            }
+        }
+
+        // zones that changed recheck their sleeping edges, `recheck()`
+        for edge in self.edges.iter_mut().filter(|edge| edge.sleeping) {
+            let zone = &self.zones[edge.zone];
+            let (other_air, other_size) = match edge.target {
+                EdgeTarget::Zone(other) if updated.contains(&edge.zone) || updated.contains(&other) => {
+                    (self.zones[other].air.clone(), self.zones[other].len())
+                },
+                EdgeTarget::Space if updated.contains(&edge.zone) => {
+                    (GasMixture::from_empty(num::TCMB, num::CELL_VOLUME * edge.connecting_tiles), edge.connecting_tiles)
+                },
+                _ => continue
+            };
+            if !equivalent(&zone.air, zone.len() as Real, &other_air, other_size as Real) {
+                edge.sleeping = false;
+                awake.insert(edge.zone);
+                if let EdgeTarget::Zone(other) = edge.target {
+                    awake.insert(other);
+                }
+            }
+        }
+
+        for id in &updated {
+            self.zones[*id].air.react();
+            scatter(grid, &self.zones[*id]);
+        }
+        self.awake = awake;
+        stats.zones = self.zones.len();
+        stats.edges = self.edges.len();
+        stats.updated = updated.len();
+        stats
+    }
+}
+
+/// All of the air on `turfs`, in one mixture as big as all of them.
+fn gather<'a>(grid: &TurfGrid<'a>, turfs: &[usize]) -> GasMixture<'a> {
+    let mut air = grid[grid.position_of(turfs[0])].air.clone();
+    for index in &turfs[1..] {
+        air.merge(grid[grid.position_of(*index)].air.clone());
+    }
+    air.volume = turfs.iter().map(|index| grid[grid.position_of(*index)].air.volume).sum();
+    air
+}
+
+/// Copies `zone`'s air back out to its turfs, each getting a share as big as its volume.
+fn scatter<'a>(grid: &mut TurfGrid<'a>, zone: &Zone<'a>) {
+    for index in &zone.turfs {
+        let pos = grid.position_of(*index);
+        let volume = grid[pos].air.volume;
+        let share = volume as Real / zone.air.volume as Real;
+        let (gases, moles): (Vec<_>, Vec<_>) = zone.air.iter().map(|(gas, moles)| (gas, moles * share)).unzip();
+        let air = GasMixture::from_vecs(gases, moles, zone.air.temperature, volume).unwrap(); // made from a valid mixture
+        grid[pos].air.copy_from(&air);
+    }
+}
+
+/// `share_ratio()`: moves `a` and `b` part of the way towards their average, per tile. How far
+/// depends on `connecting_tiles`, going by `ZAS_SHARING_LOOKUP_TABLE`. `size` and `other_size`
+/// are how many tiles each mixture covers; `share_size` is how many `b` counts as when working
+/// out the average. With `one_way`, `b` doesn't change. Returns true if the two are about the
+/// same afterwards. Like in Baystation, temperatures just get averaged without caring how much
+/// heat that takes, so this doesn't quite conserve energy.
+fn share_ratio<'a>(a: &mut GasMixture<'a>, size: Real, b: &mut GasMixture<'a>, other_size: Real, connecting_tiles: usize, share_size: Real, one_way: bool) -> bool {
+    let table = &num::ZAS_SHARING_LOOKUP_TABLE;
+    let ratio = table[connecting_tiles.clamp(1, table.len()) - 1];
+    let size = size.max(1.0);
+    let other_size = other_size.max(1.0);
+
+    let full_heat_capacity = a.heat_capacity();
+    let s_full_heat_capacity = b.heat_capacity();
+    let all_gases = a.iter().chain(b.iter()).map(|(gas, _)| gas).collect::<BTreeSet<_>>();
+    for gas in all_gases {
+        let comp = a.get_moles(gas).unwrap_or(0.0) / size;
+        let s_comp = b.get_moles(gas).unwrap_or(0.0) / other_size;
+        let avg = (comp * size + s_comp * share_size) / (size + share_size);
+        a.assert_gas(gas);
+        a.change_moles(gas, ((comp - avg) * (1.0 - ratio) + avg).max(0.0) * size).unwrap(); // never negative
+        if !one_way {
+            b.assert_gas(gas);
+            b.change_moles(gas, ((s_comp - avg) * (1.0 - ratio) + avg).max(0.0) * other_size).unwrap(); // never negative
+        }
+    }
+    if full_heat_capacity + s_full_heat_capacity > 0.0 {
+        let temp_avg = (a.temperature * full_heat_capacity + b.temperature * s_full_heat_capacity) / (full_heat_capacity + s_full_heat_capacity);
+        a.temperature = ((a.temperature - temp_avg) * (1.0 - ratio) + temp_avg).max(0.0);
+        if !one_way {
+            b.temperature = ((b.temperature - temp_avg) * (1.0 - ratio) + temp_avg).max(0.0);
+        }
+    }
+    equivalent(a, size, b, other_size)
+}
+
+/// Baystation's `compare()`, per tile: true if no gas is off by more than
+/// `MINIMUM_AIR_TO_SUSPEND` and `MINIMUM_AIR_RATIO_TO_SUSPEND`, and the temperature isn't off by
+/// more than `MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND` and `MINIMUM_TEMPERATURE_RATIO_TO_SUSPEND`.
+fn equivalent(a: &GasMixture, size: Real, b: &GasMixture, other_size: Real) -> bool {
+    let all_gases = a.iter().chain(b.iter()).map(|(gas, _)| gas).collect::<BTreeSet<_>>();
+    for gas in all_gases {
+        let comp = a.get_moles(gas).unwrap_or(0.0) / size;
+        let s_comp = b.get_moles(gas).unwrap_or(0.0) / other_size;
+        if (comp - s_comp).abs() > num::MINIMUM_AIR_TO_SUSPEND
+            && (comp < (1.0 - num::MINIMUM_AIR_RATIO_TO_SUSPEND) * s_comp || comp > (1.0 + num::MINIMUM_AIR_RATIO_TO_SUSPEND) * s_comp) {
+            return false;
+        }
+    }
+    if a.total_moles() / size > num::MINIMUM_AIR_TO_SUSPEND
+        && (a.temperature - b.temperature).abs() > num::MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND
+        && (a.temperature < (1.0 - num::MINIMUM_TEMPERATURE_RATIO_TO_SUSPEND) * b.temperature
+            || a.temperature > (1.0 + num::MINIMUM_TEMPERATURE_RATIO_TO_SUSPEND) * b.temperature) {
+        return false;
+    }
+    true
+}
+
+/// `equalize()`: evens out `a` and `b` completely, by volume, and brings them to the same
+/// temperature without losing any heat.
+fn equalize<'a>(a: &mut GasMixture<'a>, b: &mut GasMixture<'a>) {
+    let (our_heatcap, share_heatcap) = (a.heat_capacity(), b.heat_capacity());
+    let (volume, s_volume) = (a.volume as Real, b.volume as Real);
+    let all_gases = a.iter().chain(b.iter()).map(|(gas, _)| gas).collect::<BTreeSet<_>>();
+    for gas in all_gases {
+        let comb = (a.get_moles(gas).unwrap_or(0.0) + b.get_moles(gas).unwrap_or(0.0)) / (volume + s_volume);
+        a.assert_gas(gas);
+        a.change_moles(gas, comb * volume).unwrap(); // never negative
+        b.assert_gas(gas);
+        b.change_moles(gas, comb * s_volume).unwrap(); // never negative
+    }
+    if our_heatcap + share_heatcap > 0.0 {
+        a.temperature = (a.temperature * our_heatcap + b.temperature * share_heatcap) / (our_heatcap + share_heatcap);
+    }
+    b.temperature = a.temperature;
+}
@@ -0,0 +1,179 @@
+use crate::byond::Real;
+use crate::constants::*;
+use std::fmt;
+
+// Explosion ranges, ported from tgstation's `explosion()` proc and the tank fragment code
+// that calls it. BYOND's one-argument `round()` rounds *down*, so everything here floors.
+
+/// The four ranges of an explosion, in tiles.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ExplosionRanges {
+    /// Devastation range, everything inside gets destroyed outright.
+    pub devastation: Real,
+    /// Heavy impact range.
+    pub heavy_impact: Real,
+    /// Light impact range.
+    pub light_impact: Real,
+    /// Flash range, anyone inside without eye protection gets flashed.
+    pub flash: Real
+}
+
+impl ExplosionRanges {
+    /// Ranges of a fragmenting tank with raw range `range`, i.e.
+    /// `explosion(epicenter, round(range*0.25), round(range*0.5), round(range), round(range*1.5))`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::explosion::ExplosionRanges;
+    /// let ranges = ExplosionRanges::from_range(10.0);
+    /// assert_eq!(ranges.devastation, 2.0);
+    /// assert_eq!(ranges.heavy_impact, 5.0);
+    /// assert_eq!(ranges.light_impact, 10.0);
+    /// assert_eq!(ranges.flash, 15.0);
+    /// ```
+    pub fn from_range(range: Real) -> ExplosionRanges {
+        ExplosionRanges {
+            devastation: (range * 0.25).floor(),
+            heavy_impact: (range * 0.5).floor(),
+            light_impact: range.floor(),
+            flash: (range * 1.5).floor()
+        }
+    }
+
+    /// Clamps every range to `cap`.
+    pub fn capped(&self, cap: &BombCap) -> ExplosionRanges {
+        ExplosionRanges {
+            devastation: self.devastation.min(cap.devastation),
+            heavy_impact: self.heavy_impact.min(cap.heavy_impact),
+            light_impact: self.light_impact.min(cap.light_impact),
+            flash: self.flash.min(cap.flash)
+        }
+    }
+
+    /// Largest of the four ranges, `orig_max_distance` in tgstation.
+    pub fn max_distance(&self) -> Real {
+        self.devastation.max(self.heavy_impact).max(self.light_impact).max(self.flash)
+    }
+}
+
+impl fmt::Display for ExplosionRanges {
+    /// Formats the ranges the way admin logs do, "(dev, heavy, light, flash)".
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {}, {}, {})", self.devastation, self.heavy_impact, self.light_impact, self.flash)
+    }
+}
+
+/// Largest allowed explosion ranges, `GLOB.MAX_EX_*_RANGE` in tgstation.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BombCap {
+    /// Cap on the devastation range, `GLOB.MAX_EX_DEVESTATION_RANGE`.
+    pub devastation: Real,
+    /// Cap on the heavy impact range, `GLOB.MAX_EX_HEAVY_RANGE`.
+    pub heavy_impact: Real,
+    /// Cap on the light impact range, `GLOB.MAX_EX_LIGHT_RANGE`.
+    pub light_impact: Real,
+    /// Cap on the flash range, `GLOB.MAX_EX_FLASH_RANGE`.
+    pub flash: Real
+}
+
+impl BombCap {
+    /// Caps derived from the `BOMBCAP` config value the way tgstation's config does:
+    /// devastation is a quarter of it, heavy impact half, and light impact and flash all of it.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::explosion::BombCap;
+    /// let cap = BombCap::new(20.0);
+    /// assert_eq!(cap.devastation, 5.0);
+    /// assert_eq!(cap.heavy_impact, 10.0);
+    /// assert_eq!(cap.light_impact, 20.0);
+    /// assert_eq!(cap.flash, 20.0);
+    /// ```
+    pub fn new(bombcap: Real) -> BombCap {
+        BombCap {
+            devastation: (bombcap / 4.0).floor(),
+            heavy_impact: (bombcap / 2.0).floor(),
+            light_impact: bombcap,
+            flash: bombcap
+        }
+    }
+
+    /// No cap at all, i.e. `ignorecap = TRUE`.
+    pub fn uncapped() -> BombCap {
+        BombCap {
+            devastation: Real::INFINITY,
+            heavy_impact: Real::INFINITY,
+            light_impact: Real::INFINITY,
+            flash: Real::INFINITY
+        }
+    }
+
+    /// Multiplies every cap by `multiplier`, for z-levels with the bombcap multiplier trait.
+    pub fn scaled(&self, multiplier: Real) -> BombCap {
+        BombCap {
+            devastation: self.devastation * multiplier,
+            heavy_impact: self.heavy_impact * multiplier,
+            light_impact: self.light_impact * multiplier,
+            flash: self.flash * multiplier
+        }
+    }
+}
+
+impl Default for BombCap {
+    /// The default caps, (3, 7, 14, 14).
+    fn default() -> BombCap {
+        BombCap::new(num::DEFAULT_BOMBCAP)
+    }
+}
+
+/// An explosion with both its theoretical ranges (what the doppler array reports) and the
+/// ranges it actually goes off with after the bomb cap.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::explosion::{Explosion, BombCap};
+/// use libatmos::constants::num;
+/// let explosion = Explosion::from_pressure(num::TANK_FRAGMENT_PRESSURE + 20.0 * num::TANK_FRAGMENT_SCALE, &BombCap::default());
+/// assert_eq!(explosion.theoretical.light_impact, 20.0);
+/// assert_eq!(explosion.actual.light_impact, 14.0);
+/// assert!(explosion.is_capped());
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Explosion {
+    /// Raw range the ranges were worked out from.
+    pub range: Real,
+    /// Ranges before the bomb cap.
+    pub theoretical: ExplosionRanges,
+    /// Ranges after the bomb cap.
+    pub actual: ExplosionRanges
+}
+
+impl Explosion {
+    /// Explosion of a tank fragmenting with raw range `range`, capped by `cap`.
+    pub fn from_range(range: Real, cap: &BombCap) -> Explosion {
+        let theoretical = ExplosionRanges::from_range(range);
+        Explosion {
+            range,
+            theoretical,
+            actual: theoretical.capped(cap)
+        }
+    }
+
+    /// Explosion of a tank fragmenting at `pressure` kPa, capped by `cap`. The raw range is
+    /// `(pressure - TANK_FRAGMENT_PRESSURE) / TANK_FRAGMENT_SCALE`.
+    pub fn from_pressure(pressure: Real, cap: &BombCap) -> Explosion {
+        Explosion::from_range((pressure - num::TANK_FRAGMENT_PRESSURE) / num::TANK_FRAGMENT_SCALE, cap)
+    }
+
+    /// Returns true if the bomb cap cut any of the ranges down.
+    pub fn is_capped(&self) -> bool {
+        self.theoretical != self.actual
+    }
+}
+
+impl fmt::Display for Explosion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_capped() {
+            write!(f, "{} (theoretical {})", self.actual, self.theoretical)
+        } else {
+            write!(f, "{}", self.actual)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::byond::Real;
+use std::fmt;
+
+/// Breathing requirements of a species, i.e. what its lungs will put up with.
+/// All values are partial pressures in kPa, taken from tgstation's `/obj/item/organ/lungs`.
+/// A `_max` of `0.0` means there is no upper limit, same as in the game.
+#[derive(Debug, PartialEq)]
+pub struct Species {
+    /// Short identifier
+    pub id: &'static str,
+    /// More formal, user-friendly name
+    pub name: &'static str,
+    /// Minimum partial pressure of oxygen needed to breathe
+    pub safe_oxygen_min: Real,
+    /// Maximum partial pressure of oxygen before it starts hurting
+    pub safe_oxygen_max: Real,
+    /// Minimum partial pressure of nitrogen needed to breathe
+    pub safe_nitro_min: Real,
+    /// Maximum partial pressure of nitrogen before it starts hurting
+    pub safe_nitro_max: Real,
+    /// Minimum partial pressure of carbon dioxide needed to breathe
+    pub safe_co2_min: Real,
+    /// Maximum partial pressure of carbon dioxide before it starts hurting
+    pub safe_co2_max: Real,
+    /// Minimum partial pressure of plasma needed to breathe
+    pub safe_toxins_min: Real,
+    /// Maximum partial pressure of plasma before it starts hurting
+    pub safe_toxins_max: Real
+}
+
+impl fmt::Display for Species {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)
+    }
+}
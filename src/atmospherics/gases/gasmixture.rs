@@ -1,8 +1,14 @@
-use crate::atmospherics::gases::GasType;
+use crate::atmospherics::gases::{GasType, Species};
+use crate::atmospherics::gases::reactions::{self, ReactionResults};
+#[cfg(feature = "invariants")]
+use crate::atmospherics::gases::invariants;
+use crate::byond::{self, Real};
 use crate::constants::*;
 use crate::errors::AtmosError;
-use std::collections::HashMap;
+use crate::units::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
+use std::str::FromStr;
 
 /// GasMixture struct. "Unit" type for pretty much everything
 /// atmospheric in the game. Holds a list of gases, their amounts
@@ -17,15 +23,48 @@ use std::fmt;
 /// assert_eq!(mix.temperature, 273.15);
 /// assert_eq!(mix.volume, 70);
 /// ```
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct GasMixture<'a> {
-    /// HashMap of a reference to a static GasType (i.e. O2, plasma)
-    /// and their mole count as an f64.
-    gases: HashMap<&'a GasType, f64>,
+    /// Map of a reference to a static GasType (i.e. O2, plasma)
+    /// and their mole count as a Real. A BTreeMap rather than a HashMap so gases are
+    /// always iterated in registry order (see `GasType`'s `Ord`), which keeps sums and
+    /// `Display` output identical between runs.
+    gases: BTreeMap<&'a GasType, Real>,
     /// Temperature of the gas mixture.
-    pub temperature: f64,
+    pub temperature: Real,
     /// Volume of the gas mixture.
-    pub volume: usize
+    pub volume: usize,
+    /// Mole counts as of the last `archive()`. `share()` moves gas based on these rather than
+    /// the live counts, so the order turfs get processed in doesn't matter as much.
+    gases_archived: BTreeMap<&'a GasType, Real>,
+    /// Temperature as of the last `archive()`.
+    temperature_archived: Real,
+    /// Total mols moved by the last `share()`.
+    last_share: Real,
+    /// How much burned the last time the mixture reacted, `reaction_results["fire"]`.
+    last_fire: Real
+}
+
+/// How two mixtures differ, as returned by `GasMixture::compare()`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MixtureDifference<'a> {
+    /// The amount of this gas is different enough to be worth moving.
+    Gas(&'a GasType),
+    /// The temperatures are more than `MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND` apart.
+    Temperature
+}
+
+/// One gas' share of a `GasMixture`, as returned by `GasMixture::composition()`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GasComponent<'a> {
+    /// Which gas this is.
+    pub gas: &'a GasType,
+    /// Mole count of the gas.
+    pub moles: Real,
+    /// Mole fraction of the gas, from 0 to 1.
+    pub fraction: Real,
+    /// Partial pressure of the gas in kPa.
+    pub partial_pressure: Real
 }
 
 impl<'a> GasMixture<'a> {
@@ -38,29 +77,38 @@ impl<'a> GasMixture<'a> {
     /// let mix = GasMixture::from_empty(293.15, 1000);
     /// assert_eq!(mix.temperature, 293.15);
     /// ```
-    pub fn from_empty(temperature: f64, volume: usize) -> GasMixture<'a> {
+    pub fn from_empty(temperature: Real, volume: usize) -> GasMixture<'a> {
         info!("New gas mixture created using from_empty");
-        let gases: HashMap<&'a GasType, f64> = HashMap::new();
+        let gases: BTreeMap<&'a GasType, Real> = BTreeMap::new();
         GasMixture {
             gases,
             temperature,
-            volume
+            volume,
+            gases_archived: BTreeMap::new(),
+            temperature_archived: temperature,
+            last_share: 0.0,
+            last_fire: 0.0
         }
     }
 
     /// Creates a new instance of a `GasMixture` normally.
     /// `GasMixure::empty()` and `GasMixture::from_vecs` are preferred heavily.
-    pub fn new(gases: HashMap<&'a GasType, f64>, temperature: f64, volume: usize) -> GasMixture {
+    pub fn new(gases: HashMap<&'a GasType, Real>, temperature: Real, volume: usize) -> GasMixture<'a> {
         info!("New gas mixture created using new");
         GasMixture {
-            gases,
+            gases: gases.into_iter().collect(),
             temperature,
-            volume
+            volume,
+            gases_archived: BTreeMap::new(),
+            temperature_archived: temperature,
+            last_share: 0.0,
+            last_fire: 0.0
         }
     }
 
     /// Creates a new instance of a `GasMixture` from two vectors.
-    /// Errors if both vectors are different sizes.
+    /// Errors if both vectors are different sizes, if any mole count is negative or not a number,
+    /// if `temperature` isn't a finite, non-negative number or if `volume` is 0.
     /// ## Example
     /// ```rust
     /// use libatmos::atmospherics::gases::GasMixture;
@@ -70,44 +118,113 @@ impl<'a> GasMixture<'a> {
     /// let mix = GasMixture::from_vecs(gas_vec, mole_vec, 273.15, 70).unwrap();
     /// assert_eq!(mix.get_moles(&gases::BZ).unwrap(), 50.0);
     /// ```
-    pub fn from_vecs(gas_types: Vec<&'a GasType>, moles: Vec<f64>, temperature: f64, volume: usize) -> Result<GasMixture, AtmosError> {
+    pub fn from_vecs(gas_types: Vec<&'a GasType>, moles: Vec<Real>, temperature: Real, volume: usize) -> Result<GasMixture<'a>, AtmosError> {
         if gas_types.len() != moles.len() {
             return Err(AtmosError::VectorLengthMismatch { gas_length: gas_types.len(), mole_length: moles.len() });
         }
+        if !temperature.is_finite() || temperature < 0.0 {
+            return Err(AtmosError::InvalidTemperature { temperature });
+        }
+        if volume == 0 {
+            return Err(AtmosError::InvalidVolume { volume: 0.0 });
+        }
+        if let Some((gas, amount)) = gas_types.iter().zip(moles.iter()).find(|(_, m)| !m.is_finite() || **m < 0.0) {
+            return Err(AtmosError::NegativeMoles { gas: gas.id.to_string(), moles: *amount });
+        }
 
         // gas_ids contains a vec of references to static gastypes located in constants/gases.rs
-        // this zips it into a hashmap with moles, so it looks like
+        // this zips it into a map with moles, so it looks like
         // [(constants::gases::O2, 5), (constants::gases::N2, 300.7)...]
         let gases = gas_types.into_iter()
-                             .zip(moles.into_iter())
-                             .collect::<HashMap<_, _>>();
+                             .zip(moles)
+                             .collect::<BTreeMap<_, _>>();
         info!("New gas mixture created using from_vecs; temperature {} and volume {}", temperature, volume);
         Ok(GasMixture {
             gases,
             temperature,
-            volume
+            volume,
+            gases_archived: BTreeMap::new(),
+            temperature_archived: temperature,
+            last_share: 0.0,
+            last_fire: 0.0
         })
     }
 
+    /// Same as `from_vecs`, but with typed units so temperature and volume can't be swapped.
+    /// `GasMixture` stores volume in whole litres, so `volume` is rounded to the nearest litre.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// use libatmos::units::*;
+    /// let mix = GasMixture::from_units(vec![&gases::O2], vec![Moles(50.0)], Celsius(20.0), Liters(70.0)).unwrap();
+    /// assert_eq!(mix.liters(), Liters(70.0));
+    /// assert!((mix.temperature - 293.15).abs() < 1e-4);
+    /// ```
+    pub fn from_units<T: Into<Kelvin>>(gas_types: Vec<&'a GasType>, moles: Vec<Moles>, temperature: T, volume: Liters) -> Result<GasMixture<'a>, AtmosError> {
+        if !volume.value().is_finite() || volume.value() < 0.0 {
+            return Err(AtmosError::InvalidVolume { volume: volume.value() });
+        }
+        let moles = moles.into_iter().map(Moles::value).collect();
+        GasMixture::from_vecs(gas_types, moles, temperature.into().value(), volume.value().round() as usize)
+    }
+
     /// Returns true if gas of type `gas_type` exists in the mixture and has >0 moles.. Pretty simple.
     /// ## Example
     /// ```rust
     ///
     /// ```
     pub fn gas_exists(&self, gas_type: &'a GasType) -> bool {
-        self.gases.contains_key(gas_type) && self.get_moles(gas_type).unwrap() > 0.0 // get_moles is guaranteed by contains_key so this is safe
+        match self.gases.get(gas_type) {
+            Some(moles) => *moles > 0.0,
+            None => false
+        }
+    }
+
+    /// Checks that the mixture is physically sane: a finite, non-negative temperature and
+    /// no negative or NaN mole counts. See `invariants` for the conservation checks.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// let mut mix = GasMixture::from_empty(293.15, 70);
+    /// assert!(mix.validate().is_ok());
+    /// mix.temperature = -1.0;
+    /// assert!(mix.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<(), AtmosError> {
+        if !self.temperature.is_finite() || self.temperature < 0.0 {
+            return Err(AtmosError::InvalidTemperature { temperature: self.temperature });
+        }
+        match self.gases.iter().find(|(_, moles)| !moles.is_finite() || **moles < 0.0) {
+            Some((gastype, moles)) => Err(AtmosError::NegativeMoles { gas: gastype.id.to_string(), moles: *moles }),
+            None => Ok(())
+        }
+    }
+
+    /// Iterates over every gas in the mixture and its mole count, in registry order
+    /// (the order of `constants::gases::ALL`). Includes gases asserted at 0 mols.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mix = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![10.0, 20.0], 293.15, 70).unwrap();
+    /// let ids = mix.iter().map(|(gas, _)| gas.id).collect::<Vec<_>>();
+    /// assert_eq!(ids, vec!["o2", "plasma"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&'a GasType, Real)> + '_ {
+        self.gases.iter().map(|(gastype, moles)| (*gastype, *moles))
     }
 
     /// Returns true if gases list is completely empty.
     /// ## Example
     /// ```rust
     /// use libatmos::atmospherics::gases::GasMixture;
-    /// let mut mix = GasMixture::from_empty(100.0, 100.0); // well, duh, now its empty
+    /// let mix = GasMixture::from_empty(100.0, 100); // well, duh, now its empty
     ///
     /// assert_eq!(mix.is_empty(), true);
     /// ```
     pub fn is_empty(&self) -> bool {
-        return self.gases.is_empty();
+        self.gases.is_empty()
     }
 
     /// Guarantees that a `GasMixture` has a gas of type `gas_id`.
@@ -131,56 +248,75 @@ impl<'a> GasMixture<'a> {
         false
     }
 
+    /// Removes every gas at 0 mols from the mixture, tgstation's `GAS_GARBAGE_COLLECT()`.
+    /// `react()` does this after any reaction happens.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut mix = GasMixture::from_empty(293.15, 70);
+    /// mix.assert_gas(&gases::O2);
+    /// mix.garbage_collect();
+    /// assert!(mix.is_empty());
+    /// ```
+    pub fn garbage_collect(&mut self) {
+        self.gases.retain(|_, moles| *moles > 0.0);
+    }
+
     // add_gas would be here, but its functionally useless in this library
     // as its really only in SS13 for performance reasons
 
     // TODO: Maybe figure out a more idiomatic way to do get_moles and change_moles
 
-    /// Returns `Some(mole count)` of GasType if it exists, `None` otherwise.
+    /// Returns `Some(mole count)` of GasType if it's in the mixture (even at 0 mols,
+    /// i.e. after `assert_gas()`), `None` otherwise.
     /// ## Example
     /// ```rust
     /// use libatmos::atmospherics::gases::GasMixture;
     /// use libatmos::constants::gases;
     /// // 50 mol O2, 50 mol N2, 100 K at 100 L
-    /// let mut mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100.0)   ///
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100).unwrap();
     ///
     /// assert_eq!(mix.get_moles(&gases::O2).unwrap(), 50.0);
     /// ```
-    pub fn get_moles(&self, gas_type: &'a GasType) -> Option<f64> {
-        if self.gas_exists(gas_type) {
-            return Some(self.gases.get(gas_type)?.clone()); // not sure why get() returns a reference.. to a double.. when it could just clone but..
-        }
-        None
+    pub fn get_moles(&self, gas_type: &'a GasType) -> Option<Real> {
+        self.gases.get(gas_type).copied()
     }
 
     /// Changes the mole count of gas `gas_type` to `moles`. Errors if the gas isn't
-    /// in the mixture.
-    pub fn change_moles(&mut self, gas_type: &'a GasType, moles: f64) -> Result<(), AtmosError> {
-        if self.gas_exists(gas_type) {
-            self.gases.entry(gas_type)
-                      .and_modify(|v| { *v = moles });
-            return Ok(())
+    /// in the mixture (call `assert_gas()` first) or if `moles` is negative or not a number.
+    pub fn change_moles(&mut self, gas_type: &'a GasType, moles: Real) -> Result<(), AtmosError> {
+        if !moles.is_finite() || moles < 0.0 {
+            return Err(AtmosError::NegativeMoles { gas: gas_type.id.to_string(), moles });
+        }
+        match self.gases.get_mut(gas_type) {
+            Some(v) => {
+                *v = moles;
+                Ok(())
+            },
+            None => Err(AtmosError::GasNotFound { gas: gas_type.id.to_string() })
         }
-        Err(AtmosError::GasNotFound { gas: gas_type })
     }
 
-    /// Returns total mole count of the gas mixture.
-    pub fn total_moles(&self) -> Result<f64, AtmosError> {
-        if !self.is_empty() {
-            let sum = self.gases.values()
-                                .fold(0.0, |acc, x| acc + x);
-            return Ok(sum)
-        }
-        Err(AtmosError::GasMixtureEmpty)
+    /// Returns total mole count of the gas mixture. 0 for an empty mixture.
+    pub fn total_moles(&self) -> Real {
+        self.gases.values()
+                  .fold(0.0, |acc, x| acc + x)
     }
 
-    /// Returns pressure of the mixture in kPa.
-    pub fn return_pressure(&self) -> Result<f64, AtmosError> {
-        if !self.is_empty() { // P = nRT / V
-            let pressure = (self.total_moles().unwrap() * num::R_IDEAL_GAS_EQUATION * self.temperature) / self.volume as f64;
-            return Ok(pressure);
+    /// Returns pressure of the mixture in kPa. Empty (or 0 L) mixtures are a vacuum at 0 kPa.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// let vacuum = GasMixture::from_empty(293.15, 1000);
+    /// assert_eq!(vacuum.return_pressure(), 0.0);
+    /// ```
+    pub fn return_pressure(&self) -> Real {
+        if self.volume == 0 {
+            return 0.0;
         }
-        Err(AtmosError::GasMixtureEmpty)
+        // P = nRT / V
+        (self.total_moles() * num::R_IDEAL_GAS_EQUATION * self.temperature) / self.volume as Real
     }
 
     /// Returns heat capacity of the mixture.
@@ -190,16 +326,12 @@ impl<'a> GasMixture<'a> {
     /// use libatmos::atmospherics::gases::GasMixture;
     /// use libatmos::constants::gases;
     /// // 50 mol O2, 50 mol N2, 100 K at 100 L
-    /// let mut mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100.0);
-    /// assert_eq!(mix.heat_capacity().unwrap(), 2000.0) // mix heatcap = (50 * 20) * 2 = 2000
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100).unwrap();
+    /// assert_eq!(mix.heat_capacity(), 2000.0) // mix heatcap = (50 * 20) * 2 = 2000
     /// ```
-    pub fn heat_capacity(&self) -> Result<f64, AtmosError> {
-        if !self.is_empty() {
-            let sum = self.gases.iter()
-                                .fold(0.0, |acc, (gastype, moles)| acc + ((gastype.specific_heat as f64) * moles)); // big boy
-            return Ok(sum);
-        }
-        Err(AtmosError::GasMixtureEmpty)
+    pub fn heat_capacity(&self) -> Real {
+        self.gases.iter()
+                  .fold(0.0, |acc, (gastype, moles)| acc + ((gastype.specific_heat as Real) * moles)) // big boy
     }
 
     /// Returns thermal energy of the mixture
@@ -209,42 +341,40 @@ impl<'a> GasMixture<'a> {
     /// use libatmos::atmospherics::gases::GasMixture;
     /// use libatmos::constants::gases;
     /// // 50 mol O2, 50 mol N2, 100 K at 100 L
-    /// let mut mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100.0);
-    /// assert_eq!(mix.thermal_energy().unwrap(), 200_000.0) // mix thermal energy = 2000 (heatcap) * 100
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100).unwrap();
+    /// assert_eq!(mix.thermal_energy(), 200_000.0) // mix thermal energy = 2000 (heatcap) * 100
     /// ```
-    pub fn thermal_energy(&self) -> Result<f64, AtmosError> {
-        if !self.is_empty() {
-            let thermal_energy = self.heat_capacity().unwrap() * self.temperature;
-            return Ok(thermal_energy);
-        }
-        Err(AtmosError::GasMixtureEmpty)
+    pub fn thermal_energy(&self) -> Real {
+        self.heat_capacity() * self.temperature
     }
 
     /// Merges two gas mixtures into self, adding together their gases mole counts and
-    /// equalizing temperatures according to heat capacity.
+    /// equalizing temperatures according to heat capacity. Either mixture can be empty,
+    /// e.g. when releasing gas into a vacuum.
     ///
     /// ## Example
     /// ```rust
     /// use libatmos::atmospherics::gases::GasMixture;
     /// use libatmos::constants::gases;
     ///
-    /// let mut giver = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100.0);
+    /// let giver = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 100.0, 100).unwrap();
     /// let mut taker = giver.clone();
     ///
-    /// taker.merge(giver).unwrap();
-    /// assert_eq!(taker.get_moles(&gases::O2), 100.0) // added up
+    /// taker.merge(giver);
+    /// assert_eq!(taker.get_moles(&gases::O2).unwrap(), 100.0) // added up
     /// ```
-    pub fn merge(&mut self, giver: GasMixture<'a>) -> Result<(), AtmosError> {
-        if self.is_empty() || giver.is_empty() {
-            return Err(AtmosError::GasMixtureEmpty);
-        }
+    pub fn merge(&mut self, giver: GasMixture<'a>) {
+        #[cfg(feature = "invariants")]
+        let before = (self.clone(), giver.clone());
 
         if (self.temperature - giver.temperature).abs() > num::MINIMUM_TEMP_DELTA_TO_CONSIDER {
-            let self_heatcap = self.heat_capacity().unwrap();
-            let giver_heatcap = giver.heat_capacity().unwrap();
+            let self_heatcap = self.heat_capacity();
+            let giver_heatcap = giver.heat_capacity();
             let combined_heatcap = giver_heatcap + self_heatcap;
 
-            self.temperature = (giver.temperature * giver_heatcap + self.temperature * self_heatcap) / combined_heatcap;
+            if combined_heatcap > 0.0 {
+                self.temperature = (giver.temperature * giver_heatcap + self.temperature * self_heatcap) / combined_heatcap;
+            }
         }
 
         info!("Merging gas mixture taker {} with giver {}", self, giver);
@@ -253,73 +383,763 @@ impl<'a> GasMixture<'a> {
             self.change_moles(gastype, self.get_moles(gastype).unwrap() + moles).unwrap();
         }
 
-        Ok(())
+        #[cfg(feature = "invariants")]
+        {
+            // tiny temperature differences aren't equalized, which is allowed to cost a bit of energy
+            let delta = (before.0.temperature - before.1.temperature).abs();
+            let slack = if delta > num::MINIMUM_TEMP_DELTA_TO_CONSIDER { 0.0 } else { delta * before.1.heat_capacity() };
+            invariants::enforce(invariants::check_conservation("merge", &[&before.0, &before.1], &[self], slack));
+        }
+    }
+
+    /// Makes this mixture a copy of `sample`: same gases, same temperature. Keeps its own volume.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let sample = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 500.0, 70).unwrap();
+    /// let mut mix = GasMixture::from_empty(293.15, 2500);
+    /// mix.copy_from(&sample);
+    /// assert_eq!(mix.get_moles(&gases::O2).unwrap(), 10.0);
+    /// assert_eq!(mix.temperature, 500.0);
+    /// assert_eq!(mix.volume, 2500);
+    /// ```
+    pub fn copy_from(&mut self, sample: &GasMixture<'a>) {
+        self.gases = sample.gases.clone();
+        self.temperature = sample.temperature;
     }
 
     /// Removes a quantity of gas in `mol`s from the gas mixture.
+    /// Removed amounts are `QUANTIZE`d the same way tgstation does it.
+    /// Removing from an empty mixture just gives back another empty mixture.
     ///
     /// ## Example
-    pub fn remove(&mut self, mut amount: f64) -> Result<GasMixture, AtmosError> {
-        let total_moles = self.total_moles().unwrap();
-        if amount > total_moles {
-            amount = total_moles;
-        }
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// let mut vacuum = GasMixture::from_empty(293.15, 70);
+    /// assert!(vacuum.remove(10.0).unwrap().is_empty());
+    /// ```
+    pub fn remove(&mut self, mut amount: Real) -> Result<GasMixture<'a>, AtmosError> {
         if amount < 0.0 {
             return Err(AtmosError::LessThanZero { value: amount });
         }
-
         let mut removed = GasMixture::from_empty(self.temperature, self.volume);
+        let total_moles = self.total_moles();
+        if total_moles <= 0.0 {
+            return Ok(removed);
+        }
+        if amount > total_moles {
+            amount = total_moles;
+        }
+        #[cfg(feature = "invariants")]
+        let before = self.clone();
+
         for (gastype, moles) in self.gases.clone().iter() { // clone is necessary to avoid mutability issues
+            // quantizing can round the removed amount up past what's there, which used to leave
+            // slightly negative moles behind when removing (almost) everything
+            let removed_moles = byond::quantize((moles / total_moles) * amount).min(*moles); // percentage
             removed.assert_gas(gastype);
-            removed.change_moles(gastype, (moles / total_moles) * amount).unwrap(); // percentage
-            self.change_moles(gastype, moles - removed.get_moles(gastype).unwrap()).unwrap();
+            removed.change_moles(gastype, removed_moles).unwrap();
+            self.change_moles(gastype, moles - removed_moles).unwrap();
         }
+
+        #[cfg(feature = "invariants")]
+        invariants::enforce(invariants::check_conservation("remove", &[&before], &[self, &removed], 0.0));
         Ok(removed)
     }
 
     /// Removes a percentage / ratio of gas from the gas mixture, as opposed to `mol` count directly.
     ///
     /// ## Example
-    pub fn remove_ratio(&mut self, mut ratio: f64) -> Result<GasMixture, AtmosError> {
-        if ratio <= 0.0 {
+    pub fn remove_ratio(&mut self, mut ratio: Real) -> Result<GasMixture<'a>, AtmosError> {
+        if ratio < 0.0 {
             return Err(AtmosError::LessThanZero { value: ratio });
         }
         if ratio > 1.0 {
             ratio = 1.0;
         }
 
+        #[cfg(feature = "invariants")]
+        let before = self.clone();
+
         let mut removed = GasMixture::from_empty(self.temperature, self.volume);
         for (gastype, moles) in self.gases.clone().iter() {
+            let removed_moles = byond::quantize(moles * ratio).min(*moles);
             removed.assert_gas(gastype);
-            removed.change_moles(gastype, moles * ratio).unwrap();
-            self.change_moles(gastype, moles - (moles * ratio)).unwrap();
+            removed.change_moles(gastype, removed_moles).unwrap();
+            self.change_moles(gastype, moles - removed_moles).unwrap();
         }
+
+        #[cfg(feature = "invariants")]
+        invariants::enforce(invariants::check_conservation("remove_ratio", &[&before], &[self, &removed], 0.0));
         Ok(removed)
     }
 
-    // Not implementing these yet because they are rarely used except in environmental
-    // atmos + machinery and I'm not coding that yet
-    // TODO: Implement share() / temperature_share()
+    // Gas movement helpers used by machinery. These all sit on top of remove() / merge().
+
+    /// Moves `moles` mols of gas from this mixture into `target`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut source = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 70).unwrap();
+    /// let mut target = GasMixture::from_vecs(vec![&gases::N2], vec![10.0], 293.15, 70).unwrap();
+    /// source.transfer_to(&mut target, 40.0).unwrap();
+    /// assert_eq!(target.get_moles(&gases::O2).unwrap(), 40.0);
+    /// assert_eq!(source.get_moles(&gases::O2).unwrap(), 60.0);
+    /// ```
+    pub fn transfer_to(&mut self, target: &mut GasMixture<'a>, moles: Real) -> Result<(), AtmosError> {
+        let removed = self.remove(moles)?;
+        target.merge(removed);
+        Ok(())
+    }
+
+    /// Moves a ratio (0 to 1) of this mixture's gas into `target`.
+    pub fn transfer_ratio_to(&mut self, target: &mut GasMixture<'a>, ratio: Real) -> Result<(), AtmosError> {
+        let removed = self.remove_ratio(ratio)?;
+        target.merge(removed);
+        Ok(())
+    }
+
+    /// Moves `volume` litres' worth of this mixture's gas into `target`, i.e. the gas that
+    /// would be in `volume` L of this mixture. Used by volume pumps.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut source = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 200).unwrap();
+    /// let mut target = GasMixture::from_vecs(vec![&gases::N2], vec![10.0], 293.15, 70).unwrap();
+    /// source.transfer_volume_to(&mut target, 50.0).unwrap(); // a quarter of the source
+    /// assert_eq!(target.get_moles(&gases::O2).unwrap(), 25.0);
+    /// ```
+    pub fn transfer_volume_to(&mut self, target: &mut GasMixture<'a>, volume: Real) -> Result<(), AtmosError> {
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(AtmosError::InvalidVolume { volume });
+        }
+        let ratio = volume / self.volume as Real;
+        self.transfer_ratio_to(target, ratio)
+    }
+
+    /// Pumps gas into `output` until it reaches `target_pressure` kPa, same as a pressure pump.
+    /// Returns `true` if any gas was moved, `false` if `output` was already at (or above) the target
+    /// or this mixture has nothing to give.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut canister = GasMixture::from_vecs(vec![&gases::O2], vec![1000.0], 293.15, 1000).unwrap();
+    /// let mut tank = GasMixture::from_empty(293.15, 70);
+    /// assert!(canister.pump_gas_to(&mut tank, 500.0));
+    /// assert!((tank.return_pressure() - 500.0).abs() < 0.01);
+    /// ```
+    pub fn pump_gas_to(&mut self, output: &mut GasMixture<'a>, target_pressure: Real) -> bool {
+        let output_starting_pressure = output.return_pressure();
+        let pressure_delta = target_pressure - output_starting_pressure;
+        if pressure_delta < 0.01 {
+            return false;
+        }
+
+        if self.total_moles() > 0.0 && self.temperature > 0.0 {
+            // n = PV / RT
+            let transfer_moles = (pressure_delta * output.volume as Real) / (self.temperature * num::R_IDEAL_GAS_EQUATION);
+            let removed = self.remove(transfer_moles).unwrap(); // delta is positive, so this can't fail
+            output.merge(removed);
+            return true;
+        }
+        false
+    }
+
+    /// Releases gas into `output` like a release valve (canisters, tanks): moves gas until
+    /// `output` reaches `target_pressure` kPa, but never more than half the pressure difference
+    /// between the two, and never if the difference is under 10 kPa.
+    /// Returns `true` if any gas was moved.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut canister = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 293.15, 1000).unwrap();
+    /// let mut room = GasMixture::from_empty(293.15, 1000);
+    /// assert!(canister.release_gas_to(&mut room, 1000.0));
+    /// // only half the difference gets through in one go
+    /// assert!((room.return_pressure() - canister.return_pressure()).abs() < 0.01);
+    /// ```
+    pub fn release_gas_to(&mut self, output: &mut GasMixture<'a>, target_pressure: Real) -> bool {
+        let output_starting_pressure = output.return_pressure();
+        let input_starting_pressure = self.return_pressure();
+
+        if output_starting_pressure >= target_pressure.min(input_starting_pressure - 10.0) {
+            return false;
+        }
+
+        let pressure_delta = (target_pressure - output_starting_pressure)
+            .min((input_starting_pressure - output_starting_pressure) / 2.0);
+
+        if self.total_moles() > 0.0 && self.temperature > 0.0 {
+            let transfer_moles = (pressure_delta * output.volume as Real) / (self.temperature * num::R_IDEAL_GAS_EQUATION);
+            let removed = self.remove(transfer_moles).unwrap(); // delta is positive, so this can't fail
+            output.merge(removed);
+            return true;
+        }
+        false
+    }
+
+    // Energy helpers. Anything that heats or cools a mixture (heaters, freezers, fires)
+    // should go through these instead of setting `temperature` directly.
+
+    /// Adds `joules` of thermal energy to the mixture (or removes it, if negative) and returns
+    /// how much energy was actually transferred. Mixtures with (next to) no heat capacity
+    /// can't hold energy, so nothing happens and 0 is returned. Cooling stops at `TCMB`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// // 100 mol O2 = 2000 J/K
+    /// let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 300.0, 100).unwrap();
+    /// assert_eq!(mix.adjust_thermal_energy(20_000.0), 20_000.0);
+    /// assert_eq!(mix.temperature, 310.0);
+    ///
+    /// let mut vacuum = GasMixture::from_empty(300.0, 100);
+    /// assert_eq!(vacuum.adjust_thermal_energy(20_000.0), 0.0);
+    /// ```
+    pub fn adjust_thermal_energy(&mut self, joules: Real) -> Real {
+        let heat_capacity = self.heat_capacity();
+        if heat_capacity < num::MINIMUM_HEAT_CAPACITY {
+            return 0.0;
+        }
+
+        // can't take out more energy than the mixture has above the cosmic background
+        let available = ((self.temperature - num::TCMB) * heat_capacity).max(0.0);
+        if -joules >= available {
+            self.temperature = self.temperature.min(num::TCMB);
+            return -available;
+        }
+        self.temperature += joules / heat_capacity;
+        joules
+    }
+
+    /// Sets the temperature of the mixture and returns the energy that took, in joules.
+    /// Positive if energy was added, negative if it was removed. Handy for keeping track of
+    /// where energy went when something has to pin a mixture to a temperature.
+    /// Mixtures with (next to) no heat capacity still get the new temperature, but take 0 J.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 300.0, 100).unwrap();
+    /// assert_eq!(mix.set_temperature_preserving_energy(250.0), -100_000.0);
+    /// assert_eq!(mix.temperature, 250.0);
+    /// ```
+    pub fn set_temperature_preserving_energy(&mut self, temperature: Real) -> Real {
+        let heat_capacity = self.heat_capacity();
+        let old_temperature = self.temperature;
+        self.temperature = temperature;
+        if heat_capacity < num::MINIMUM_HEAT_CAPACITY {
+            return 0.0;
+        }
+        (temperature - old_temperature) * heat_capacity
+    }
+
+    /// Heats (or cools) the mixture towards `target` K with at most `max_power` watts over
+    /// `dt` seconds, like a space heater or freezer would. Never overshoots the target.
+    /// Returns the energy transferred in joules, negative when cooling.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 300.0, 100).unwrap();
+    /// // 10 kW for 2 seconds is 20 kJ, only enough for 10 K
+    /// assert_eq!(mix.heat_to(400.0, 10_000.0, 2.0), 20_000.0);
+    /// assert_eq!(mix.temperature, 310.0);
+    /// // plenty of power, so it stops right at the target
+    /// assert_eq!(mix.heat_to(320.0, 1_000_000.0, 2.0), 20_000.0);
+    /// assert_eq!(mix.temperature, 320.0);
+    /// ```
+    pub fn heat_to(&mut self, target: Real, max_power: Real, dt: Real) -> Real {
+        let heat_capacity = self.heat_capacity();
+        if heat_capacity < num::MINIMUM_HEAT_CAPACITY {
+            return 0.0;
+        }
+
+        let max_energy = (max_power * dt).abs();
+        let needed = (target - self.temperature) * heat_capacity;
+        if needed.abs() <= max_energy {
+            // close enough to get there this tick, so set it exactly instead of adding a rounded delta
+            return self.set_temperature_preserving_energy(target);
+        }
+        self.adjust_thermal_energy(max_energy.copysign(needed))
+    }
+
+    // Analysis stuff, i.e. what gas analyzers and air alarms show
+
+    /// Returns the mole fraction (0 to 1) of `gas_type` in the mixture. Gases that
+    /// aren't in the mixture (or mixtures that are empty) have a fraction of 0.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![25.0, 75.0], 293.15, 100).unwrap();
+    /// assert_eq!(mix.mole_fraction(&gases::O2), 0.25);
+    /// assert_eq!(mix.mole_fraction(&gases::PLASMA), 0.0);
+    /// ```
+    pub fn mole_fraction(&self, gas_type: &'a GasType) -> Real {
+        let total_moles = self.total_moles();
+        if total_moles <= 0.0 {
+            return 0.0;
+        }
+        self.get_moles(gas_type).unwrap_or(0.0) / total_moles
+    }
+
+    /// Same as `mole_fraction()`, but as a percentage from 0 to 100.
+    pub fn percentage(&self, gas_type: &'a GasType) -> Real {
+        self.mole_fraction(gas_type) * 100.0
+    }
 
-    /// It shares!
+    /// Returns the partial pressure of `gas_type` in kPa, i.e. the pressure it would have
+    /// if it were the only gas in the mixture.
     /// ## Example
-    pub fn share(&mut self, _sharer: GasMixture, _atmos_adjacent_turfs: f64) {
-        unimplemented!();
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![25.0, 75.0], 300.0, 100).unwrap();
+    /// let o2_pressure = mix.partial_pressure(&gases::O2);
+    /// assert!((o2_pressure - 623.25).abs() < 0.01); // (25 * 8.31 * 300) / 100
+    /// ```
+    pub fn partial_pressure(&self, gas_type: &'a GasType) -> Real {
+        self.mole_fraction(gas_type) * self.return_pressure()
+    }
+
+    /// Returns the average molar mass of the mixture in g/mol. 0 for an empty mixture.
+    pub fn molar_mass(&self) -> Real {
+        let total_moles = self.total_moles();
+        if total_moles <= 0.0 {
+            return 0.0;
+        }
+        let mass = self.gases.iter()
+                             .fold(0.0, |acc, (gastype, moles)| acc + gastype.molar_mass * moles);
+        mass / total_moles
     }
 
-    /// It shares (temperature)!
+    /// Returns the density of the mixture in g/L. 0 for an empty (or 0 L) mixture.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![50.0, 50.0], 293.15, 100).unwrap();
+    /// assert_eq!(mix.molar_mass(), 30.0); // (32 + 28) / 2
+    /// assert_eq!(mix.density(), 30.0); // (50 * 32 + 50 * 28) g / 100 L
+    /// ```
+    pub fn density(&self) -> Real {
+        if self.volume == 0 {
+            return 0.0;
+        }
+        self.molar_mass() * self.total_moles() / self.volume as Real
+    }
+
+    /// Returns every gas in the mixture with its moles, mole fraction and partial
+    /// pressure, sorted from most to least moles. Gases at 0 mols are left out.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![21.0, 79.0], 293.15, 100).unwrap();
+    /// let composition = mix.composition();
+    /// assert_eq!(composition[0].gas, &gases::N2);
+    /// assert_eq!(composition[1].fraction, 0.21);
+    /// ```
+    pub fn composition(&self) -> Vec<GasComponent<'a>> {
+        let total_moles = self.total_moles();
+        let pressure = self.return_pressure();
+        let mut components = self.gases.iter()
+                                       .filter(|(_, moles)| **moles > 0.0)
+                                       .map(|(gastype, moles)| GasComponent {
+                                           gas: gastype,
+                                           moles: *moles,
+                                           fraction: moles / total_moles,
+                                           partial_pressure: (moles / total_moles) * pressure
+                                       })
+                                       .collect::<Vec<_>>();
+        // most moles first; the sort is stable, so ties stay in registry order
+        components.sort_by(|a, b| b.moles.partial_cmp(&a.moles)
+                                         .unwrap_or(std::cmp::Ordering::Equal));
+        components
+    }
+
+    /// Returns true if `species` could breathe this mixture without its lungs complaining,
+    /// going by the partial pressure limits in `Species`. Empty mixtures are only breathable
+    /// by species that don't need any gas at all.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::{gases, species};
+    /// // standard station air, one 2500 L turf's worth
+    /// let air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![21.8, 82.1], 293.15, 2500).unwrap();
+    /// assert!(air.is_breathable_for(&species::HUMAN));
+    /// assert!(!air.is_breathable_for(&species::PLASMAMAN));
+    /// ```
+    pub fn is_breathable_for(&self, species: &Species) -> bool {
+        let pp = |gas_type: &'a GasType| self.partial_pressure(gas_type);
+        // a max of 0 means "no limit", same as tgstation lungs
+        let within = |pressure: Real, min: Real, max: Real| pressure >= min && (max == 0.0 || pressure <= max);
+
+        within(pp(&gases::O2), species.safe_oxygen_min, species.safe_oxygen_max)
+            && within(pp(&gases::N2), species.safe_nitro_min, species.safe_nitro_max)
+            && within(pp(&gases::CO2), species.safe_co2_min, species.safe_co2_max)
+            && within(pp(&gases::PLASMA), species.safe_toxins_min, species.safe_toxins_max)
+    }
+
+    // Typed unit versions of the raw Real methods above. See `crate::units`.
+
+    /// Returns the temperature of the mixture in `Kelvin`.
+    pub fn kelvin(&self) -> Kelvin {
+        Kelvin(self.temperature)
+    }
+
+    /// Sets the temperature of the mixture from any temperature unit.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::units::*;
+    /// let mut mix = GasMixture::from_empty(293.15, 70);
+    /// mix.set_kelvin(Celsius(-273.15));
+    /// assert_eq!(mix.kelvin(), Kelvin(0.0));
+    /// ```
+    pub fn set_kelvin<T: Into<Kelvin>>(&mut self, temperature: T) {
+        self.temperature = temperature.into().value();
+    }
+
+    /// Returns the volume of the mixture in `Liters`.
+    pub fn liters(&self) -> Liters {
+        Liters(self.volume as Real)
+    }
+
+    /// Typed version of `get_moles()`.
+    pub fn moles(&self, gas_type: &'a GasType) -> Option<Moles> {
+        self.get_moles(gas_type).map(Moles)
+    }
+
+    /// Typed version of `change_moles()`.
+    pub fn set_moles(&mut self, gas_type: &'a GasType, moles: Moles) -> Result<(), AtmosError> {
+        self.change_moles(gas_type, moles.value())
+    }
+
+    /// Typed version of `total_moles()`.
+    pub fn total_mol(&self) -> Moles {
+        Moles(self.total_moles())
+    }
+
+    /// Typed version of `return_pressure()`. Convert the result with `Atmospheres::from` or
+    /// `Psi::from` if kPa isn't what you want.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// use libatmos::units::*;
+    /// let mix = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 300.0, 100).unwrap();
+    /// let pressure = mix.pressure();
+    /// assert!((pressure.value() - 2493.0).abs() < 0.01); // (100 * 8.31 * 300) / 100
+    /// assert!((Atmospheres::from(pressure).value() - 24.6039).abs() < 0.001);
+    /// ```
+    pub fn pressure(&self) -> KiloPascal {
+        KiloPascal(self.return_pressure())
+    }
+
+    /// Typed version of `heat_capacity()`.
+    pub fn heat_capacity_per_kelvin(&self) -> JoulesPerKelvin {
+        JoulesPerKelvin(self.heat_capacity())
+    }
+
+    /// Typed version of `thermal_energy()`.
+    pub fn thermal_joules(&self) -> Joules {
+        Joules(self.thermal_energy())
+    }
+
+    /// Saves the current mole counts and temperature for `share()` and `temperature_share()` to
+    /// work from, `archive()` in tgstation. LINDA archives each turf once a cycle before it shares.
+    pub fn archive(&mut self) {
+        self.gases_archived = self.gases.clone();
+        self.temperature_archived = self.temperature;
+    }
+
+    /// Temperature as of the last `archive()`, or the starting temperature if it's never been archived.
+    pub fn temperature_archived(&self) -> Real {
+        self.temperature_archived
+    }
+
+    /// Heat capacity as of the last `archive()`, `heat_capacity(ARCHIVE)`.
+    pub fn heat_capacity_archived(&self) -> Real {
+        self.gases_archived.iter()
+                           .fold(0.0, |acc, (gastype, moles)| acc + ((gastype.specific_heat as Real) * moles))
+    }
+
+    /// Total mols the last `share()` moved, counting both directions.
+    pub fn last_share(&self) -> Real {
+        self.last_share
+    }
+
+    /// Shares air with `sharer`, the air of a neighbouring turf. Every gas moves a
+    /// `1 / (atmos_adjacent_turfs + 1)` share of the difference between the two, from the fuller
+    /// mixture to the emptier one, and takes its heat with it. Works off the *archived* moles and
+    /// temperatures, so `archive()` both mixtures first; a mixture that's never been archived
+    /// doesn't give anything up.
     ///
+    /// Returns the pressure difference between the two in kPa, positive if gas moved out of
+    /// `self`, for space wind. 0 if hardly any gas moved on net (gases swapping places evenly
+    /// don't count) and `self` was no more than `MINIMUM_TEMPERATURE_TO_MOVE` hotter.
     /// ## Example
-    pub fn temperature_share(&self) {
-        unimplemented!();
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut full = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 2500).unwrap();
+    /// let mut empty = GasMixture::from_empty(293.15, 2500);
+    /// full.archive();
+    /// empty.archive();
+    ///
+    /// let difference = full.share(&mut empty, 1); // one adjacent turf, so half the difference moves
+    /// assert_eq!(full.get_moles(&gases::O2).unwrap(), 50.0);
+    /// assert_eq!(empty.get_moles(&gases::O2).unwrap(), 50.0);
+    /// assert_eq!(full.last_share(), 50.0);
+    /// assert!(difference > 0.0);
+    /// ```
+    pub fn share(&mut self, sharer: &mut GasMixture<'a>, atmos_adjacent_turfs: usize) -> Real {
+        #[cfg(feature = "invariants")]
+        let before = (self.clone(), sharer.clone());
+
+        let difference = self.share_impl(sharer, atmos_adjacent_turfs, None);
+
+        #[cfg(feature = "invariants")]
+        {
+            // with the temperatures too close to bother, gas moves without adjusting either
+            // temperature, which doesn't quite conserve energy. Same as tgstation.
+            let moved_heat_capacity = self.gases.iter()
+                                                .map(|(gas, moles)| (moles - before.0.get_moles(gas).unwrap_or(0.0)).abs() * gas.specific_heat as Real)
+                                                .sum::<Real>();
+            let mut slack = moved_heat_capacity * (before.0.temperature - before.1.temperature).abs();
+            // conduction takes heat out of our current temperature at our archived heat capacity,
+            // so if we've changed since the last archive (LINDA sharing with an earlier neighbour
+            // this cycle), a bit of heat appears or disappears. Also same as tgstation.
+            slack += (before.0.heat_capacity() - before.0.heat_capacity_archived()).abs()
+                * (before.0.temperature_archived - before.1.temperature_archived).abs();
+            invariants::enforce(invariants::check_conservation("share", &[&before.0, &before.1], &[self, sharer], slack));
+        }
+        difference
+    }
+
+    /// `share()` with space. Space is a vacuum at `TCMB` that never fills up, and pretends to have
+    /// a heat capacity of `HEAT_CAPACITY_VACUUM` so it cools things down like you'd expect. Returns
+    /// the pressure difference, like `share()`.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut air = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 2500).unwrap();
+    /// air.archive();
+    /// air.share_with_space(4);
+    /// assert_eq!(air.get_moles(&gases::O2).unwrap(), 80.0);
+    /// assert!(air.temperature < 293.15);
+    /// ```
+    pub fn share_with_space(&mut self, atmos_adjacent_turfs: usize) -> Real {
+        let mut space = GasMixture::from_empty(num::TCMB, self.volume);
+        self.share_impl(&mut space, atmos_adjacent_turfs, Some(num::HEAT_CAPACITY_VACUUM))
+    }
+
+    /// The actual `share()`. `sharer_heat_capacity` overrides the sharer's heat capacity, archived
+    /// or not, for immutable mixtures like space.
+    fn share_impl(&mut self, sharer: &mut GasMixture<'a>, atmos_adjacent_turfs: usize, sharer_heat_capacity: Option<Real>) -> Real {
+        let temperature_delta = self.temperature_archived - sharer.temperature_archived;
+        let abs_temperature_delta = temperature_delta.abs();
+
+        let mut old_self_heat_capacity = 0.0;
+        let mut old_sharer_heat_capacity = 0.0;
+        if abs_temperature_delta > num::MINIMUM_TEMP_DELTA_TO_CONSIDER {
+            old_self_heat_capacity = self.heat_capacity();
+            old_sharer_heat_capacity = sharer_heat_capacity.unwrap_or_else(|| sharer.heat_capacity());
+        }
+
+        let mut heat_capacity_self_to_sharer = 0.0; // heat capacity of the moles going from us to the sharer
+        let mut heat_capacity_sharer_to_self = 0.0; // and the other way
+        let mut moved_moles = 0.0;
+        let mut abs_moved_moles = 0.0;
+
+        // if both have every gas the other has, nothing can hit 0 and there's no need to garbage collect
+        let same_gases = self.gases.keys().eq(sharer.gases.keys());
+        for gas in sharer.gases.keys() {
+            self.gases.entry(*gas).or_insert(0.0);
+        }
+        for (gas, moles) in self.gases.iter_mut() {
+            let sharer_moles = sharer.gases.entry(*gas).or_insert(0.0);
+            let delta = byond::quantize(self.gases_archived.get(gas).copied().unwrap_or(0.0) - sharer.gases_archived.get(gas).copied().unwrap_or(0.0))
+                / (atmos_adjacent_turfs + 1) as Real;
+
+            if delta != 0.0 && abs_temperature_delta > num::MINIMUM_TEMP_DELTA_TO_CONSIDER {
+                let gas_heat_capacity = delta * gas.specific_heat as Real;
+                if delta > 0.0 {
+                    heat_capacity_self_to_sharer += gas_heat_capacity;
+                } else {
+                    heat_capacity_sharer_to_self -= gas_heat_capacity; // delta's negative
+                }
+            }
+            *moles -= delta;
+            *sharer_moles += delta;
+            moved_moles += delta;
+            abs_moved_moles += delta.abs();
+        }
+        self.last_share = abs_moved_moles;
+
+        if abs_temperature_delta > num::MINIMUM_TEMP_DELTA_TO_CONSIDER {
+            let new_self_heat_capacity = old_self_heat_capacity + heat_capacity_sharer_to_self - heat_capacity_self_to_sharer;
+            let new_sharer_heat_capacity = old_sharer_heat_capacity + heat_capacity_self_to_sharer - heat_capacity_sharer_to_self;
+
+            if new_self_heat_capacity > num::MINIMUM_HEAT_CAPACITY {
+                self.temperature = (old_self_heat_capacity * self.temperature
+                    - heat_capacity_self_to_sharer * self.temperature_archived
+                    + heat_capacity_sharer_to_self * sharer.temperature_archived) / new_self_heat_capacity;
+            }
+            if new_sharer_heat_capacity > num::MINIMUM_HEAT_CAPACITY {
+                sharer.temperature = (old_sharer_heat_capacity * sharer.temperature
+                    - heat_capacity_sharer_to_self * sharer.temperature_archived
+                    + heat_capacity_self_to_sharer * self.temperature_archived) / new_sharer_heat_capacity;
+
+                // if the sharer's heat capacity hardly changed, conduct some heat on top
+                if old_sharer_heat_capacity.abs() > num::MINIMUM_HEAT_CAPACITY
+                    && (new_sharer_heat_capacity / old_sharer_heat_capacity - 1.0).abs() < 0.1 {
+                    let sharer_heat_capacity = sharer_heat_capacity.unwrap_or_else(|| sharer.heat_capacity_archived());
+                    if let Some(temperature) = self.conduct(sharer.temperature_archived, sharer_heat_capacity, num::OPEN_HEAT_TRANSFER_COEFFICIENT) {
+                        sharer.temperature = temperature;
+                    }
+                }
+            }
+        }
+
+        if !same_gases {
+            self.garbage_collect();
+            sharer.garbage_collect();
+        }
+        if temperature_delta > num::MINIMUM_TEMPERATURE_TO_MOVE || moved_moles.abs() > num::MINIMUM_MOLES_DELTA_TO_MOVE {
+            let our_moles = self.total_moles();
+            let their_moles = sharer.total_moles();
+            return (self.temperature_archived * (our_moles + moved_moles) - sharer.temperature_archived * (their_moles - moved_moles))
+                * num::R_IDEAL_GAS_EQUATION / self.volume as Real;
+        }
+        0.0
+    }
+
+    /// Conducts heat between this mixture and `sharer`, `temperature_share()` in tgstation. The
+    /// heat moved is `conduction_coefficient` times the archived temperature difference, scaled by
+    /// both (archived) heat capacities. Returns the sharer's new temperature.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// let mut hot = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 1000.0, 2500).unwrap();
+    /// let mut cold = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 100.0, 2500).unwrap();
+    /// hot.archive();
+    /// cold.archive();
+    ///
+    /// hot.temperature_share(&mut cold, 0.4);
+    /// assert_eq!(hot.temperature, 820.0);
+    /// assert_eq!(cold.temperature, 280.0);
+    /// ```
+    pub fn temperature_share(&mut self, sharer: &mut GasMixture<'a>, conduction_coefficient: Real) -> Real {
+        let sharer_heat_capacity = sharer.heat_capacity_archived();
+        if let Some(temperature) = self.conduct(sharer.temperature_archived, sharer_heat_capacity, conduction_coefficient) {
+            sharer.temperature = temperature;
+        }
+        sharer.temperature
+    }
+
+    /// `temperature_share()` with something solid, like a wall, at `solid_temperature` with
+    /// `solid_heat_capacity` J/K. Returns the solid's new temperature.
+    pub fn temperature_share_solid(&mut self, solid_temperature: Real, solid_heat_capacity: Real, conduction_coefficient: Real) -> Real {
+        self.conduct(solid_temperature, solid_heat_capacity, conduction_coefficient)
+            .unwrap_or(solid_temperature)
+    }
+
+    /// Moves heat from this mixture to a sharer at `sharer_temperature`, returning the sharer's new
+    /// temperature, or `None` if the two are too close in temperature or one can't hold any heat.
+    fn conduct(&mut self, sharer_temperature: Real, sharer_heat_capacity: Real, conduction_coefficient: Real) -> Option<Real> {
+        let temperature_delta = self.temperature_archived - sharer_temperature;
+        if temperature_delta.abs() <= num::MINIMUM_TEMP_DELTA_TO_CONSIDER {
+            return None;
+        }
+        let self_heat_capacity = self.heat_capacity_archived();
+        if sharer_heat_capacity <= num::MINIMUM_HEAT_CAPACITY || self_heat_capacity <= num::MINIMUM_HEAT_CAPACITY {
+            return None;
+        }
+        let heat = conduction_coefficient * temperature_delta
+            * (self_heat_capacity * sharer_heat_capacity / (self_heat_capacity + sharer_heat_capacity));
+        self.temperature = (self.temperature - heat / self_heat_capacity).max(num::TCMB);
+        Some((sharer_temperature + heat / sharer_heat_capacity).max(num::TCMB))
+    }
+
+    /// Checks whether `sample` is different enough from this mixture for air to bother moving
+    /// between them, `compare()` in tgstation. Returns the first gas (in registry order) with a
+    /// big enough difference, then checks temperature, and returns `None` if they're close enough.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::{GasMixture, MixtureDifference};
+    /// use libatmos::constants::gases;
+    /// let air = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 2500).unwrap();
+    /// let mut other = air.clone();
+    /// assert_eq!(air.compare(&other), None);
+    ///
+    /// other.temperature = 500.0;
+    /// assert_eq!(air.compare(&other), Some(MixtureDifference::Temperature));
+    /// assert_eq!(air.compare(&GasMixture::from_empty(293.15, 2500)), Some(MixtureDifference::Gas(&gases::O2)));
+    /// ```
+    pub fn compare(&self, sample: &GasMixture<'a>) -> Option<MixtureDifference<'a>> {
+        let all_gases = self.gases.keys().chain(sample.gases.keys()).copied().collect::<BTreeSet<_>>();
+        for gas in all_gases {
+            let moles = self.get_moles(gas).unwrap_or(0.0);
+            let delta = (moles - sample.get_moles(gas).unwrap_or(0.0)).abs();
+            if delta > num::MINIMUM_MOLES_DELTA_TO_MOVE && delta > moles * num::MINIMUM_AIR_RATIO_TO_MOVE {
+                return Some(MixtureDifference::Gas(gas));
+            }
+        }
+        // don't bother with temperature if there's hardly any air
+        if self.total_moles() > num::MINIMUM_MOLES_DELTA_TO_MOVE
+            && (self.temperature - sample.temperature).abs() > num::MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND {
+            return Some(MixtureDifference::Temperature);
+        }
+        None
+    }
+
+    /// Runs every gas reaction that can happen in the mixture once, highest priority first.
+    /// See `constants::reactions` for the list of reactions.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::constants::gases;
+    /// // plasma and oxygen at 500 K, which is well past the point it catches fire
+    /// let mut mix = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![10.0, 40.0], 500.0, 70).unwrap();
+    /// let results = mix.react();
+    /// assert!(results.has_fired("plasmafire"));
+    /// assert!(results.fire > 0.0);
+    /// assert!(mix.temperature > 500.0);
+    /// assert!(mix.get_moles(&gases::CO2).unwrap() > 0.0);
+    /// ```
+    pub fn react(&mut self) -> ReactionResults {
+        let results = reactions::react(self);
+        self.last_fire = results.fire;
+        results
+    }
+
+    /// How much burned the last time `react()` ran on the mixture, `reaction_results["fire"]`.
+    /// Hotspots that cover the whole turf grow with this.
+    pub fn last_fire(&self) -> Real {
+        self.last_fire
     }
+}
 
-    // TODO: Implement compare()?
-    // TODO: Implement react()
+impl<'a> PartialEq for GasMixture<'a> {
+    /// Mixtures are equal if they hold the same gases at the same temperature and volume.
+    /// What they were last archived as (or what last reacted in them) doesn't count.
+    fn eq(&self, other: &GasMixture<'a>) -> bool {
+        self.gases == other.gases && self.temperature == other.temperature && self.volume == other.volume
+    }
 }
 
 impl<'a> fmt::Display for GasMixture<'a> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut gas_string = String::new();
         for (gastype, moles) in self.gases.iter() {
             gas_string.push_str(format!("{}:{},", gastype.id, moles).as_str());
@@ -327,3 +1147,37 @@ impl<'a> fmt::Display for GasMixture<'a> {
         write!(f, "t:{},v:{},g:{}", self.temperature, self.volume, gas_string)
     }
 }
+
+/// Parses the `Display` format back into a mixture, i.e. `t:293.15,v:70,g:o2:21.8,n2:82.1,`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::constants::gases;
+/// let mix: GasMixture = "t:293.15,v:70,g:o2:21.8,n2:82.1,".parse().unwrap();
+/// assert_eq!(mix.get_moles(&gases::N2).unwrap(), 82.1);
+/// assert_eq!(mix.to_string().parse::<GasMixture>().unwrap(), mix);
+/// ```
+impl<'a> FromStr for GasMixture<'a> {
+    type Err = AtmosError;
+
+    fn from_str(input: &str) -> Result<GasMixture<'a>, AtmosError> {
+        let parse_error = |reason: &str| AtmosError::Parse { input: input.to_string(), reason: reason.to_string() };
+
+        let rest = input.trim()
+                        .strip_prefix("t:")
+                        .ok_or_else(|| parse_error("expected it to start with 't:'"))?;
+        let (temperature, rest) = rest.split_once(",v:").ok_or_else(|| parse_error("missing ',v:'"))?;
+        let (volume, gas_list) = rest.split_once(",g:").ok_or_else(|| parse_error("missing ',g:'"))?;
+        let temperature = temperature.parse::<Real>().map_err(|_| parse_error("temperature isn't a number"))?;
+        let volume = volume.parse::<usize>().map_err(|_| parse_error("volume isn't a whole number"))?;
+
+        let mut gas_types = Vec::new();
+        let mut moles = Vec::new();
+        for entry in gas_list.split(',').filter(|entry| !entry.is_empty()) {
+            let (id, amount) = entry.split_once(':').ok_or_else(|| parse_error("gases have to look like 'id:moles'"))?;
+            gas_types.push(gases::from_id(id)?);
+            moles.push(amount.parse::<Real>().map_err(|_| parse_error("mole count isn't a number"))?);
+        }
+        GasMixture::from_vecs(gas_types, moles, temperature, volume)
+    }
+}
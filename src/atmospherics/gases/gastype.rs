@@ -1,22 +1,66 @@
-use std::fmt;
-
-/// Struct used to identify and organize different gases by name.
-#[derive(Debug, Eq, PartialEq, Hash)]
-pub struct GasType {
-    /// Short identifier
-    pub id: &'static str,
-    /// More formal, user-friendly name
-    pub name: &'static str,
-    /// Amount of energy required to heat a gas up by 1 deg. celsius.
-    /// Higher => harder to cool/heat.
-    /// Lower => easier to cool/heat
-    pub specific_heat: usize,
-    /// Multiplier for how much a gas accelerates a fusion reaction
-    pub fusion_power: isize
-}
-
-impl fmt::Display for GasType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} ({})", self.name, self.id) // i.e. "o2 (Oxygen)"
-    }
+use crate::byond::Real;
+use crate::constants::gases;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Struct used to identify and organize different gases by name.
+/// Two gases are the same gas if they have the same `id`. Gases are ordered by their
+/// position in `constants::gases::ALL`, with any gases not in there coming last, by `id`.
+#[derive(Debug)]
+pub struct GasType {
+    /// Short identifier
+    pub id: &'static str,
+    /// More formal, user-friendly name
+    pub name: &'static str,
+    /// Amount of energy required to heat a gas up by 1 deg. celsius.
+    /// Higher => harder to cool/heat.
+    /// Lower => easier to cool/heat
+    pub specific_heat: usize,
+    /// Multiplier for how much a gas accelerates a fusion reaction
+    pub fusion_power: isize,
+    /// Molar mass in g/mol. SS13 doesn't have these, so gases that don't exist
+    /// in real life get made-up (but plausible) values.
+    pub molar_mass: Real
+}
+
+impl PartialEq for GasType {
+    fn eq(&self, other: &GasType) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for GasType {}
+
+impl Hash for GasType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl GasType {
+    /// Returns the position of this gas in `constants::gases::ALL`, or `None` if it isn't in there.
+    pub fn registry_index(&self) -> Option<usize> {
+        gases::ALL.iter().position(|gas| gas.id == self.id)
+    }
+}
+
+impl Ord for GasType {
+    fn cmp(&self, other: &GasType) -> Ordering {
+        let index = |gas: &GasType| gas.registry_index().unwrap_or(gases::ALL.len());
+        index(self).cmp(&index(other))
+                   .then_with(|| self.id.cmp(other.id))
+    }
+}
+
+impl PartialOrd for GasType {
+    fn partial_cmp(&self, other: &GasType) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for GasType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id) // i.e. "o2 (Oxygen)"
+    }
 }
\ No newline at end of file
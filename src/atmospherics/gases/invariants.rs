@@ -0,0 +1,113 @@
+use crate::atmospherics::gases::GasMixture;
+use crate::byond::Real;
+use crate::errors::AtmosError;
+
+// Checks for things that should always hold for gas mixtures: no negative or NaN moles or
+// temperatures, and moles + thermal energy going in equal moles + thermal energy coming out.
+// With the `invariants` cargo feature, GasMixture runs these after every operation that moves
+// gas around and panics on the first violation. They're public so callers (and reaction code)
+// can run them by hand too.
+
+/// Relative difference allowed between the before and after totals of a conservation check.
+/// Covers floating point error and `QUANTIZE` rounding, not actual bugs.
+pub const RELATIVE_TOLERANCE: Real = 0.000_01;
+
+/// Absolute difference always allowed, for totals close to 0.
+pub const ABSOLUTE_TOLERANCE: Real = 0.000_01;
+
+fn violation(operation: &str, details: String) -> AtmosError {
+    AtmosError::InvariantViolated { operation: operation.to_string(), details }
+}
+
+fn close(before: Real, after: Real, slack: Real) -> bool {
+    (before - after).abs() <= ABSOLUTE_TOLERANCE + RELATIVE_TOLERANCE * before.abs().max(after.abs()) + slack
+}
+
+fn describe(mixtures: &[&GasMixture]) -> String {
+    mixtures.iter()
+            .map(|mix| format!("[{}]", mix))
+            .collect::<Vec<_>>()
+            .join(" + ")
+}
+
+/// Checks that `mix` has no negative or NaN moles and a sane temperature.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::{GasMixture, invariants};
+/// let mut mix = GasMixture::from_empty(293.15, 70);
+/// assert!(invariants::check_mixture("example", &mix).is_ok());
+/// mix.temperature = -5.0;
+/// assert!(invariants::check_mixture("example", &mix).is_err());
+/// ```
+pub fn check_mixture(operation: &str, mix: &GasMixture) -> Result<(), AtmosError> {
+    mix.validate()
+       .map_err(|error| violation(operation, format!("{} (mixture: [{}])", error, mix)))
+}
+
+/// Checks that every mixture in `after` is valid and that the mixtures in `before` and `after`
+/// hold the same total moles and thermal energy. `energy_slack` is extra energy (in joules) the
+/// operation is allowed to lose or gain, i.e. when `merge()` skips a tiny temperature change.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::{GasMixture, invariants};
+/// use libatmos::constants::gases;
+/// let before = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 70).unwrap();
+/// let mut after = before.clone();
+/// let removed = after.remove(25.0).unwrap();
+/// assert!(invariants::check_conservation("remove", &[&before], &[&after, &removed], 0.0).is_ok());
+/// assert!(invariants::check_conservation("remove", &[&before], &[&after], 0.0).is_err()); // 25 mol went missing
+/// ```
+pub fn check_conservation(operation: &str, before: &[&GasMixture], after: &[&GasMixture], energy_slack: Real) -> Result<(), AtmosError> {
+    for mix in after {
+        check_mixture(operation, mix)?;
+    }
+
+    let moles_before = before.iter().fold(0.0, |acc, mix| acc + mix.total_moles());
+    let moles_after = after.iter().fold(0.0, |acc, mix| acc + mix.total_moles());
+    if !close(moles_before, moles_after, 0.0) {
+        return Err(violation(operation, format!("moles not conserved: {} mol before, {} mol after ({} -> {})",
+                                                moles_before, moles_after, describe(before), describe(after))));
+    }
+
+    let energy_before = before.iter().fold(0.0, |acc, mix| acc + mix.thermal_energy());
+    let energy_after = after.iter().fold(0.0, |acc, mix| acc + mix.thermal_energy());
+    if !close(energy_before, energy_after, energy_slack) {
+        return Err(violation(operation, format!("thermal energy not conserved: {} J before, {} J after ({} -> {})",
+                                                energy_before, energy_after, describe(before), describe(after))));
+    }
+    Ok(())
+}
+
+/// Checks that a reaction turning `before` into `after` released as much thermal energy as it
+/// says it did (`energy_released`, negative for endothermic reactions), and left a valid mixture.
+/// Moles don't have to be conserved, since reactions turn gases into other gases.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::{GasMixture, invariants};
+/// use libatmos::constants::gases;
+/// let before = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 70).unwrap();
+/// let mut after = before.clone();
+/// after.adjust_thermal_energy(5000.0);
+/// assert!(invariants::check_reaction("fire", &before, &after, 5000.0).is_ok());
+/// assert!(invariants::check_reaction("fire", &before, &after, 0.0).is_err());
+/// ```
+pub fn check_reaction(operation: &str, before: &GasMixture, after: &GasMixture, energy_released: Real) -> Result<(), AtmosError> {
+    check_mixture(operation, after)?;
+
+    let expected = before.thermal_energy() + energy_released;
+    let actual = after.thermal_energy();
+    if !close(expected, actual, 0.0) {
+        return Err(violation(operation, format!("reaction reported releasing {} J, but thermal energy went from {} J to {} J ([{}] -> [{}])",
+                                                energy_released, before.thermal_energy(), actual, before, after)));
+    }
+    Ok(())
+}
+
+/// Panics if `result` is a violation. Used by `GasMixture` when the `invariants` feature is on.
+#[cfg(feature = "invariants")]
+pub(crate) fn enforce(result: Result<(), AtmosError>) {
+    if let Err(error) = result {
+        error!("{}", error);
+        panic!("{}", error);
+    }
+}
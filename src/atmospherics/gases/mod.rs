@@ -1,11 +1,18 @@
 pub use self::{
     gasmixture::GasMixture,
     gastype::GasType,
+    gasmixture::GasComponent,
+    gasmixture::MixtureDifference,
+    breathing::Species,
 };
 
 /// gas type struct
 pub mod gastype;
 /// gas mixture struct and functions
 pub mod gasmixture;
+/// species breathing requirements
+pub mod breathing;
+/// conservation and sanity checks for gas mixtures
+pub mod invariants;
 /// reaction functions and react loop code
 pub mod reactions;
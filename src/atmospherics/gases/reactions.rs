@@ -0,0 +1,459 @@
+use crate::atmospherics::gases::{GasMixture, GasType};
+#[cfg(feature = "invariants")]
+use crate::atmospherics::gases::invariants;
+use crate::byond::{self, Real};
+use crate::constants::*;
+use std::fmt;
+
+// Gas reactions, ported from tgstation's code/modules/atmospherics/gasmixtures/reactions.dm.
+// Every reaction is a plain function that mutates the mixture and says whether it did anything,
+// and `constants::reactions::ALL` lists them all in the order react() tries them. Anything that
+// needs a turf (water vapor freezing floors, radiation pulses, hotspots) is left to the caller,
+// which can look at `ReactionResults::fire` to decide whether the air is burning.
+
+/// What a single reaction did, tgstation's `NO_REACTION` / `REACTING` / `STOP_REACTIONS`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ReactionFlag {
+    /// Requirements were met, but nothing changed.
+    NoReaction,
+    /// The reaction happened.
+    Reacting,
+    /// The reaction happened, and no other reactions should run this time.
+    StopReactions
+}
+
+/// A minimum requirement a mixture has to meet before a reaction is tried.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Requirement {
+    /// Temperature at least this many kelvin, `"TEMP"` in tgstation.
+    Temperature(Real),
+    /// Thermal energy at least this many joules, `"ENER"` in tgstation.
+    ThermalEnergy(Real),
+    /// At least this many mols of a gas.
+    Moles(&'static GasType, Real)
+}
+
+/// A gas reaction. The actual ones live in `constants::reactions`.
+pub struct GasReaction {
+    /// Unique id, the same as tgstation's, i.e. `"plasmafire"`.
+    pub id: &'static str,
+    /// Human readable name.
+    pub name: &'static str,
+    /// Higher priority reactions are tried first. Noblium suppression is infinite.
+    pub priority: Real,
+    /// Everything that has to hold for the reaction to be tried at all.
+    pub min_requirements: &'static [Requirement],
+    /// The reaction itself.
+    pub react: for<'a> fn(&mut GasMixture<'a>, &mut ReactionResults) -> ReactionFlag
+}
+
+impl GasReaction {
+    /// Returns true if `air` meets all of the reaction's minimum requirements. Like tgstation,
+    /// temperature and energy are checked against the values from before any reaction ran this
+    /// `react()`, which get passed in as `temperature` and `thermal_energy`.
+    pub fn requirements_met(&self, air: &GasMixture, temperature: Real, thermal_energy: Real) -> bool {
+        self.min_requirements.iter().all(|requirement| match *requirement {
+            Requirement::Temperature(min) => temperature >= min,
+            Requirement::ThermalEnergy(min) => thermal_energy >= min,
+            Requirement::Moles(gas, min) => air.get_moles(gas).is_some_and(|moles| moles >= min)
+        })
+    }
+}
+
+impl PartialEq for GasReaction {
+    fn eq(&self, other: &GasReaction) -> bool {
+        self.id == other.id
+    }
+}
+
+impl fmt::Debug for GasReaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GasReaction")
+         .field("id", &self.id)
+         .field("priority", &self.priority)
+         .field("min_requirements", &self.min_requirements)
+         .finish()
+    }
+}
+
+impl fmt::Display for GasReaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Everything one call to `react()` did, tgstation's `reaction_results` list plus a bit extra.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ReactionResults {
+    /// Ids of the reactions that happened, in the order they happened.
+    pub fired: Vec<&'static str>,
+    /// How much stuff burned, `reaction_results["fire"]`. Hotspots grow with this.
+    pub fire: Real,
+    /// Net thermal energy released in joules, negative if the reactions were endothermic overall.
+    pub energy_released: Real,
+    /// Research points the reactions would've given the station's techweb.
+    pub research: Real
+}
+
+impl ReactionResults {
+    /// Returns true if any reaction happened.
+    pub fn reacted(&self) -> bool {
+        !self.fired.is_empty()
+    }
+
+    /// Returns true if the reaction with id `id` happened.
+    pub fn has_fired(&self, id: &str) -> bool {
+        self.fired.contains(&id)
+    }
+}
+
+/// Runs every reaction in `constants::reactions::ALL` on `air`, in priority order.
+/// This is what `GasMixture::react()` calls.
+pub fn react(air: &mut GasMixture) -> ReactionResults {
+    let mut results = ReactionResults::default();
+    if air.is_empty() {
+        return results;
+    }
+    let temperature = air.temperature;
+    let thermal_energy = air.thermal_energy();
+
+    for reaction in crate::constants::reactions::ALL.iter() {
+        if !reaction.requirements_met(air, temperature, thermal_energy) {
+            continue;
+        }
+        #[cfg(feature = "invariants")]
+        let before = (air.clone(), results.energy_released);
+
+        let flag = (reaction.react)(air, &mut results);
+
+        #[cfg(feature = "invariants")]
+        invariants::enforce(invariants::check_reaction(reaction.id, &before.0, air, results.energy_released - before.1));
+
+        match flag {
+            ReactionFlag::NoReaction => {},
+            ReactionFlag::Reacting => results.fired.push(reaction.id),
+            ReactionFlag::StopReactions => {
+                results.fired.push(reaction.id);
+                break;
+            }
+        }
+    }
+
+    if results.reacted() {
+        air.garbage_collect();
+        if air.temperature < num::TCMB { // just for safety
+            air.temperature = num::TCMB;
+        }
+    }
+    results
+}
+
+fn moles(air: &GasMixture, gas: &'static GasType) -> Real {
+    air.get_moles(gas).unwrap_or(0.0)
+}
+
+fn set_moles(air: &mut GasMixture, gas: &'static GasType, amount: Real) {
+    air.assert_gas(gas);
+    // some of tgstation's reactions can take a hair more of a gas than there is, BYOND just
+    // lets it go negative but we don't
+    air.change_moles(gas, amount.max(0.0)).unwrap();
+}
+
+/// Sets the temperature after a reaction changed the gases and released `energy` joules
+/// (negative for endothermic reactions), never going below `TCMB`. Returns the energy actually
+/// released, which is a bit different if the temperature got clamped.
+fn release_energy(air: &mut GasMixture, old_thermal_energy: Real, energy: Real) -> Real {
+    let new_heat_capacity = air.heat_capacity();
+    if new_heat_capacity > num::MINIMUM_HEAT_CAPACITY {
+        air.temperature = ((old_thermal_energy + energy) / new_heat_capacity).max(num::TCMB);
+    }
+    air.thermal_energy() - old_thermal_energy
+}
+
+/// BYOND `MODULUS()`, which floors instead of truncating like `%`.
+fn modulus(value: Real, modulo: Real) -> Real {
+    value - modulo * (value / modulo).floor()
+}
+
+/// Hyper-noblium stops every other reaction.
+pub fn noblium_suppression(_air: &mut GasMixture, _results: &mut ReactionResults) -> ReactionFlag {
+    ReactionFlag::StopReactions
+}
+
+/// Tritium + oxygen burning into water vapor.
+pub fn tritium_fire(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let oxygen = moles(air, &gases::O2);
+    let tritium = moles(air, &gases::TRIT);
+    results.fire = 0.0;
+
+    let burned_fuel;
+    if oxygen < tritium || (num::MINIMUM_TRIT_OXYBURN_ENERGY as Real) > old_thermal_energy {
+        burned_fuel = oxygen / num::TRITIUM_BURN_OXY_FACTOR as Real;
+        set_moles(air, &gases::TRIT, tritium - burned_fuel);
+    } else {
+        burned_fuel = tritium * num::TRITIUM_BURN_TRIT_FACTOR as Real;
+        let tritium = tritium - tritium / num::TRITIUM_BURN_TRIT_FACTOR as Real;
+        set_moles(air, &gases::TRIT, tritium);
+        // yes, tgstation takes away the tritium that's *left* here, not what burned
+        set_moles(air, &gases::O2, oxygen - tritium);
+    }
+
+    if burned_fuel > 0.0 {
+        let water = moles(air, &gases::H2O);
+        set_moles(air, &gases::H2O, water + burned_fuel / num::TRITIUM_BURN_OXY_FACTOR as Real);
+        results.fire += burned_fuel;
+        results.energy_released += release_energy(air, old_thermal_energy, num::FIRE_HYDROGEN_ENERGY_RELEASED as Real * burned_fuel);
+    }
+
+    if results.fire > 0.0 { ReactionFlag::Reacting } else { ReactionFlag::NoReaction }
+}
+
+/// Plasma + oxygen burning into carbon dioxide, or tritium if there's loads more oxygen than plasma.
+pub fn plasma_fire(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let temperature = air.temperature;
+    // this resets whatever tritium fire did, same as tgstation
+    results.fire = 0.0;
+
+    let temperature_scale = if temperature > num::PLASMA_UPPER_TEMPERATURE {
+        1.0
+    } else {
+        (temperature - num::PLASMA_MINIMUM_BURN_TEMPERATURE) / (num::PLASMA_UPPER_TEMPERATURE - num::PLASMA_MINIMUM_BURN_TEMPERATURE)
+    };
+
+    let mut energy_released = 0.0;
+    if temperature_scale > 0.0 {
+        let plasma = moles(air, &gases::PLASMA);
+        let oxygen = moles(air, &gases::O2);
+        let fullburn = num::PLASMA_OXYGEN_FULLBURN as Real;
+        let oxygen_burn_rate = num::OXYGEN_BURN_RATE_BASE - temperature_scale;
+        let super_saturation = oxygen / plasma > num::SUPER_SATURATION_THRESHOLD as Real;
+
+        let mut plasma_burn_rate = if oxygen > plasma * fullburn {
+            (plasma * temperature_scale) / num::PLASMA_BURN_RATE_DELTA as Real
+        } else {
+            (temperature_scale * (oxygen / fullburn)) / num::PLASMA_BURN_RATE_DELTA as Real
+        };
+
+        if plasma_burn_rate > num::MINIMUM_HEAT_CAPACITY {
+            plasma_burn_rate = plasma_burn_rate.min(plasma).min(oxygen / oxygen_burn_rate); // conserve matter
+            set_moles(air, &gases::PLASMA, byond::quantize(plasma - plasma_burn_rate));
+            set_moles(air, &gases::O2, byond::quantize(oxygen - plasma_burn_rate * oxygen_burn_rate));
+            let product = if super_saturation { &gases::TRIT } else { &gases::CO2 };
+            let produced = moles(air, product) + plasma_burn_rate;
+            set_moles(air, product, produced);
+
+            energy_released += num::FIRE_PLASMA_ENERGY_RELEASED as Real * plasma_burn_rate;
+            results.fire += plasma_burn_rate * (1.0 + oxygen_burn_rate);
+        }
+    }
+
+    if energy_released > 0.0 {
+        results.energy_released += release_energy(air, old_thermal_energy, energy_released);
+    }
+    if results.fire > 0.0 { ReactionFlag::Reacting } else { ReactionFlag::NoReaction }
+}
+
+/// Plasma and carbon dioxide fusing with a bit of tritium as a catalyst. Chaotic on purpose.
+pub fn fusion(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_heat_capacity = air.heat_capacity();
+    let old_thermal_energy = air.thermal_energy();
+    let initial_plasma = moles(air, &gases::PLASMA);
+    let initial_carbon = moles(air, &gases::CO2);
+    let volume = air.volume as Real;
+    let threshold = num::FUSION_MOLE_THRESHOLD as Real;
+    let breakeven = num::TOROID_VOLUME_BREAKEVEN as Real;
+
+    let scale_factor = volume / std::f64::consts::PI as Real;
+    let toroidal_size = 2.0 * std::f64::consts::PI as Real + ((volume - breakeven) / breakeven).atan();
+    let gas_power = air.iter().fold(0.0, |acc, (gas, amount)| acc + gas.fusion_power as Real * amount);
+    let instability = modulus((gas_power * num::INSTABILITY_GAS_POWER_FACTOR).powi(2), toroidal_size);
+
+    let mut plasma = (initial_plasma - threshold) / scale_factor;
+    let mut carbon = (initial_carbon - threshold) / scale_factor;
+    plasma = modulus(plasma - instability * carbon.sin(), toroidal_size); // modulus, not a minus
+    carbon = modulus(carbon - plasma, toroidal_size);
+
+    set_moles(air, &gases::PLASMA, plasma * scale_factor + threshold);
+    set_moles(air, &gases::CO2, carbon * scale_factor + threshold);
+    let delta_plasma = initial_plasma - moles(air, &gases::PLASMA);
+
+    let mut reaction_energy = delta_plasma * num::PLASMA_BINDING_ENERGY as Real;
+    if instability < num::FUSION_INSTABILITY_ENDOTHERMALITY as Real {
+        reaction_energy = reaction_energy.max(0.0); // stable reactions don't end up endothermic
+    } else if reaction_energy < 0.0 {
+        reaction_energy *= (instability - num::FUSION_INSTABILITY_ENDOTHERMALITY as Real).sqrt();
+    }
+
+    if air.thermal_energy() + reaction_energy < 0.0 { // no using energy that doesn't exist
+        set_moles(air, &gases::PLASMA, initial_plasma);
+        set_moles(air, &gases::CO2, initial_carbon);
+        return ReactionFlag::NoReaction;
+    }
+
+    let tritium_used = num::FUSION_TRITIUM_MOLES_USED as Real;
+    let tritium = moles(air, &gases::TRIT);
+    set_moles(air, &gases::TRIT, tritium - tritium_used);
+    let waste = tritium_used * reaction_energy.abs() * num::FUSION_TRIT_CONVERSION_COEFFICIENT;
+    let (first, second) = if reaction_energy > 0.0 { (&gases::O2, &gases::N2O) } else { (&gases::BZ, &gases::NO2) };
+    let first_moles = moles(air, first);
+    set_moles(air, first, first_moles + waste);
+    let second_moles = moles(air, second);
+    set_moles(air, second, second_moles + waste);
+
+    if reaction_energy != 0.0 {
+        let new_heat_capacity = air.heat_capacity();
+        if new_heat_capacity > num::MINIMUM_HEAT_CAPACITY {
+            air.temperature = ((air.temperature * old_heat_capacity + reaction_energy) / new_heat_capacity).max(num::TCMB);
+        }
+    }
+    results.energy_released += air.thermal_energy() - old_thermal_energy;
+    ReactionFlag::Reacting
+}
+
+/// Nitrous oxide breaking down into nitrogen and oxygen when it gets hot.
+pub fn nitrous_decomposition(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let temperature = air.temperature;
+    let nitrous = moles(air, &gases::N2O);
+
+    let burned_fuel = (0.000_02 * (temperature - 0.000_01 * temperature.powi(2))).max(0.0) * nitrous;
+    if burned_fuel <= 0.0 {
+        return ReactionFlag::NoReaction;
+    }
+    set_moles(air, &gases::N2O, nitrous - burned_fuel);
+    let oxygen = moles(air, &gases::O2);
+    set_moles(air, &gases::O2, oxygen + burned_fuel / 2.0);
+    let nitrogen = moles(air, &gases::N2);
+    set_moles(air, &gases::N2, nitrogen + burned_fuel);
+
+    results.energy_released += release_energy(air, old_thermal_energy, num::N2O_DECOMPOSITION_ENERGY_RELEASED as Real * burned_fuel);
+    ReactionFlag::Reacting
+}
+
+/// Oxygen + nitrogen into nitryl, using up a lot of heat.
+pub fn nitryl_formation(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let oxygen = moles(air, &gases::O2);
+    let nitrogen = moles(air, &gases::N2);
+
+    let heat_efficiency = (air.temperature / (num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST * 100.0)).min(oxygen).min(nitrogen);
+    let energy_used = heat_efficiency * num::NITRYL_FORMATION_ENERGY as Real;
+    if oxygen - heat_efficiency < 0.0 || nitrogen - heat_efficiency < 0.0 { // shouldn't produce gas from nothing
+        return ReactionFlag::NoReaction;
+    }
+    set_moles(air, &gases::O2, oxygen - heat_efficiency);
+    set_moles(air, &gases::N2, nitrogen - heat_efficiency);
+    let nitryl = moles(air, &gases::NO2);
+    set_moles(air, &gases::NO2, nitryl + heat_efficiency * 2.0);
+
+    if energy_used > 0.0 {
+        results.energy_released += release_energy(air, old_thermal_energy, -energy_used);
+    }
+    ReactionFlag::Reacting
+}
+
+/// Nitrous oxide + plasma into BZ. Works better at low pressures.
+pub fn bz_formation(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let pressure = air.return_pressure();
+    let nitrous = moles(air, &gases::N2O);
+    let plasma = moles(air, &gases::PLASMA);
+
+    let reaction_efficiency = (1.0 / ((pressure / (0.1 * num::ONE_ATMOSPHERE)) * (plasma / nitrous).max(1.0)))
+        .min(nitrous)
+        .min(plasma / 2.0);
+    let energy_released = 2.0 * reaction_efficiency * num::FIRE_CARBON_ENERGY_RELEASED as Real;
+    if nitrous - reaction_efficiency < 0.0 || plasma - 2.0 * reaction_efficiency < 0.0 || energy_released <= 0.0 {
+        return ReactionFlag::NoReaction;
+    }
+
+    let mut bz = moles(air, &gases::BZ) + reaction_efficiency;
+    if reaction_efficiency == nitrous {
+        // all the nitrous got used, so some of the BZ breaks back down into oxygen
+        bz -= pressure.min(1.0);
+        let oxygen = moles(air, &gases::O2);
+        set_moles(air, &gases::O2, oxygen + pressure.min(1.0));
+    }
+    set_moles(air, &gases::BZ, bz);
+    set_moles(air, &gases::N2O, nitrous - reaction_efficiency);
+    set_moles(air, &gases::PLASMA, plasma - 2.0 * reaction_efficiency);
+    // tgstation's min() here is missing a parenthesis, so BZ_RESEARCH_MAX_AMOUNT never actually
+    // applies in game. Uncapped, same as the points you'd really get.
+    results.research += reaction_efficiency.powi(2) * num::BZ_RESEARCH_SCALE as Real;
+
+    results.energy_released += release_energy(air, old_thermal_energy, energy_released);
+    ReactionFlag::Reacting
+}
+
+/// Tritium, plasma, BZ and nitryl into stimulum.
+pub fn stimulum_formation(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let tritium = moles(air, &gases::TRIT);
+    let plasma = moles(air, &gases::PLASMA);
+    let nitryl = moles(air, &gases::NO2);
+
+    let heat_scale = (air.temperature / num::STIMULUM_HEAT_SCALE as Real).min(tritium).min(plasma).min(nitryl);
+    let stim_energy_change = heat_scale
+        + num::STIMULUM_FIRST_RISE * heat_scale.powi(2)
+        - num::STIMULUM_FIRST_DROP * heat_scale.powi(3)
+        + num::STIMULUM_SECOND_RISE * heat_scale.powi(4)
+        - num::STIMULUM_ABSOLUTE_DROP * heat_scale.powi(5);
+
+    if tritium - heat_scale < 0.0 || plasma - heat_scale < 0.0 || nitryl - heat_scale < 0.0 {
+        return ReactionFlag::NoReaction;
+    }
+    let stimulum = moles(air, &gases::STIM);
+    set_moles(air, &gases::STIM, stimulum + heat_scale / 10.0);
+    set_moles(air, &gases::TRIT, tritium - heat_scale);
+    set_moles(air, &gases::PLASMA, plasma - heat_scale);
+    set_moles(air, &gases::NO2, nitryl - heat_scale);
+    results.research += num::STIMULUM_RESEARCH_AMOUNT as Real * stim_energy_change.max(0.0);
+
+    if stim_energy_change != 0.0 {
+        results.energy_released += release_energy(air, old_thermal_energy, stim_energy_change);
+    }
+    ReactionFlag::Reacting
+}
+
+/// Nitrogen + tritium condensing into hyper-noblium. Takes a ridiculous amount of energy, less with BZ around.
+pub fn noblium_formation(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    let nitrogen = moles(air, &gases::N2);
+    let tritium = moles(air, &gases::TRIT);
+
+    let nob_formed = ((nitrogen + tritium) / 100.0).min(tritium / 10.0).min(nitrogen / 20.0);
+    let energy_taken = nob_formed * (num::NOBLIUM_FORMATION_ENERGY as Real / moles(air, &gases::BZ).max(1.0));
+    if tritium - 10.0 * nob_formed < 0.0 || nitrogen - 20.0 * nob_formed < 0.0 || nob_formed <= 0.0 {
+        return ReactionFlag::NoReaction;
+    }
+    set_moles(air, &gases::TRIT, tritium - 10.0 * nob_formed);
+    set_moles(air, &gases::N2, nitrogen - 20.0 * nob_formed);
+    let noblium = moles(air, &gases::HYPERNOB);
+    set_moles(air, &gases::HYPERNOB, noblium + nob_formed);
+    results.research += nob_formed * num::NOBLIUM_RESEARCH_AMOUNT as Real;
+
+    results.energy_released += release_energy(air, old_thermal_energy, -energy_taken);
+    ReactionFlag::Reacting
+}
+
+/// Dry heat turning miasma back into oxygen. Doesn't work in damp air.
+pub fn miasma_sterilization(air: &mut GasMixture, results: &mut ReactionResults) -> ReactionFlag {
+    let old_thermal_energy = air.thermal_energy();
+    if moles(air, &gases::H2O) / air.total_moles() > 0.1 {
+        return ReactionFlag::NoReaction;
+    }
+
+    let miasma = moles(air, &gases::MIASMA);
+    let cleaned_air = miasma.min(20.0 + (air.temperature - num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST - 70.0) / 20.0);
+    set_moles(air, &gases::MIASMA, miasma - cleaned_air);
+    let oxygen = moles(air, &gases::O2);
+    set_moles(air, &gases::O2, oxygen + cleaned_air);
+    // burning a bit of organic matter, so a tiny bit of extra heat
+    air.temperature += cleaned_air * 0.002;
+    results.research += cleaned_air * num::MIASMA_RESEARCH_AMOUNT as Real;
+
+    results.energy_released += air.thermal_energy() - old_thermal_energy;
+    ReactionFlag::Reacting
+}
@@ -0,0 +1,172 @@
+use crate::atmospherics::gases::{GasMixture, GasType};
+use crate::byond::{self, Real};
+use crate::constants::*;
+use std::fmt;
+
+// The handheld gas analyzer, /obj/item/analyzer. Numbers get rounded to 0.01 and printed the
+// way BYOND prints them, so "101.33" and "79", never "79.00". BYOND switches to exponents for
+// really big numbers, which isn't done here.
+
+/// One gas line of an analyzer readout.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnalyzerLine<'a> {
+    /// The gas.
+    pub gas: &'a GasType,
+    /// What the analyzer calls it. Same as the gas' name, except carbon dioxide is "CO2".
+    pub label: &'a str,
+    /// Mols of the gas.
+    pub moles: Real,
+    /// Percentage of the mixture, 0 to 100.
+    pub percentage: Real,
+    /// Whether the analyzer shows this line in red.
+    pub dangerous: bool
+}
+
+/// What a gas analyzer shows for a `GasMixture`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::atmospherics::machinery::analyzer::AnalyzerReport;
+/// use libatmos::constants::gases;
+/// let air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![21.0, 79.0], 293.15, 2500).unwrap();
+/// let report = AnalyzerReport::new(&air);
+/// assert!(!report.pressure_alert); // 97.44 kPa is close enough to an atmosphere
+/// assert_eq!(report.to_string().lines().last().unwrap(), "Temperature: 20 °C (293.15 K)");
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnalyzerReport<'a> {
+    /// Pressure in kPa.
+    pub pressure: Real,
+    /// True if the pressure is 10 kPa or more away from one atmosphere.
+    pub pressure_alert: bool,
+    /// Temperature in kelvin.
+    pub temperature: Real,
+    /// Volume in litres.
+    pub volume: usize,
+    /// Total mols.
+    pub total_moles: Real,
+    /// Nitrogen, oxygen, CO2 and plasma (always, in that order), then every other gas in the
+    /// mixture. Empty if the mixture is.
+    pub gases: Vec<AnalyzerLine<'a>>
+}
+
+/// Gases the analyzer always shows, in the order it shows them.
+static HARDCODED: [&GasType; 4] = [&gases::N2, &gases::O2, &gases::CO2, &gases::PLASMA];
+
+impl<'a> AnalyzerReport<'a> {
+    /// Scans `mix` the way the handheld analyzer scans the tile you're standing on. Traces of
+    /// anything other than the four gases that are always shown, less than `MOLES_GAS_VISIBLE`
+    /// mols, get left out.
+    pub fn new(mix: &GasMixture<'a>) -> AnalyzerReport<'a> {
+        AnalyzerReport::with_threshold(mix, num::MOLES_GAS_VISIBLE)
+    }
+
+    /// Same as `new()`, but leaves out every gas (other than the four that are always shown)
+    /// with less than `min_moles` mols instead. 0 shows everything in the mixture.
+    pub fn with_threshold(mix: &GasMixture<'a>, min_moles: Real) -> AnalyzerReport<'a> {
+        let pressure = mix.return_pressure();
+        let total_moles = mix.total_moles();
+        let mut lines = Vec::new();
+
+        if total_moles > 0.0 {
+            for gas in HARDCODED.iter().copied() {
+                let moles = mix.get_moles(gas).unwrap_or(0.0);
+                let fraction = moles / total_moles;
+                // tgstation compares the fractions against 20 and 2 rather than 0.2 and 0.02,
+                // so nitrogen and oxygen never actually show up red. Same here.
+                let dangerous = if gas == &gases::N2 {
+                    (fraction - num::N2STANDARD).abs() >= 20.0
+                } else if gas == &gases::O2 {
+                    (fraction - num::O2STANDARD).abs() >= 2.0
+                } else if gas == &gases::CO2 {
+                    fraction > 0.01
+                } else {
+                    fraction > 0.005
+                };
+                let label = if gas == &gases::CO2 { "CO2" } else { gas.name };
+                lines.push(AnalyzerLine { gas, label, moles, percentage: fraction * 100.0, dangerous });
+            }
+            for (gas, moles) in mix.iter() {
+                if HARDCODED.contains(&gas) || moles <= 0.0 || moles < min_moles {
+                    continue;
+                }
+                // anything else in the air is always worth pointing out
+                lines.push(AnalyzerLine { gas, label: gas.name, moles, percentage: moles / total_moles * 100.0, dangerous: true });
+            }
+        }
+
+        AnalyzerReport {
+            pressure,
+            pressure_alert: (pressure - num::ONE_ATMOSPHERE).abs() >= 10.0,
+            temperature: mix.temperature,
+            volume: mix.volume,
+            total_moles,
+            gases: lines
+        }
+    }
+
+    /// Temperature in degrees celsius.
+    pub fn celsius(&self) -> Real {
+        self.temperature - num::T0C
+    }
+
+    /// Returns true if anything in the readout is red.
+    pub fn is_dangerous(&self) -> bool {
+        self.pressure_alert || self.gases.iter().any(|line| line.dangerous)
+    }
+
+    /// Every line of the readout as `(text, dangerous)`, `text` without any markup. There's no
+    /// temperature line for an empty mixture, same as in game.
+    fn lines(&self) -> Vec<(String, bool)> {
+        let mut lines = vec![
+            (format!("Pressure: {} kPa", number(self.pressure)), self.pressure_alert),
+            (format!("Volume: {} L", self.volume), false),
+            (format!("Moles: {} mol", number(self.total_moles)), false)
+        ];
+        for line in &self.gases {
+            lines.push((format!("{}: {} % ({} mol)", line.label, number(line.percentage), number(line.moles)), line.dangerous));
+        }
+        if self.total_moles > 0.0 {
+            lines.push((format!("Temperature: {} °C ({} K)", number(self.celsius()), number(self.temperature)), false));
+        }
+        lines
+    }
+
+    /// Renders the readout as HTML, with the same `info` / `alert` spans the game uses.
+    /// ## Example
+    /// ```rust
+    /// use libatmos::atmospherics::gases::GasMixture;
+    /// use libatmos::atmospherics::machinery::analyzer::AnalyzerReport;
+    /// use libatmos::constants::gases;
+    /// let air = GasMixture::from_vecs(vec![&gases::N2], vec![10.0], 293.15, 70).unwrap();
+    /// let html = AnalyzerReport::new(&air).to_html();
+    /// assert!(html.contains("<span class='alert'>Pressure: 348.01 kPa</span>"));
+    /// assert!(html.contains("20 &deg;C"));
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<span class='info'><B>Results:</B></span>");
+        for (text, dangerous) in self.lines() {
+            let class = if dangerous { "alert" } else { "info" };
+            html.push_str(&format!("<br><span class='{}'>{}</span>", class, text.replace('°', "&deg;")));
+        }
+        html
+    }
+}
+
+impl<'a> fmt::Display for AnalyzerReport<'a> {
+    /// Renders the readout as plain text, one line each, with `(!)` in front of the red ones.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Results:")?;
+        for (text, dangerous) in self.lines() {
+            write!(f, "\n{}{}", if dangerous { "(!) " } else { "" }, text)?;
+        }
+        Ok(())
+    }
+}
+
+/// `round(value, 0.01)`, printed without trailing zeros.
+fn number(value: Real) -> String {
+    let text = format!("{:.2}", byond::round(value, 0.01));
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
@@ -1,2 +1,8 @@
 mod airpump;
 mod canister;
+/// Gas tanks and their leak / rupture / fragment logic
+pub mod tank;
+/// Tank transfer valves, and simulating the bombs made out of them
+pub mod ttv;
+/// Handheld gas analyzer readouts
+pub mod analyzer;
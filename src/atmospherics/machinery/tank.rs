@@ -0,0 +1,137 @@
+use crate::atmospherics::explosion::{Explosion, BombCap};
+use crate::atmospherics::gases::GasMixture;
+use crate::atmospherics::gases::reactions::ReactionResults;
+use crate::byond::Real;
+use crate::constants::*;
+
+/// Integrity a fresh tank starts with. Every tick spent over `TANK_LEAK_PRESSURE` or
+/// `TANK_RUPTURE_PRESSURE` knocks one off, and every tick under them adds one back.
+pub const TANK_MAX_INTEGRITY: usize = 3;
+
+/// Seconds between tank ticks, `SSobj`'s wait.
+pub const TANK_TICK_SECONDS: Real = 2.0;
+
+/// What happened to a tank during a `check_status()` tick.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TankStatus<'a> {
+    /// Nothing, apart from maybe losing or regaining some integrity.
+    Stable,
+    /// The tank is leaking; this is the gas that leaked out this tick.
+    Leaking(GasMixture<'a>),
+    /// The tank burst and released all of its gas, which is this.
+    Ruptured(GasMixture<'a>),
+    /// The tank fragmented, with these explosion ranges.
+    Exploded(Explosion)
+}
+
+/// A gas tank, i.e. an oxygen or plasma tank, or one half of a TTV. Holds its own `GasMixture`
+/// and leaks, ruptures or explodes according to tgstation's `/obj/item/tank/proc/check_status()`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::atmospherics::machinery::tank::{Tank, TankStatus};
+/// use libatmos::constants::gases;
+/// // 1000 mol of plasma in a 70 L tank is way over the fragment pressure
+/// let air = GasMixture::from_vecs(vec![&gases::PLASMA], vec![1000.0], 293.15, 70).unwrap();
+/// let mut tank = Tank::new(air);
+/// match tank.check_status() {
+///     TankStatus::Exploded(explosion) => assert!(explosion.theoretical.light_impact > 0.0),
+///     other => panic!("expected an explosion, got {:?}", other)
+/// }
+/// assert!(tank.is_destroyed());
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tank<'a> {
+    /// Gas inside the tank. Its volume is the volume of the tank.
+    pub air_contents: GasMixture<'a>,
+    /// How many more ticks the tank can spend over its leak/rupture pressure before it gives.
+    pub integrity: usize,
+    /// Bomb cap applied to the tank's explosion. The theoretical ranges are always reported too.
+    pub bombcap: BombCap,
+    /// Set once the tank ruptures or explodes. Destroyed tanks don't do anything anymore.
+    destroyed: bool
+}
+
+impl<'a> Tank<'a> {
+    /// Creates a tank holding `air_contents`, at full integrity.
+    pub fn new(air_contents: GasMixture<'a>) -> Tank<'a> {
+        Tank {
+            air_contents,
+            integrity: TANK_MAX_INTEGRITY,
+            bombcap: BombCap::default(),
+            destroyed: false
+        }
+    }
+
+    /// Creates an empty tank of `volume` litres at `T20C`. Standard tanks are 70 L.
+    pub fn from_empty(volume: usize) -> Tank<'a> {
+        Tank::new(GasMixture::from_empty(num::T20C, volume))
+    }
+
+    /// Volume of the tank in litres.
+    pub fn volume(&self) -> usize {
+        self.air_contents.volume
+    }
+
+    /// Returns true if the tank has ruptured or exploded.
+    pub fn is_destroyed(&self) -> bool {
+        self.destroyed
+    }
+
+    /// Runs one `SSobj` tick of the tank, i.e. `/obj/item/tank/process()`: the gas reacts,
+    /// then `check_status()` runs. Destroyed tanks don't do anything.
+    pub fn process(&mut self) -> (ReactionResults, TankStatus<'a>) {
+        if self.destroyed {
+            return (ReactionResults::default(), TankStatus::Stable);
+        }
+        let results = self.air_contents.react();
+        (results, self.check_status())
+    }
+
+    /// Runs one tick of tank status checks, i.e. `/obj/item/tank/proc/check_status()`:
+    /// - over `TANK_FRAGMENT_PRESSURE` the gas reacts three more times, then the tank explodes,
+    /// - over `TANK_RUPTURE_PRESSURE` (or over `TANK_MELT_TEMPERATURE`) it loses integrity, then ruptures,
+    /// - over `TANK_LEAK_PRESSURE` it loses integrity, then leaks a quarter of its air every tick,
+    /// - otherwise it slowly regains integrity.
+    ///
+    /// Leaked or released gas is handed back in the returned `TankStatus`, to go wherever the tank is.
+    pub fn check_status(&mut self) -> TankStatus<'a> {
+        if self.destroyed {
+            return TankStatus::Stable;
+        }
+
+        let mut pressure = self.air_contents.return_pressure();
+        let temperature = self.air_contents.temperature;
+
+        if pressure > num::TANK_FRAGMENT_PRESSURE {
+            // give the gas a chance to build up more pressure through reacting
+            for _ in 0..3 {
+                self.air_contents.react();
+            }
+            pressure = self.air_contents.return_pressure();
+            let explosion = Explosion::from_pressure(pressure, &self.bombcap);
+            info!("Tank exploded at {} kPa, ranges {}", pressure, explosion);
+            self.destroyed = true;
+            return TankStatus::Exploded(explosion);
+        }
+
+        if pressure > num::TANK_RUPTURE_PRESSURE || temperature > num::TANK_MELT_TEMPERATURE as Real {
+            if self.integrity == 0 {
+                info!("Tank ruptured at {} kPa, {} K", pressure, temperature);
+                let released = self.air_contents.remove_ratio(1.0).unwrap(); // 1 is never negative
+                self.destroyed = true;
+                return TankStatus::Ruptured(released);
+            }
+            self.integrity -= 1;
+        } else if pressure > num::TANK_LEAK_PRESSURE {
+            if self.integrity == 0 {
+                let leaked = self.air_contents.remove_ratio(0.25).unwrap();
+                return TankStatus::Leaking(leaked);
+            }
+            self.integrity -= 1;
+        } else if self.integrity < TANK_MAX_INTEGRITY {
+            self.integrity += 1;
+        }
+        TankStatus::Stable
+    }
+}
@@ -0,0 +1,164 @@
+use crate::atmospherics::explosion::Explosion;
+use crate::atmospherics::machinery::tank::{Tank, TankStatus, TANK_MAX_INTEGRITY};
+use crate::byond::Real;
+
+/// Ticks `TransferValve::simulate()` gives a bomb before giving up on it. One tick is one
+/// `SSobj` fire, so this is a bit over 16 minutes.
+pub const TTV_DEFAULT_MAX_TICKS: usize = 500;
+
+/// How the simulated bomb ended up.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TtvOutcome {
+    /// The tank fragmented. This is the one you want.
+    Exploded(Explosion),
+    /// The tank burst and let all of its gas out without exploding.
+    Ruptured,
+    /// Nothing is reacting anymore and the tank is holding, so nothing else will happen.
+    Stabilised,
+    /// Still going after the maximum number of ticks.
+    TimedOut
+}
+
+/// One tick of a TTV simulation.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TtvTick<'a> {
+    /// Tick number, starting at 0 right after the valve opens.
+    pub tick: usize,
+    /// Temperature of the tank after reacting, right before its status got checked.
+    pub temperature: Real,
+    /// Pressure of the tank after reacting, right before its status got checked.
+    pub pressure: Real,
+    /// Ids of the reactions that happened this tick.
+    pub reactions: Vec<&'static str>,
+    /// Research points the reactions gave this tick.
+    pub research: Real,
+    /// What `check_status()` did.
+    pub status: TankStatus<'a>,
+    /// Integrity of the tank at the end of the tick.
+    pub integrity: usize
+}
+
+/// Result of `TransferValve::simulate()`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TtvReport<'a> {
+    /// Every tick, in order.
+    pub timeline: Vec<TtvTick<'a>>,
+    /// How it ended.
+    pub outcome: TtvOutcome
+}
+
+impl<'a> TtvReport<'a> {
+    /// The explosion, if there was one.
+    pub fn explosion(&self) -> Option<Explosion> {
+        match self.outcome {
+            TtvOutcome::Exploded(explosion) => Some(explosion),
+            _ => None
+        }
+    }
+
+    /// Highest temperature any tick reached.
+    pub fn peak_temperature(&self) -> Real {
+        self.timeline.iter().fold(0.0, |acc, tick| acc.max(tick.temperature))
+    }
+
+    /// Highest pressure any tick reached.
+    pub fn peak_pressure(&self) -> Real {
+        self.timeline.iter().fold(0.0, |acc, tick| acc.max(tick.pressure))
+    }
+
+    /// Research points the reactions gave over the whole run. The explosion itself is scored
+    /// separately, by a doppler array.
+    pub fn research(&self) -> Real {
+        self.timeline.iter().map(|tick| tick.research).sum()
+    }
+}
+
+/// A tank transfer valve, `/obj/item/transfer_valve`: two tanks that get opened into each other.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::atmospherics::machinery::tank::Tank;
+/// use libatmos::atmospherics::machinery::ttv::{TransferValve, TTV_DEFAULT_MAX_TICKS};
+/// use libatmos::constants::gases;
+/// // hot plasma in one tank, oxygen in the other
+/// let plasma = GasMixture::from_vecs(vec![&gases::PLASMA], vec![25.0], 1000.0, 70).unwrap();
+/// let oxygen = GasMixture::from_vecs(vec![&gases::O2], vec![75.0], 293.15, 70).unwrap();
+/// let ttv = TransferValve::new(Tank::new(plasma), Tank::new(oxygen));
+///
+/// let report = ttv.simulate(TTV_DEFAULT_MAX_TICKS);
+/// let explosion = report.explosion().unwrap();
+/// assert!(explosion.theoretical.light_impact > 0.0);
+/// assert!(report.timeline[0].reactions.contains(&"plasmafire"));
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransferValve<'a> {
+    pub tank_one: Tank<'a>,
+    pub tank_two: Tank<'a>,
+    valve_open: bool
+}
+
+impl<'a> TransferValve<'a> {
+    /// Creates a TTV with both tanks attached and the valve closed.
+    pub fn new(tank_one: Tank<'a>, tank_two: Tank<'a>) -> TransferValve<'a> {
+        TransferValve {
+            tank_one,
+            tank_two,
+            valve_open: false
+        }
+    }
+
+    /// Returns true if the valve has been opened.
+    pub fn is_open(&self) -> bool {
+        self.valve_open
+    }
+
+    /// Opens the valve, `merge_gases()` in tgstation: all of tank one's gas moves into tank two,
+    /// and tank two's volume grows by tank one's so the two act as one big tank. Does nothing
+    /// if the valve is already open.
+    pub fn open(&mut self) {
+        if self.valve_open {
+            return;
+        }
+        self.valve_open = true;
+        self.tank_two.air_contents.volume += self.tank_one.volume();
+        let moved = self.tank_one.air_contents.remove_ratio(1.0).unwrap(); // 1 is never negative
+        self.tank_two.air_contents.merge(moved);
+    }
+
+    /// Opens the valve and runs the tank the gas ended up in tick by tick until it explodes,
+    /// ruptures or stabilises, or `max_ticks` ticks have gone by.
+    pub fn simulate(mut self, max_ticks: usize) -> TtvReport<'a> {
+        self.open();
+        let tank = &mut self.tank_two;
+        let mut timeline = Vec::new();
+
+        for tick in 0..max_ticks {
+            let results = tank.air_contents.react();
+            let temperature = tank.air_contents.temperature;
+            let pressure = tank.air_contents.return_pressure();
+            let status = tank.check_status();
+
+            let outcome = match status {
+                TankStatus::Exploded(explosion) => Some(TtvOutcome::Exploded(explosion)),
+                TankStatus::Ruptured(_) => Some(TtvOutcome::Ruptured),
+                TankStatus::Stable if !results.reacted() && tank.integrity == TANK_MAX_INTEGRITY => Some(TtvOutcome::Stabilised),
+                _ => None
+            };
+            timeline.push(TtvTick {
+                tick,
+                temperature,
+                pressure,
+                reactions: results.fired,
+                research: results.research,
+                status,
+                integrity: tank.integrity
+            });
+
+            if let Some(outcome) = outcome {
+                info!("TTV simulation ended on tick {}: {:?}", tick, outcome);
+                return TtvReport { timeline, outcome };
+            }
+        }
+        TtvReport { timeline, outcome: TtvOutcome::TimedOut }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::atmospherics::explosion::{BombCap, Explosion};
+use crate::atmospherics::gases::{GasMixture, GasType};
+use crate::atmospherics::machinery::tank::Tank;
+use crate::atmospherics::machinery::ttv::{TransferValve, TtvReport, TTV_DEFAULT_MAX_TICKS};
+use crate::byond::Real;
+use crate::constants::*;
+use crate::errors::AtmosError;
+use std::thread;
+
+// Searches for the best two-tank TTV fill by just running the TTV simulation a lot. The
+// simulation is full of thresholds and integer rounding, so there are no gradients to follow;
+// instead this scatters starting points over the search space with a Halton sequence, then
+// runs a compass search (poll a step each way along every axis, move if it's better, halve the
+// step if not) from the best of them. Nothing's random, so the same constraints always give
+// the same answer, no matter how many threads the candidates get split across.
+
+/// Smallest step the compass search takes, as a fraction of each parameter's range.
+const MIN_STEP: Real = 1.0 / 256.0;
+
+/// What `Optimizer` scores designs by when they reach the bomb cap with `Goal::ReachCapWithLeast`.
+/// Big enough that every design at the cap beats every design that isn't.
+const CAP_REACHED_SCORE: Real = 1_000_000.0;
+
+/// What the optimizer is looking for.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Goal<'a> {
+    /// The biggest theoretical explosion.
+    MaxRange,
+    /// An explosion that reaches the bomb cap, using as little of this gas (i.e. plasma) as possible.
+    ReachCapWithLeast(&'a GasType)
+}
+
+/// What a toxins lab has to work with.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BombConstraints<'a> {
+    /// Gases available for the first tank.
+    pub tank_one_gases: Vec<&'a GasType>,
+    /// Gases available for the second tank.
+    pub tank_two_gases: Vec<&'a GasType>,
+    /// Highest pressure the tanks can be filled to in kPa, i.e. a canister's max release pressure.
+    pub max_pressure: Real,
+    /// Volume of each tank in litres.
+    pub tank_volume: usize,
+    /// Coldest the freezer can get a tank, in kelvin.
+    pub min_temperature: Real,
+    /// Hottest the heater can get a tank, in kelvin.
+    pub max_temperature: Real,
+    /// Bomb cap the explosion is checked against.
+    pub bombcap: BombCap,
+    /// Ticks each simulated bomb gets before it's given up on.
+    pub max_ticks: usize
+}
+
+impl<'a> Default for BombConstraints<'a> {
+    /// Plasma in one tank and oxygen in the other, filled from a canister and temperature-controlled
+    /// with a stock freezer and heater.
+    fn default() -> BombConstraints<'a> {
+        BombConstraints {
+            tank_one_gases: vec![&gases::PLASMA],
+            tank_two_gases: vec![&gases::O2],
+            max_pressure: num::CAN_MAX_RELEASE_PRESSURE,
+            tank_volume: 70,
+            min_temperature: num::T0C - 200.0,
+            max_temperature: num::T20C + 500.0,
+            bombcap: BombCap::default(),
+            max_ticks: TTV_DEFAULT_MAX_TICKS
+        }
+    }
+}
+
+impl<'a> BombConstraints<'a> {
+    /// Checks the constraints describe tanks that can actually be filled: temperatures above
+    /// absolute zero (and a freezer that isn't hotter than the heater), a pressure that isn't
+    /// negative and tanks with some volume.
+    pub fn validate(&self) -> Result<(), AtmosError> {
+        for temperature in [self.min_temperature, self.max_temperature] {
+            if !temperature.is_finite() || temperature <= 0.0 {
+                return Err(AtmosError::InvalidTemperature { temperature });
+            }
+        }
+        if self.min_temperature > self.max_temperature {
+            return Err(AtmosError::InvalidTemperature { temperature: self.min_temperature });
+        }
+        if !(self.max_pressure >= 0.0 && self.max_pressure.is_finite()) {
+            return Err(AtmosError::LessThanZero { value: self.max_pressure });
+        }
+        if self.tank_volume == 0 {
+            return Err(AtmosError::InvalidVolume { volume: 0.0 });
+        }
+        Ok(())
+    }
+}
+
+/// A TTV fill the optimizer tried, and what it did.
+#[derive(Debug, PartialEq, Clone)]
+pub struct BombDesign<'a> {
+    /// What goes in the first tank.
+    pub tank_one: GasMixture<'a>,
+    /// What goes in the second tank.
+    pub tank_two: GasMixture<'a>,
+    /// The simulated bomb.
+    pub report: TtvReport<'a>,
+    /// How good it is, higher is better. Only comparable between designs scored for the same goal.
+    pub score: Real
+}
+
+impl<'a> BombDesign<'a> {
+    /// The explosion, if the design explodes.
+    pub fn explosion(&self) -> Option<Explosion> {
+        self.report.explosion()
+    }
+
+    /// Total mols of `gas` across both tanks.
+    pub fn moles_of(&self, gas: &'a GasType) -> Real {
+        self.tank_one.get_moles(gas).unwrap_or(0.0) + self.tank_two.get_moles(gas).unwrap_or(0.0)
+    }
+}
+
+/// Best design found by `Optimizer::run()`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OptimizerResult<'a> {
+    /// The best design.
+    pub best: BombDesign<'a>,
+    /// How many designs got simulated to find it.
+    pub evaluations: usize
+}
+
+/// Searches for the best TTV fill within some `BombConstraints`.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::optimizer::{Optimizer, BombConstraints, Goal};
+/// let mut optimizer = Optimizer::new(BombConstraints::default(), Goal::MaxRange);
+/// optimizer.max_evaluations = 200;
+/// let result = optimizer.run().unwrap();
+/// assert!(result.best.explosion().is_some());
+/// assert!(result.evaluations <= 200);
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Optimizer<'a> {
+    pub constraints: BombConstraints<'a>,
+    pub goal: Goal<'a>,
+    /// How many starting points get scattered over the search space.
+    pub starts: usize,
+    /// How many of the best starting points get a compass search.
+    pub seeds: usize,
+    /// Most designs to simulate in total, starting points included. The search stops early when
+    /// it runs out, though there's always at least one design.
+    pub max_evaluations: usize,
+    /// How many threads to simulate designs on.
+    pub threads: usize
+}
+
+/// A point in the search space and the design it decodes to.
+struct Candidate<'a> {
+    point: Vec<Real>,
+    design: BombDesign<'a>
+}
+
+impl<'a> Optimizer<'a> {
+    /// Creates an optimizer with sensible search settings, using every available core.
+    pub fn new(constraints: BombConstraints<'a>, goal: Goal<'a>) -> Optimizer<'a> {
+        Optimizer {
+            constraints,
+            goal,
+            starts: 32,
+            seeds: 4,
+            max_evaluations: 2000,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get())
+        }
+    }
+
+    /// Runs the search and returns the best design it found. Always gives the same answer
+    /// for the same settings, whatever `threads` is. Errors if the constraints don't make sense;
+    /// see `BombConstraints::validate()`.
+    pub fn run(&self) -> Result<OptimizerResult<'a>, AtmosError> {
+        self.constraints.validate()?;
+        let dimensions = self.dimensions();
+        let starts = (0..self.starts.min(self.max_evaluations).max(1)).map(|index| halton(index + 1, dimensions))
+                                                                      .collect::<Vec<_>>();
+        let mut evaluations = starts.len();
+        let mut candidates = self.evaluate_all(starts)?;
+
+        // best first, earlier points winning ties so the order is stable
+        candidates.sort_by(|a, b| b.design.score.partial_cmp(&a.design.score).unwrap_or(std::cmp::Ordering::Equal));
+        candidates.truncate(self.seeds.max(1));
+
+        let mut best: Option<Candidate<'a>> = None;
+        for seed in candidates {
+            let (found, used) = self.compass_search(seed, self.max_evaluations.saturating_sub(evaluations))?;
+            evaluations += used;
+            if best.as_ref().is_none_or(|best| found.design.score > best.design.score) {
+                best = Some(found);
+            }
+        }
+        Ok(OptimizerResult {
+            best: best.unwrap().design, // there's always at least one seed
+            evaluations
+        })
+    }
+
+    /// Compass search from `start`, simulating at most `budget` designs. Returns the best
+    /// candidate and how many designs it simulated.
+    fn compass_search(&self, start: Candidate<'a>, budget: usize) -> Result<(Candidate<'a>, usize), AtmosError> {
+        let mut current = start;
+        let mut step = 0.25;
+        let mut used = 0;
+
+        while step >= MIN_STEP {
+            let mut polls = Vec::new();
+            for axis in 0..current.point.len() {
+                for direction in &[1.0, -1.0] {
+                    let mut point = current.point.clone();
+                    point[axis] = (point[axis] + direction * step).clamp(0.0, 1.0);
+                    if point != current.point {
+                        polls.push(point);
+                    }
+                }
+            }
+            if polls.is_empty() || used + polls.len() > budget {
+                break;
+            }
+            used += polls.len();
+
+            let mut better = None;
+            for candidate in self.evaluate_all(polls)? {
+                let to_beat = better.as_ref().unwrap_or(&current);
+                if candidate.design.score > to_beat.design.score {
+                    better = Some(candidate);
+                }
+            }
+            match better {
+                Some(candidate) => current = candidate,
+                None => step /= 2.0
+            }
+        }
+        Ok((current, used))
+    }
+
+    /// Simulates every point, split across `threads` threads. Results come back in the same order.
+    fn evaluate_all(&self, points: Vec<Vec<Real>>) -> Result<Vec<Candidate<'a>>, AtmosError> {
+        let chunk_size = points.len().div_ceil(self.threads.max(1)).max(1);
+        thread::scope(|scope| {
+            let handles = points.chunks(chunk_size)
+                                .map(|chunk| scope.spawn(move || chunk.iter().map(|point| self.evaluate(point)).collect::<Result<Vec<_>, _>>()))
+                                .collect::<Vec<_>>();
+            handles.into_iter()
+                   .map(|handle| handle.join().unwrap())
+                   .collect::<Result<Vec<_>, _>>()
+                   .map(|chunks| chunks.into_iter().flatten().collect())
+        })
+    }
+
+    /// Number of parameters per design: a temperature and a partial pressure for each gas, per tank.
+    fn dimensions(&self) -> usize {
+        2 + self.constraints.tank_one_gases.len() + self.constraints.tank_two_gases.len()
+    }
+
+    /// Decodes a point in the unit hypercube into a filled tank, starting at `point[0]`.
+    /// Partial pressures that add up to more than `max_pressure` get scaled down to fit.
+    fn fill(&self, gas_types: &[&'a GasType], point: &[Real]) -> Result<GasMixture<'a>, AtmosError> {
+        let constraints = &self.constraints;
+        let temperature = constraints.min_temperature + point[0] * (constraints.max_temperature - constraints.min_temperature);
+        let mut pressures = point[1..=gas_types.len()].iter()
+                                                      .map(|fraction| fraction * constraints.max_pressure)
+                                                      .collect::<Vec<_>>();
+        let total_pressure = pressures.iter().fold(0.0, |acc, pressure| acc + pressure);
+        if total_pressure > constraints.max_pressure {
+            for pressure in pressures.iter_mut() {
+                *pressure *= constraints.max_pressure / total_pressure;
+            }
+        }
+        // n = PV / RT
+        let moles = pressures.iter()
+                             .map(|pressure| pressure * constraints.tank_volume as Real / (num::R_IDEAL_GAS_EQUATION * temperature))
+                             .collect::<Vec<_>>();
+        GasMixture::from_vecs(gas_types.to_vec(), moles, temperature, constraints.tank_volume)
+    }
+
+    /// Decodes and simulates one point.
+    fn evaluate(&self, point: &[Real]) -> Result<Candidate<'a>, AtmosError> {
+        let split = 1 + self.constraints.tank_one_gases.len();
+        let tank_one = self.fill(&self.constraints.tank_one_gases, &point[..split])?;
+        let tank_two = self.fill(&self.constraints.tank_two_gases, &point[split..])?;
+
+        let mut first = Tank::new(tank_one.clone());
+        let mut second = Tank::new(tank_two.clone());
+        first.bombcap = self.constraints.bombcap;
+        second.bombcap = self.constraints.bombcap;
+        let report = TransferValve::new(first, second).simulate(self.constraints.max_ticks);
+
+        let mut design = BombDesign { tank_one, tank_two, report, score: 0.0 };
+        design.score = self.score(&design);
+        Ok(Candidate { point: point.to_vec(), design })
+    }
+
+    /// Scores a design for `goal`. Bombs that don't go off score by how close their peak pressure
+    /// got to fragmenting, which still gives the search something to climb.
+    fn score(&self, design: &BombDesign<'a>) -> Real {
+        let range = match design.explosion() {
+            Some(explosion) => explosion.range,
+            None => (design.report.peak_pressure() - num::TANK_FRAGMENT_PRESSURE) / num::TANK_FRAGMENT_SCALE
+        };
+        match self.goal {
+            Goal::MaxRange => range,
+            Goal::ReachCapWithLeast(gas) => {
+                let cap = &self.constraints.bombcap;
+                match design.explosion() {
+                    Some(explosion) if explosion.theoretical.devastation >= cap.devastation
+                                    && explosion.theoretical.heavy_impact >= cap.heavy_impact
+                                    && explosion.theoretical.light_impact >= cap.light_impact
+                                    && explosion.theoretical.flash >= cap.flash => CAP_REACHED_SCORE - design.moles_of(gas),
+                    _ => range
+                }
+            }
+        }
+    }
+}
+
+/// `index`th point of the Halton sequence in `dimensions` dimensions, each coordinate from 0 to 1.
+fn halton(index: usize, dimensions: usize) -> Vec<Real> {
+    const PRIMES: [usize; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+    (0..dimensions).map(|dimension| {
+        let base = PRIMES[dimension % PRIMES.len()];
+        let mut fraction = 1.0;
+        let mut result = 0.0;
+        let mut i = index;
+        while i > 0 {
+            fraction /= base as Real;
+            result += fraction * (i % base) as Real;
+            i /= base;
+        }
+        result
+    }).collect()
+}
@@ -0,0 +1,67 @@
+use crate::atmospherics::explosion::Explosion;
+use crate::byond::Real;
+use crate::constants::*;
+
+// The research side of toxins, ported from /obj/machinery/doppler_array/research. The doppler
+// array scores a bomb by its light impact range *before* the bomb cap, so bombs keep paying out
+// long after they stop getting any bigger in game. Reactions award research too; that's
+// tallied up in `ReactionResults::research`.
+
+/// Research points the doppler array's scoring curve gives a bomb with a theoretical light
+/// impact range of `light`, before the techweb takes away what earlier bombs already got.
+/// Nothing under 10 tiles, `83300 * light / (light + 3000)` up to 4500 tiles, and
+/// `TECHWEB_BOMB_POINTCAP` past that.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::research;
+/// assert_eq!(research::bomb_points(9.0), 0.0);
+/// assert_eq!(research::bomb_points(3000.0), 41650.0);
+/// assert_eq!(research::bomb_points(10000.0), 50000.0);
+/// ```
+pub fn bomb_points(light: Real) -> Real {
+    if light < 10.0 {
+        0.0 // "Explosion not large enough for research calculations."
+    } else if light < 4500.0 {
+        (83300.0 * light) / (light + 3000.0)
+    } else {
+        num::TECHWEB_BOMB_POINTCAP as Real
+    }
+}
+
+/// A research doppler array, and the part of the techweb it's linked to that remembers the
+/// biggest bomb so far. Only beating that bomb gets points, and only the difference.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::explosion::{BombCap, Explosion};
+/// use libatmos::atmospherics::research::{self, DopplerArray};
+/// let mut doppler = DopplerArray::default();
+/// let bomb = Explosion::from_range(100.0, &BombCap::default());
+///
+/// assert_eq!(doppler.sense_explosion(&bomb), research::bomb_points(100.0));
+/// assert_eq!(doppler.sense_explosion(&bomb), 0.0); // the same bomb twice is worth nothing
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct DopplerArray {
+    /// Points the biggest bomb so far was worth, `largest_bomb_value` on the techweb.
+    pub largest_bomb_value: Real
+}
+
+impl DopplerArray {
+    /// Creates a doppler array linked to a techweb that's already seen a bomb worth
+    /// `largest_bomb_value` points.
+    pub fn new(largest_bomb_value: Real) -> DopplerArray {
+        DopplerArray { largest_bomb_value }
+    }
+
+    /// Points the techweb would get for the array picking up `explosion`, scored on its
+    /// theoretical light impact range. Beating the biggest bomb so far makes this the new one.
+    pub fn sense_explosion(&mut self, explosion: &Explosion) -> Real {
+        let point_gain = bomb_points(explosion.theoretical.light_impact);
+        if point_gain <= self.largest_bomb_value {
+            return 0.0;
+        }
+        let gained = (point_gain - self.largest_bomb_value).min(num::TECHWEB_BOMB_POINTCAP as Real);
+        self.largest_bomb_value = point_gain;
+        gained
+    }
+}
@@ -0,0 +1,287 @@
+use crate::atmospherics::explosion::Explosion;
+use crate::atmospherics::gases::GasMixture;
+use crate::atmospherics::machinery::tank::{Tank, TankStatus, TANK_MAX_INTEGRITY, TANK_TICK_SECONDS};
+use crate::byond::Real;
+use crate::constants::*;
+
+// Ways to hurt people with gas that aren't a TTV: heating a single tank until it gives, and
+// dumping a grenade's worth of gas into a room. Rooms here are one well-mixed mixture, which is
+// close enough for a room with its doors shut; use the turf simulation for anything bigger.
+
+/// Seconds between air ticks, `SSair`'s wait.
+pub const AIR_TICK_SECONDS: Real = 0.5;
+
+/// Something heating (or cooling) a tank from the outside, like a heater or a fire.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HeatSource {
+    /// Temperature the source heats the tank towards, in kelvin.
+    pub temperature: Real,
+    /// Most energy the source can put into the tank, in watts.
+    pub power: Real
+}
+
+/// How a tank gave out.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TankFailure {
+    /// Over `TANK_FRAGMENT_PRESSURE`, with these ranges.
+    Fragmented(Explosion),
+    /// Burst from being over `TANK_RUPTURE_PRESSURE`.
+    Ruptured,
+    /// Burst from the gas inside getting past `TANK_MELT_TEMPERATURE`, without the pressure to rupture.
+    Melted
+}
+
+/// One tick of a single tank scenario.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TankTick {
+    /// Tick number, starting at 0.
+    pub tick: usize,
+    /// Seconds since the start, at the end of this tick.
+    pub time: Real,
+    /// Temperature of the tank right before its status got checked.
+    pub temperature: Real,
+    /// Pressure of the tank right before its status got checked.
+    pub pressure: Real,
+    /// Ids of the reactions that happened this tick.
+    pub reactions: Vec<&'static str>,
+    /// Research points the reactions gave this tick.
+    pub research: Real,
+    /// Integrity of the tank at the end of the tick.
+    pub integrity: usize,
+    /// Mols that leaked or burst out of the tank this tick.
+    pub released: Real
+}
+
+/// Result of `heat_tank()`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TankScenarioReport<'a> {
+    /// Every tick, in order.
+    pub timeline: Vec<TankTick>,
+    /// How the tank gave out, if it did. A tank that only leaks doesn't count.
+    pub failure: Option<TankFailure>,
+    /// Seconds until the tank first stopped holding its gas in, by leaking or worse.
+    pub time_to_failure: Option<Real>,
+    /// All the gas that got out of the tank, leaked or released.
+    pub released: GasMixture<'a>
+}
+
+impl<'a> TankScenarioReport<'a> {
+    /// The explosion, if the tank fragmented.
+    pub fn explosion(&self) -> Option<Explosion> {
+        match self.failure {
+            Some(TankFailure::Fragmented(explosion)) => Some(explosion),
+            _ => None
+        }
+    }
+
+    /// Research points the reactions gave over the whole run.
+    pub fn research(&self) -> Real {
+        self.timeline.iter().map(|tick| tick.research).sum()
+    }
+}
+
+/// Runs a single tank tick by tick, heated by `heat` if there's one, until it fragments,
+/// ruptures or melts, or settles down (nothing reacting, full integrity, and at the heat
+/// source's temperature), or `max_ticks` go by. Covers single-tank bombs (heat it until it
+/// fragments) and tank melts (something inside gets it past `TANK_MELT_TEMPERATURE`).
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::atmospherics::machinery::tank::Tank;
+/// use libatmos::atmospherics::scenarios::{self, HeatSource, TankFailure};
+/// use libatmos::constants::gases;
+/// // a tank at 2000 kPa, left on a 50 kW heater running at 3000 K
+/// let air = GasMixture::from_vecs(vec![&gases::N2], vec![57.5], 293.15, 70).unwrap();
+/// let heater = HeatSource { temperature: 3000.0, power: 50_000.0 };
+/// let report = scenarios::heat_tank(Tank::new(air), Some(heater), 1000);
+/// assert!(report.explosion().is_some());
+/// assert!(report.time_to_failure.unwrap() > 0.0);
+/// ```
+pub fn heat_tank<'a>(mut tank: Tank<'a>, heat: Option<HeatSource>, max_ticks: usize) -> TankScenarioReport<'a> {
+    let mut timeline = Vec::new();
+    let mut failure = None;
+    let mut time_to_failure = None;
+    let mut released = GasMixture::from_empty(tank.air_contents.temperature, tank.volume());
+
+    for tick in 0..max_ticks {
+        let time = (tick + 1) as Real * TANK_TICK_SECONDS;
+        if let Some(source) = heat {
+            tank.air_contents.heat_to(source.temperature, source.power, TANK_TICK_SECONDS);
+        }
+        let results = tank.air_contents.react();
+        let temperature = tank.air_contents.temperature;
+        let pressure = tank.air_contents.return_pressure();
+
+        let mut released_moles = 0.0;
+        match tank.check_status() {
+            TankStatus::Stable => {},
+            TankStatus::Leaking(gas) => {
+                released_moles = gas.total_moles();
+                released.merge(gas);
+            },
+            TankStatus::Ruptured(gas) => {
+                released_moles = gas.total_moles();
+                released.merge(gas);
+                failure = Some(if pressure > num::TANK_RUPTURE_PRESSURE { TankFailure::Ruptured } else { TankFailure::Melted });
+            },
+            TankStatus::Exploded(explosion) => failure = Some(TankFailure::Fragmented(explosion))
+        }
+        if (released_moles > 0.0 || failure.is_some()) && time_to_failure.is_none() {
+            time_to_failure = Some(time);
+        }
+
+        let settled = failure.is_none()
+            && released_moles <= 0.0
+            && !results.reacted()
+            && tank.integrity == TANK_MAX_INTEGRITY
+            && heat.is_none_or(|source| tank.air_contents.temperature == source.temperature || tank.air_contents.total_moles() <= 0.0);
+        timeline.push(TankTick {
+            tick,
+            time,
+            temperature,
+            pressure,
+            reactions: results.fired,
+            research: results.research,
+            integrity: tank.integrity,
+            released: released_moles
+        });
+        if failure.is_some() || settled {
+            break;
+        }
+    }
+
+    TankScenarioReport {
+        timeline,
+        failure,
+        time_to_failure,
+        released
+    }
+}
+
+/// A sealed room of `tiles` tiles full of standard station air.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::scenarios;
+/// let room = scenarios::standard_room(9);
+/// assert!((room.return_pressure() - 101.325).abs() < 0.01);
+/// ```
+pub fn standard_room<'a>(tiles: usize) -> GasMixture<'a> {
+    GasMixture::from_vecs(vec![&gases::O2, &gases::N2],
+                          vec![num::MOLES_O2STANDARD * tiles as Real, num::MOLES_N2STANDARD * tiles as Real],
+                          num::T20C,
+                          tiles * num::CELL_VOLUME).unwrap()
+}
+
+/// One air tick of a grenade scenario.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GrenadeTick {
+    /// Tick number. Tick 0 is right after the gas gets released.
+    pub tick: usize,
+    /// Seconds since the gas got released.
+    pub time: Real,
+    /// Room temperature after reacting.
+    pub temperature: Real,
+    /// Room pressure after reacting.
+    pub pressure: Real,
+    /// Ids of the reactions that happened this tick.
+    pub reactions: Vec<&'static str>,
+    /// How much burned this tick.
+    pub fire: Real,
+    /// Research points the reactions gave this tick.
+    pub research: Real
+}
+
+/// Result of `release_grenade()`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GrenadeReport<'a> {
+    /// Every tick, in order.
+    pub timeline: Vec<GrenadeTick>,
+    /// The room's air at the end.
+    pub room: GasMixture<'a>,
+    /// Seconds until the room got dangerous: over `HAZARD_HIGH_PRESSURE`, or hot enough to burn
+    /// (`BODYTEMP_HEAT_DAMAGE_LIMIT`). `None` if it never did.
+    pub time_to_hazard: Option<Real>,
+    /// Total fire over every tick.
+    pub burned: Real,
+    /// Total thermal energy the reactions released, in joules.
+    pub energy_released: Real,
+    /// Total research points the reactions gave.
+    pub research: Real
+}
+
+impl<'a> GrenadeReport<'a> {
+    /// Highest temperature the room reached.
+    pub fn peak_temperature(&self) -> Real {
+        self.timeline.iter().fold(0.0, |acc, tick| acc.max(tick.temperature))
+    }
+
+    /// Highest pressure the room reached.
+    pub fn peak_pressure(&self) -> Real {
+        self.timeline.iter().fold(0.0, |acc, tick| acc.max(tick.pressure))
+    }
+
+    /// Seconds the reactions kept going for.
+    pub fn burn_time(&self) -> Real {
+        self.timeline.iter()
+                     .rfind(|tick| !tick.reactions.is_empty())
+                     .map_or(0.0, |tick| tick.time + AIR_TICK_SECONDS)
+    }
+}
+
+/// Dumps `payload` into `room` all at once, like a chemical grenade going off, then lets the
+/// room react every air tick until nothing reacts anymore or `max_ticks` go by.
+/// ## Example
+/// ```rust
+/// use libatmos::atmospherics::gases::GasMixture;
+/// use libatmos::atmospherics::scenarios;
+/// use libatmos::constants::gases;
+/// // 100 mol of burning plasma in a 3x3 room
+/// let payload = GasMixture::from_vecs(vec![&gases::PLASMA], vec![100.0], 1000.0, 70).unwrap();
+/// let report = scenarios::release_grenade(payload, scenarios::standard_room(9), 200);
+/// assert!(report.burned > 0.0);
+/// assert!(report.peak_temperature() > 293.15);
+/// ```
+pub fn release_grenade<'a>(payload: GasMixture<'a>, mut room: GasMixture<'a>, max_ticks: usize) -> GrenadeReport<'a> {
+    room.merge(payload);
+    let mut timeline = Vec::new();
+    let mut time_to_hazard = None;
+    let mut burned = 0.0;
+    let mut energy_released = 0.0;
+    let mut research = 0.0;
+
+    for tick in 0..max_ticks {
+        let time = tick as Real * AIR_TICK_SECONDS;
+        let results = room.react();
+        let temperature = room.temperature;
+        let pressure = room.return_pressure();
+        burned += results.fire;
+        energy_released += results.energy_released;
+        research += results.research;
+
+        if time_to_hazard.is_none() && (pressure > num::HAZARD_HIGH_PRESSURE || temperature > num::BODYTEMP_HEAT_DAMAGE_LIMIT) {
+            time_to_hazard = Some(time);
+        }
+        let reacted = results.reacted();
+        timeline.push(GrenadeTick {
+            tick,
+            time,
+            temperature,
+            pressure,
+            reactions: results.fired,
+            fire: results.fire,
+            research: results.research
+        });
+        if !reacted {
+            break;
+        }
+    }
+
+    GrenadeReport {
+        timeline,
+        room,
+        time_to_hazard,
+        burned,
+        energy_released,
+        research
+    }
+}
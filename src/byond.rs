@@ -0,0 +1,40 @@
+// BYOND stores every number as a 32-bit float, so anything computed in-game is
+// rounded to single precision after every operation. With the `byond` feature
+// enabled, `Real` is an f32 and all atmos math rounds the same way the server does.
+
+/// Floating point type used for all atmos math.
+/// `f64` by default, `f32` with the `byond` cargo feature for bit-exact parity with BYOND.
+#[cfg(not(feature = "byond"))]
+pub type Real = f64;
+
+/// Floating point type used for all atmos math.
+/// `f64` by default, `f32` with the `byond` cargo feature for bit-exact parity with BYOND.
+#[cfg(feature = "byond")]
+pub type Real = f32;
+
+/// Smallest step `quantize()` rounds to.
+pub const QUANTIZE_STEP: Real = 0.000_000_1;
+
+/// BYOND's two-argument `round(value, step)`: rounds to the nearest multiple of `step`,
+/// computed as `floor(value / step + 0.5) * step` just like the engine does.
+/// ## Example
+/// ```rust
+/// use libatmos::byond;
+/// assert_eq!(byond::round(2.5, 1.0), 3.0);
+/// assert_eq!(byond::round(-2.5, 1.0), -2.0); // rounds half up, not away from zero
+/// assert_eq!(byond::round(1234.5678, 0.5), 1234.5);
+/// ```
+pub fn round(value: Real, step: Real) -> Real {
+    (value / step + 0.5).floor() * step
+}
+
+/// tgstation's `QUANTIZE()` define, `round(value, 0.0000001)`. Used when moving gas
+/// between mixtures to stop tiny floating point leftovers from piling up.
+/// ## Example
+/// ```rust
+/// use libatmos::byond;
+/// assert!((byond::quantize(0.123_456_789) - 0.123_456_8).abs() < 1e-6);
+/// ```
+pub fn quantize(value: Real) -> Real {
+    round(value, QUANTIZE_STEP)
+}
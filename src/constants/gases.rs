@@ -1,92 +1,125 @@
 use crate::atmospherics::gases::GasType;
+use crate::errors::AtmosError;
 
 pub static O2: GasType = GasType {
     id: "o2",
     name: "Oxygen",
     specific_heat: 20,
-    fusion_power: 0
+    fusion_power: 0,
+    molar_mass: 32.0
 };
 
 pub static N2: GasType = GasType {
     id: "n2",
     name: "Nitrogen",
     specific_heat: 20,
-    fusion_power: 0
+    fusion_power: 0,
+    molar_mass: 28.0
 };
 
 pub static CO2: GasType = GasType {
     id: "co2",
     name: "Carbon Dioxide",
     specific_heat: 30,
-    fusion_power: 0
+    fusion_power: 0,
+    molar_mass: 44.0
 };
 
 pub static PLASMA: GasType = GasType {
     id: "plasma",
     name: "Plasma",
     specific_heat: 200,
-    fusion_power: 0
+    fusion_power: 0,
+    molar_mass: 40.0
 };
 
 pub static H2O: GasType = GasType {
     id: "water_vapor",
     name: "Water Vapor",
     specific_heat: 40,
-    fusion_power: 8
+    fusion_power: 8,
+    molar_mass: 18.0
 };
 
 pub static HYPERNOB: GasType = GasType {
     id: "nob",
     name: "Hyper-noblium",
     specific_heat: 2000,
-    fusion_power: 0
+    fusion_power: 0,
+    molar_mass: 200.0
 };
 
 pub static N2O: GasType = GasType {
     id: "n2o",
     name: "Nitrous Oxide",
     specific_heat: 40,
-    fusion_power: 10
+    fusion_power: 10,
+    molar_mass: 44.0
 };
 
 pub static NO2: GasType = GasType {
     id: "no2",
     name: "Nitryl",
     specific_heat: 20,
-    fusion_power: 16
+    fusion_power: 16,
+    molar_mass: 46.0
 };
 
 pub static TRIT: GasType = GasType {
     id: "tritium",
     name: "Tritium",
     specific_heat: 10,
-    fusion_power: 1
+    fusion_power: 1,
+    molar_mass: 6.0
 };
 
 pub static BZ: GasType = GasType {
     id: "bz",
     name: "BZ",
     specific_heat: 20,
-    fusion_power: 8
+    fusion_power: 8,
+    molar_mass: 337.4
 };
 
 pub static STIM: GasType = GasType {
     id: "stim",
     name: "Stimulum",
     specific_heat: 5,
-    fusion_power: 7
+    fusion_power: 7,
+    molar_mass: 150.0
 };
 
 pub static PLUOX: GasType = GasType {
     id: "pluox",
     name: "Pluoxium",
     specific_heat: 80,
-    fusion_power: -10
+    fusion_power: -10,
+    molar_mass: 64.0
 };
 
 pub static MIASMA: GasType = GasType {
     id: "miasma",
     name: "Miasma",
     specific_heat: 20,
-    fusion_power: 0
+    fusion_power: 0,
+    molar_mass: 100.0
 };
+
+/// Every gas in the game, in the same order as tgstation's gas list.
+pub static ALL: [&GasType; 13] = [
+    &O2, &N2, &CO2, &PLASMA, &H2O, &HYPERNOB, &N2O, &NO2, &TRIT, &BZ, &STIM, &PLUOX, &MIASMA
+];
+
+/// Looks up a gas by its `id`, i.e. `"o2"` or `"plasma"`.
+/// ## Example
+/// ```rust
+/// use libatmos::constants::gases;
+/// assert_eq!(gases::from_id("plasma").unwrap(), &gases::PLASMA);
+/// assert!(gases::from_id("phlogiston").is_err());
+/// ```
+pub fn from_id(id: &str) -> Result<&'static GasType, AtmosError> {
+    ALL.iter()
+       .find(|gas| gas.id == id)
+       .copied()
+       .ok_or_else(|| AtmosError::UnknownGasId { id: id.to_string() })
+}
@@ -1,6 +1,8 @@
 /// Static gas structs - oxygen, nitrogen, etc.
 /// These should never be instantiated in other files.
 pub mod gases;
+/// Static species structs with their breathing requirements
+pub mod species;
 /// References to all react() functions
 pub mod reactions;
 /// All constant number values
@@ -1,21 +1,86 @@
+use crate::byond::Real;
+
 // Generic atmos 'defines'/pub constants
-pub const R_IDEAL_GAS_EQUATION:               f64   = 8.31;
-pub const ONE_ATMOSPHERE:                     f64   = 101.325;
-pub const TCMB:                               f64   = 2.7;
+pub const R_IDEAL_GAS_EQUATION:               Real   = 8.31;
+pub const ONE_ATMOSPHERE:                     Real   = 101.325;
+pub const TCMB:                               Real   = 2.7;
 pub const TCRYO:                              usize = 225;
-pub const T0C:                                f64   = 273.15;
-pub const T20C:                               f64   = 293.15;
+pub const T0C:                                Real   = 273.15;
+pub const T20C:                               Real   = 293.15;
+pub const CELL_VOLUME:                        usize = 2500;
+pub const O2STANDARD:                         Real   = 0.21;
+pub const N2STANDARD:                         Real   = 0.79;
+pub const MOLES_CELLSTANDARD:                 Real   = ONE_ATMOSPHERE * CELL_VOLUME as Real / (T20C * R_IDEAL_GAS_EQUATION);
+pub const MOLES_O2STANDARD:                   Real   = MOLES_CELLSTANDARD * O2STANDARD;
+pub const MOLES_N2STANDARD:                   Real   = MOLES_CELLSTANDARD * N2STANDARD;
+// Hazards, what air alarms and bodies care about
+pub const HAZARD_HIGH_PRESSURE:               Real   = 550.0;
+pub const WARNING_HIGH_PRESSURE:              Real   = 325.0;
+pub const WARNING_LOW_PRESSURE:               Real   = 50.0;
+pub const HAZARD_LOW_PRESSURE:                Real   = 20.0;
+pub const BODYTEMP_NORMAL:                    Real   = 310.15;
+pub const BODYTEMP_HEAT_DAMAGE_LIMIT:         Real   = BODYTEMP_NORMAL + 50.0;
+pub const BODYTEMP_COLD_DAMAGE_LIMIT:         Real   = BODYTEMP_NORMAL - 50.0;
 // Tanks
 pub const TANK_MELT_TEMPERATURE:              usize = 1_000_000;
-pub const TANK_LEAK_PRESSURE:                 f64   = 30.0 * ONE_ATMOSPHERE;
-pub const TANK_RUPTURE_PRESSURE:              f64   = 35.0 * ONE_ATMOSPHERE;
-pub const TANK_FRAGMENT_PRESSURE:             f64   = 40.0 * ONE_ATMOSPHERE;
-pub const TANK_FRAGMENT_SCALE:                f64   = 6.0  * ONE_ATMOSPHERE;
-pub const TANK_MAX_RELEASE_PRESSURE:          f64   = 3.0  * ONE_ATMOSPHERE;
+pub const TANK_LEAK_PRESSURE:                 Real   = 30.0 * ONE_ATMOSPHERE;
+pub const TANK_RUPTURE_PRESSURE:              Real   = 35.0 * ONE_ATMOSPHERE;
+pub const TANK_FRAGMENT_PRESSURE:             Real   = 40.0 * ONE_ATMOSPHERE;
+pub const TANK_FRAGMENT_SCALE:                Real   = 6.0  * ONE_ATMOSPHERE;
+pub const TANK_MAX_RELEASE_PRESSURE:          Real   = 3.0  * ONE_ATMOSPHERE;
+// Canisters
+pub const CAN_MAX_RELEASE_PRESSURE:           Real   = 10.0 * ONE_ATMOSPHERE;
+// Explosions
+pub const DEFAULT_BOMBCAP:                    Real   = 14.0; // config BOMBCAP, light impact cap
+// Turfs
+pub const OPEN_HEAT_TRANSFER_COEFFICIENT:     Real   = 0.4;
+pub const WALL_HEAT_TRANSFER_COEFFICIENT:     Real   = 0.0;
+pub const WINDOW_HEAT_TRANSFER_COEFFICIENT:   Real   = 0.1; // "a hack for now"
+pub const HEAT_CAPACITY_VACUUM:               Real   = 7000.0; // a hack to make vacuums "cold"
+// Superconduction
+pub const MINIMUM_TEMPERATURE_FOR_SUPERCONDUCTION:   Real = T20C + 10.0;
+pub const MINIMUM_TEMPERATURE_START_SUPERCONDUCTION: Real = T20C + 200.0;
+pub const M_CELL_WITH_RATIO:                  Real   = MOLES_CELLSTANDARD * 0.005; // compared against heat capacity, same as tgstation
+// LINDA
+pub const MINIMUM_AIR_RATIO_TO_SUSPEND:       Real   = 0.1;
+pub const MINIMUM_AIR_RATIO_TO_MOVE:          Real   = 0.001;
+pub const MINIMUM_AIR_TO_SUSPEND:             Real   = MOLES_CELLSTANDARD * MINIMUM_AIR_RATIO_TO_SUSPEND;
+pub const MINIMUM_MOLES_DELTA_TO_MOVE:        Real   = MOLES_CELLSTANDARD * MINIMUM_AIR_RATIO_TO_MOVE;
+pub const MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND: Real = 4.0;
+pub const MINIMUM_TEMPERATURE_TO_MOVE:        Real   = T20C + 100.0;
+pub const EXCITED_GROUP_BREAKDOWN_CYCLES:     usize = 4;
+pub const EXCITED_GROUP_DISMANTLE_CYCLES:     usize = 16;
+// Monstermos
+pub const MONSTERMOS_TURF_LIMIT:              usize = 200;
+pub const MONSTERMOS_HARD_TURF_LIMIT:         usize = 2000;
+// ZAS
+pub const MINIMUM_TEMPERATURE_RATIO_TO_SUSPEND: Real = 0.012;
+pub const ZAS_SHARING_LOOKUP_TABLE:           [Real; 6] = [0.30, 0.40, 0.48, 0.54, 0.60, 0.63]; // by connecting tiles, 6+ is the max
+// Space wind
+pub const MOVE_FORCE_DEFAULT:                 Real   = 1000.0;
+pub const MOVE_RESIST_DEFAULT:                Real   = MOVE_FORCE_DEFAULT;
+pub const MOVE_FORCE_PUSH_RATIO:              Real   = 1.0; // force needed per point of move_resist
+pub const MOVABLE_PRESSURE_RESISTANCE:        Real   = 10.0; // /atom/movable's pressure_resistance
+pub const PRESSURE_MOVE_PROBABILITY_OFFSET:   Real   = 25.0;
+pub const PRESSURE_MOVE_PROBABILITY_BASE:     Real   = 75.0;
+pub const PRESSURE_THROW_FORCE:               Real   = 6000.0; // "WALLSLAM HELL TIME OH BOY"
+// Research
+pub const TECHWEB_BOMB_POINTCAP:              usize = 50_000;
 
 // 'Defines'/pub constants used in gas reactions
-pub const MINIMUM_TEMP_DELTA_TO_CONSIDER:     f64   = 0.5;
-pub const OXYGEN_BURN_RATE_BASE:              f64   = 1.4;
+pub const MINIMUM_TEMP_DELTA_TO_CONSIDER:     Real   = 0.5;
+pub const MINIMUM_HEAT_CAPACITY:              Real   = 0.0003;
+pub const MINIMUM_MOLE_COUNT:                 Real   = 0.01;
+pub const MOLES_GAS_VISIBLE:                  Real   = 0.25;
+pub const FIRE_MINIMUM_TEMPERATURE_TO_EXIST:  Real   = 100.0 + T0C;
+pub const FIRE_MINIMUM_TEMPERATURE_TO_SPREAD: Real   = 150.0 + T0C;
+pub const FIRE_SPREAD_RADIOSITY_SCALE:        Real   = 0.85;
+pub const FIRE_GROWTH_RATE:                   usize = 40_000;
+pub const PLASMA_MINIMUM_BURN_TEMPERATURE:    Real   = 100.0 + T0C;
+pub const PLASMA_UPPER_TEMPERATURE:           Real   = 1370.0 + T0C;
+pub const PLASMA_OXYGEN_FULLBURN:             usize = 10;
+pub const FIRE_PLASMA_ENERGY_RELEASED:        usize = 3_000_000;
+pub const OXYGEN_BURN_RATE_BASE:              Real   = 1.4;
 pub const PLASMA_BURN_RATE_DELTA:             usize = 9;
 pub const PLASMA_MINIMUM_OXYGEN_NEEDED:       usize = 2;
 pub const PLASMA_MINIMUM_OXYGEN_PLASMA_RATIO: usize = 30;
@@ -31,28 +96,28 @@ pub const NITRYL_FORMATION_ENERGY:            usize = 100_000;
 pub const TRITIUM_BURN_OXY_FACTOR:            usize = 100;
 pub const TRITIUM_BURN_TRIT_FACTOR:           usize = 10;
 pub const TRITIUM_BURN_RADIOACTIVITY_FACTOR:  usize = 50_000;
-pub const TRITIUM_MINIMUM_RADIATION_ENERGY:   f64   = 0.1;
+pub const TRITIUM_MINIMUM_RADIATION_ENERGY:   Real   = 0.1;
 pub const MINIMUM_TRIT_OXYBURN_ENERGY:        usize = 2_000_000;
 pub const SUPER_SATURATION_THRESHOLD:         usize = 96;
 pub const STIMULUM_HEAT_SCALE:                usize = 100_000;
-pub const STIMULUM_FIRST_RISE:                f64   = 0.65;
-pub const STIMULUM_FIRST_DROP:                f64   = 0.065;
-pub const STIMULUM_SECOND_RISE:               f64   = 0.0009;
-pub const STIMULUM_ABSOLUTE_DROP:             f64   = 0.00000335;
+pub const STIMULUM_FIRST_RISE:                Real   = 0.65;
+pub const STIMULUM_FIRST_DROP:                Real   = 0.065;
+pub const STIMULUM_SECOND_RISE:               Real   = 0.0009;
+pub const STIMULUM_ABSOLUTE_DROP:             Real   = 0.00000335;
 pub const REACTION_OPPRESSION_THRESHOLD:      usize = 5;
 pub const NOBLIUM_FORMATION_ENERGY:           usize = 2_000_000_000;
 pub const STIM_BALL_GAS_AMOUNT:               usize = 5;
 
 pub const NOBLIUM_RESEARCH_AMOUNT:            usize = 1000;
 pub const BZ_RESEARCH_SCALE:                  usize = 4;
-pub const BZ_RESEARCH_MAX_AMOUNT:             usize = 400;
+pub const BZ_RESEARCH_MAX_AMOUNT:             usize = 400; // never applies in game, see bz_formation()
 pub const MIASMA_RESEARCH_AMOUNT:             usize = 40;
 pub const STIMULUM_RESEARCH_AMOUNT:           usize = 50;
 
 pub const FUSION_ENERGY_THRESHOLD:            usize = 3_000_000_000;
 pub const FUSION_MOLE_THRESHOLD:              usize = 250;
-pub const FUSION_TRIT_CONVERSION_COEFFICIENT: f64   = 0.00000000001;
-pub const INSTABILITY_GAS_POWER_FACTOR:       f64   = 0.003;
+pub const FUSION_TRIT_CONVERSION_COEFFICIENT: Real   = 0.00000000001;
+pub const INSTABILITY_GAS_POWER_FACTOR:       Real   = 0.003;
 pub const FUSION_TRITIUM_MOLES_USED:          usize = 1;
 pub const PLASMA_BINDING_ENERGY:              usize = 20_000_000;
 pub const TOROID_VOLUME_BREAKEVEN:            usize = 1000;
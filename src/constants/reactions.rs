@@ -0,0 +1,130 @@
+use crate::atmospherics::gases::reactions::{self, GasReaction, Requirement};
+use crate::byond::Real;
+use crate::constants::{gases, num};
+
+// tgstation's water vapor reaction only freezes and wets the floor it's on, so it isn't here.
+
+pub static NOBLIUM_SUPPRESSION: GasReaction = GasReaction {
+    id: "nobstop",
+    name: "Hyper-Noblium Reaction Suppression",
+    priority: Real::INFINITY,
+    min_requirements: &[
+        Requirement::Moles(&gases::HYPERNOB, num::REACTION_OPPRESSION_THRESHOLD as Real)
+    ],
+    react: reactions::noblium_suppression
+};
+
+pub static NOBLIUM_FORMATION: GasReaction = GasReaction {
+    id: "nobformation",
+    name: "Hyper-Noblium condensation",
+    priority: 6.0,
+    min_requirements: &[
+        Requirement::Moles(&gases::N2, 10.0),
+        Requirement::Moles(&gases::TRIT, 5.0),
+        Requirement::ThermalEnergy(num::NOBLIUM_FORMATION_ENERGY as Real)
+    ],
+    react: reactions::noblium_formation
+};
+
+pub static STIMULUM_FORMATION: GasReaction = GasReaction {
+    id: "stimformation",
+    name: "Stimulum formation",
+    priority: 5.0,
+    min_requirements: &[
+        Requirement::Moles(&gases::TRIT, 30.0),
+        Requirement::Moles(&gases::PLASMA, 10.0),
+        Requirement::Moles(&gases::BZ, 20.0),
+        Requirement::Moles(&gases::NO2, 30.0),
+        Requirement::Temperature(num::STIMULUM_HEAT_SCALE as Real / 2.0)
+    ],
+    react: reactions::stimulum_formation
+};
+
+pub static BZ_FORMATION: GasReaction = GasReaction {
+    id: "bzformation",
+    name: "BZ Gas formation",
+    priority: 4.0,
+    min_requirements: &[
+        Requirement::Moles(&gases::N2O, 10.0),
+        Requirement::Moles(&gases::PLASMA, 10.0)
+    ],
+    react: reactions::bz_formation
+};
+
+pub static NITRYL_FORMATION: GasReaction = GasReaction {
+    id: "nitrylformation",
+    name: "Nitryl formation",
+    priority: 3.0,
+    min_requirements: &[
+        Requirement::Moles(&gases::O2, 20.0),
+        Requirement::Moles(&gases::N2, 20.0),
+        Requirement::Moles(&gases::N2O, 5.0),
+        Requirement::Temperature(num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST * 400.0)
+    ],
+    react: reactions::nitryl_formation
+};
+
+pub static FUSION: GasReaction = GasReaction {
+    id: "fusion",
+    name: "Plasmic Fusion",
+    priority: 2.0,
+    min_requirements: &[
+        Requirement::Temperature(num::FUSION_TEMPERATURE_THRESHOLD as Real),
+        Requirement::Moles(&gases::TRIT, num::FUSION_TRITIUM_MOLES_USED as Real),
+        Requirement::Moles(&gases::PLASMA, num::FUSION_MOLE_THRESHOLD as Real),
+        Requirement::Moles(&gases::CO2, num::FUSION_MOLE_THRESHOLD as Real)
+    ],
+    react: reactions::fusion
+};
+
+pub static NITROUS_DECOMPOSITION: GasReaction = GasReaction {
+    id: "nitrous_decomp",
+    name: "Nitrous Oxide Decomposition",
+    priority: 0.0,
+    min_requirements: &[
+        Requirement::Temperature(num::N2O_DECOMPOSITION_MIN_ENERGY as Real),
+        Requirement::Moles(&gases::N2O, num::MINIMUM_MOLE_COUNT)
+    ],
+    react: reactions::nitrous_decomposition
+};
+
+pub static TRITIUM_FIRE: GasReaction = GasReaction {
+    id: "tritfire",
+    name: "Tritium Combustion",
+    priority: -1.0,
+    min_requirements: &[
+        Requirement::Temperature(num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST),
+        Requirement::Moles(&gases::TRIT, num::MINIMUM_MOLE_COUNT),
+        Requirement::Moles(&gases::O2, num::MINIMUM_MOLE_COUNT)
+    ],
+    react: reactions::tritium_fire
+};
+
+pub static PLASMA_FIRE: GasReaction = GasReaction {
+    id: "plasmafire",
+    name: "Plasma Combustion",
+    priority: -2.0,
+    min_requirements: &[
+        Requirement::Temperature(num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST),
+        Requirement::Moles(&gases::PLASMA, num::MINIMUM_MOLE_COUNT),
+        Requirement::Moles(&gases::O2, num::MINIMUM_MOLE_COUNT)
+    ],
+    react: reactions::plasma_fire
+};
+
+pub static MIASMA_STERILIZATION: GasReaction = GasReaction {
+    id: "sterilization",
+    name: "Dry Heat Sterilization",
+    priority: -10.0,
+    min_requirements: &[
+        Requirement::Moles(&gases::MIASMA, num::MINIMUM_MOLE_COUNT),
+        Requirement::Temperature(num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST + 70.0)
+    ],
+    react: reactions::miasma_sterilization
+};
+
+/// Every reaction, highest priority first. This is the order `react()` tries them in.
+pub static ALL: [&GasReaction; 10] = [
+    &NOBLIUM_SUPPRESSION, &NOBLIUM_FORMATION, &STIMULUM_FORMATION, &BZ_FORMATION, &NITRYL_FORMATION,
+    &FUSION, &NITROUS_DECOMPOSITION, &TRITIUM_FIRE, &PLASMA_FIRE, &MIASMA_STERILIZATION
+];
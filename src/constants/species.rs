@@ -0,0 +1,40 @@
+use crate::atmospherics::gases::Species;
+
+pub static HUMAN: Species = Species {
+    id: "human",
+    name: "Human",
+    safe_oxygen_min: 16.0,
+    safe_oxygen_max: 0.0,
+    safe_nitro_min: 0.0,
+    safe_nitro_max: 0.0,
+    safe_co2_min: 0.0,
+    safe_co2_max: 10.0,
+    safe_toxins_min: 0.0,
+    safe_toxins_max: 0.05
+};
+
+pub static LIZARD: Species = Species {
+    id: "lizard",
+    name: "Lizardperson",
+    safe_oxygen_min: 16.0,
+    safe_oxygen_max: 0.0,
+    safe_nitro_min: 0.0,
+    safe_nitro_max: 0.0,
+    safe_co2_min: 0.0,
+    safe_co2_max: 10.0,
+    safe_toxins_min: 0.0,
+    safe_toxins_max: 0.05
+};
+
+pub static PLASMAMAN: Species = Species {
+    id: "plasmaman",
+    name: "Plasmaman",
+    safe_oxygen_min: 0.0,
+    safe_oxygen_max: 0.0,
+    safe_nitro_min: 0.0,
+    safe_nitro_max: 0.0,
+    safe_co2_min: 0.0,
+    safe_co2_max: 10.0,
+    safe_toxins_min: 16.0,
+    safe_toxins_max: 0.0
+};
@@ -1,14 +1,32 @@
-use crate::atmospherics::gases::GasType;
+use crate::atmospherics::environmental::Position;
+use crate::byond::Real;
 use snafu::Snafu;
 
-#[derive(Debug, Snafu)]
-pub enum AtmosError<'a> {
+/// Everything that can go wrong in libatmos. Owns all of its data, so it's `Send + Sync + 'static`
+/// and can be boxed, sent between threads or handed across FFI without dragging a mixture's
+/// lifetime along. More variants will show up as more of SS13 gets ported, so don't match on it
+/// exhaustively.
+#[derive(Debug, Clone, PartialEq, Snafu)]
+#[non_exhaustive]
+pub enum AtmosError {
     #[snafu(display("Vectors in from_vecs were not the same length! \n Length of gas_vec: {} \n Length of mole_vec: {}", gas_length, mole_length))]
     VectorLengthMismatch { gas_length: usize, mole_length: usize },
-    #[snafu(display("Couldn't find gas '{:?}'. Did you check that it exists or call assert_gas() beforehand?", gas))]
-    GasNotFound { gas: &'a GasType },
-    #[snafu(display("Gas mixture was empty! Did you forget to call assert_gas() or add any moles beforehand?"))]
-    GasMixtureEmpty,
+    #[snafu(display("Couldn't find gas '{}'. Did you check that it exists or call assert_gas() beforehand?", gas))]
+    GasNotFound { gas: String },
+    #[snafu(display("No gas with id '{}' exists. Check constants::gases::ALL for valid ids.", id))]
+    UnknownGasId { id: String },
     #[snafu(display("Positive-only value was negative: {}. Something's gone wrong!", value))]
-    LessThanZero { value: f64 }
+    LessThanZero { value: Real },
+    #[snafu(display("Gas '{}' can't have a negative mole count ({} mol).", gas, moles))]
+    NegativeMoles { gas: String, moles: Real },
+    #[snafu(display("Invalid temperature {} K. Temperatures have to be finite and not below absolute zero.", temperature))]
+    InvalidTemperature { temperature: Real },
+    #[snafu(display("Invalid volume {} L. Mixtures that hold gas need a finite, positive volume.", volume))]
+    InvalidVolume { volume: Real },
+    #[snafu(display("Couldn't parse '{}': {}", input, reason))]
+    Parse { input: String, reason: String },
+    #[snafu(display("Invariant broken by {}: {}", operation, details))]
+    InvariantViolated { operation: String, details: String },
+    #[snafu(display("Turf {} is off the grid, which is {}x{}x{}.", position, width, height, depth))]
+    OutOfBounds { position: Position, width: usize, height: usize, depth: usize }
 }
@@ -10,33 +10,89 @@ use log::{info};
 /// Module containing all actual atmos code
 pub mod atmospherics {
     /// Environmental atmos-related stuff, LINDA, hotspots, etc
-    pub mod environmental {}
+    pub mod environmental;
     /// Gas code--reactions, gases, etc
     pub mod gases;
     /// Machinery code--air pumps, pipes, canisters, etc
     pub mod machinery;
+    /// Explosion ranges--tank fragmentation, bomb caps
+    pub mod explosion;
+    /// Searching for the best TTV bomb fills
+    pub mod optimizer;
+    /// Single-tank bombs, tank melts and gas grenades
+    pub mod scenarios;
+    /// Toxins research--doppler array points
+    pub mod research;
 }
 /// Module containing all constant or static values used in the program
 pub mod constants;
+/// BYOND number emulation--the `Real` float type and `QUANTIZE`
+pub mod byond;
+/// Typed physical units (K, kPa, mol, L, J...) and conversions between them
+pub mod units;
 
 /// Testing module.
 #[cfg(test)]
 mod tests {
     /// ENVIRONMENT!
-    mod environmental {}
+    mod environmental {
+        /// Air mixtures the other tests build their grids out of
+        mod fixtures;
+        /// Turf grids
+        mod grid;
+        /// LINDA processing
+        mod linda;
+        /// Monstermos equalization
+        mod monstermos;
+        /// ZAS zones and edges
+        mod zas;
+        /// Hotspots and fire spread
+        mod hotspot;
+        /// Pressure differences and space wind
+        mod wind;
+        /// Superconduction through walls and windows
+        mod superconduction;
+        /// Multi-z adjacency and open space
+        mod multiz;
+    }
     /// Testing for gas-related stuff.
     mod gases {
+        /// Gas type ordering
+        mod gastype;
         /// Gas-mixture methods
         mod gasmixture;
+        /// Invariant checks
+        mod invariants;
         /// Reaction methods
         mod reactions;
     }
+    /// Explosion ranges and caps
+    mod explosion;
+    /// Bomb optimizer
+    mod optimizer;
+    /// Tank heating and grenade scenarios
+    mod scenarios;
+    /// Doppler array research points
+    mod research;
     /// MACHINES!
-    mod machinery {}
+    mod machinery {
+        /// Tank status checks
+        mod tank;
+        /// Gas analyzer readouts
+        mod analyzer;
+        /// TTV bomb simulation
+        mod ttv;
+    }
+    /// BYOND rounding
+    mod byond;
+    /// Error type
+    mod errors;
+    /// Unit conversions
+    mod units;
 }
 
 /// Error module.
-mod errors;
+pub mod errors;
 
 /// Logging module using fern.
 mod logging;
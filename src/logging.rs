@@ -2,6 +2,7 @@
 // Once we're an actual crate of course
 
 /// Setup logging system utilizing `fern` + `log`.
+#[allow(dead_code)] // not hooked up to anything until logging is a cargo feature
 fn setup_logger() -> Result<(), fern::InitError> {
     fern::Dispatch::new()
         .format(|out, message, record| {
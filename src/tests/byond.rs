@@ -0,0 +1,47 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    byond::{self, Real},
+};
+
+#[test]
+fn round() {
+    assert_eq!(byond::round(2.4, 1.0), 2.0);
+    assert_eq!(byond::round(2.5, 1.0), 3.0);
+    assert_eq!(byond::round(-2.5, 1.0), -2.0);
+    assert_eq!(byond::round(17.0, 5.0), 15.0);
+    assert_eq!(byond::round(18.0, 5.0), 20.0);
+}
+
+#[test]
+fn quantize() {
+    assert_eq!(byond::quantize(0.0), 0.0);
+    assert!((byond::quantize(1.000_000_04) - 1.0).abs() < 1e-6);
+    assert!((byond::quantize(0.000_000_06) - 0.000_000_1).abs() < 1e-9);
+}
+
+#[test]
+fn remove_is_quantized() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![1.0, 2.0], 293.15, 70).unwrap();
+    let removed = mix.remove(1.0).unwrap();
+    let o2 = removed.get_moles(&gases::O2).unwrap();
+    assert_eq!(o2, byond::quantize(o2));
+    assert!((o2 + mix.get_moles(&gases::O2).unwrap() - 1.0).abs() < 1e-6); // nothing lost
+}
+
+#[cfg(feature = "byond")]
+#[test]
+fn single_precision() {
+    // 0.1 + 0.2 rounds differently in single precision
+    let sum: Real = 0.1 + 0.2;
+    assert_eq!(sum, 0.3);
+    assert_eq!(std::mem::size_of::<Real>(), 4);
+}
+
+#[cfg(not(feature = "byond"))]
+#[test]
+fn double_precision() {
+    let sum: Real = 0.1 + 0.2;
+    assert_ne!(sum, 0.3);
+    assert_eq!(std::mem::size_of::<Real>(), 8);
+}
@@ -0,0 +1,25 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    byond::Real,
+};
+
+/// A tile of standard station air.
+pub fn station_air<'a>() -> GasMixture<'a> {
+    station_air_at(num::T20C)
+}
+
+/// A tile's worth of station air's mols, at `temperature`.
+pub fn station_air_at<'a>(temperature: Real) -> GasMixture<'a> {
+    GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![num::MOLES_O2STANDARD, num::MOLES_N2STANDARD], temperature, num::CELL_VOLUME).unwrap()
+}
+
+/// An empty tile.
+pub fn vacuum<'a>() -> GasMixture<'a> {
+    GasMixture::from_empty(num::T20C, num::CELL_VOLUME)
+}
+
+/// A tile of `plasma` mol of plasma and plenty of oxygen to burn it with.
+pub fn plasma_air<'a>(plasma: Real) -> GasMixture<'a> {
+    GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![plasma, 80.0], num::T20C, num::CELL_VOLUME).unwrap()
+}
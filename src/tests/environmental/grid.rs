@@ -0,0 +1,92 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::environmental::*,
+    errors::AtmosError,
+};
+
+fn room<'a>(width: usize, height: usize, depth: usize) -> TurfGrid<'a> {
+    let air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![num::MOLES_O2STANDARD, num::MOLES_N2STANDARD], num::T20C, num::CELL_VOLUME).unwrap();
+    TurfGrid::new(width, height, depth, Turf::floor(air))
+}
+
+#[test]
+fn indices_round_trip() {
+    let grid = room(4, 3, 2);
+    assert_eq!(grid.len(), 24);
+    for (index, pos) in grid.positions().enumerate() {
+        assert_eq!(grid.index_of(pos), Some(index));
+        assert_eq!(grid.position_of(index), pos);
+    }
+    assert_eq!(grid.index_of(Position::new(4, 0, 0)), None);
+    assert_eq!(grid.index_of(Position::new(0, 0, 2)), None);
+}
+
+#[test]
+fn neighbours_stay_on_the_grid() {
+    let grid = room(3, 3, 2);
+    let corner = grid.neighbours(Position::new(0, 0, 0));
+    assert_eq!(corner, vec![(Direction::North, Position::new(0, 1, 0)), (Direction::East, Position::new(1, 0, 0))]);
+    // z-levels aren't connected to each other
+    assert_eq!(grid.neighbours(Position::new(1, 1, 1)).len(), 4);
+    assert!(grid.neighbours(Position::new(1, 1, 1)).iter().all(|(_, pos)| pos.z == 1));
+}
+
+#[test]
+fn blocking_goes_both_ways() {
+    let mut grid = room(3, 1, 1);
+    let middle = Position::new(1, 0, 0);
+    assert_eq!(grid.adjacent(middle).len(), 2);
+
+    // a window on the east side of the west turf cuts the middle off from it too
+    grid.set_blocked(Position::new(0, 0, 0), Direction::East, true).unwrap();
+    assert_eq!(grid.adjacent(middle), vec![(Direction::East, Position::new(2, 0, 0))]);
+    assert!(grid.adjacent(Position::new(0, 0, 0)).is_empty());
+
+    grid.set_blocked(Position::new(0, 0, 0), Direction::East, false).unwrap();
+    assert_eq!(grid.adjacent(middle).len(), 2);
+    assert_eq!(grid.revision(), 2);
+}
+
+#[test]
+fn replacing_turfs() {
+    let mut grid = room(2, 2, 1);
+    let pos = Position::new(1, 1, 0);
+    let old = grid.replace(pos, Turf::space()).unwrap();
+
+    assert_eq!(old.kind(), TurfKind::Floor);
+    assert!(grid[pos].is_space());
+    assert!(grid[pos].air.is_empty());
+    assert_eq!(grid.revision(), 1);
+    assert!((grid.total_moles() - 3.0 * num::MOLES_CELLSTANDARD).abs() < 0.001);
+
+    grid.replace(Position::new(0, 1, 0), Turf::wall()).unwrap();
+    assert!(grid.adjacent(Position::new(0, 1, 0)).is_empty());
+    assert_eq!(grid.adjacent(pos), vec![(Direction::South, Position::new(1, 0, 0))]); // space is open, walls aren't
+}
+
+#[test]
+fn out_of_bounds() {
+    let mut grid = room(2, 2, 1);
+    let off = Position::new(2, 0, 0);
+    assert!(grid.get(off).is_none());
+    assert!(grid.get_mut(off).is_none());
+    assert!(grid.adjacent(off).is_empty());
+    assert_eq!(grid.replace(off, Turf::wall()).unwrap_err(),
+               AtmosError::OutOfBounds { position: off, width: 2, height: 2, depth: 1 });
+    assert!(grid.set_blocked(off, Direction::North, true).is_err());
+    assert_eq!(grid.revision(), 0);
+}
+
+#[test]
+fn mutating_air() {
+    let mut grid = room(2, 1, 1);
+    let pos = Position::new(0, 0, 0);
+    grid[pos].air.change_moles(&gases::O2, 10.0).unwrap();
+    grid.get_mut(pos).unwrap().air.temperature = 500.0;
+
+    assert_eq!(grid[pos].air.temperature, 500.0);
+    assert_eq!(grid[Position::new(1, 0, 0)].air.temperature, num::T20C);
+    assert_eq!(grid[pos].initial_air().temperature, num::T20C);
+    assert_eq!(grid.revision(), 0);
+}
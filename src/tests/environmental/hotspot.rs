@@ -0,0 +1,136 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::environmental::*,
+};
+use super::fixtures::*;
+
+#[test]
+fn ignition_needs_fuel_oxygen_and_heat() {
+    let grid = TurfGrid::from_fn(4, 1, 1, |pos| match pos.x {
+        0 => Turf::floor(station_air()),
+        1 => Turf::floor(GasMixture::from_vecs(vec![&gases::PLASMA], vec![100.0], num::T20C, num::CELL_VOLUME).unwrap()),
+        2 => Turf::floor(plasma_air(20.0)),
+        _ => Turf::wall()
+    });
+    let mut sim = Simulation::new(grid);
+
+    assert!(!sim.hotspot_expose(Position::new(0, 0, 0), 1000.0, 5.0)); // no fuel
+    assert!(!sim.hotspot_expose(Position::new(1, 0, 0), 1000.0, 5.0)); // no oxygen
+    assert!(!sim.hotspot_expose(Position::new(2, 0, 0), num::PLASMA_MINIMUM_BURN_TEMPERATURE, 5.0)); // not hot enough
+    assert!(!sim.hotspot_expose(Position::new(3, 0, 0), 1000.0, 5.0)); // a wall
+    assert!(!sim.hotspot_expose(Position::new(9, 0, 0), 1000.0, 5.0)); // off the grid
+    assert!(sim.hotspots().is_empty());
+
+    assert!(sim.hotspot_expose(Position::new(2, 0, 0), 1000.0, 5.0));
+    assert!(sim.is_active(Position::new(2, 0, 0)));
+    let hotspot = *sim.hotspots().get(&sim.grid, Position::new(2, 0, 0)).unwrap();
+    assert!(hotspot.just_spawned());
+    assert!(hotspot.temperature > 1000.0);
+}
+
+#[test]
+fn fire_spreads_and_gets_reported() {
+    let mut sim = Simulation::new(TurfGrid::new(5, 5, 1, Turf::floor(plasma_air(20.0))));
+    sim.hotspot_expose(Position::new(2, 2, 0), 1000.0, 5.0);
+
+    let reports = sim.run(10);
+    assert!(reports.iter().map(|report| report.fires.ignited).sum::<usize>() >= 4);
+    let last = &reports.last().unwrap().fires;
+    assert!(last.burning.len() >= 5);
+    assert_eq!(last.burning[0].position, Position::new(2, 2, 0)); // oldest first
+    for burning in &last.burning {
+        assert!(sim.hotspots().is_burning(&sim.grid, burning.position));
+        assert!(burning.temperature >= num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST);
+    }
+    assert!(sim.grid[Position::new(2, 3, 0)].air.get_moles(&gases::CO2).unwrap() > 0.0);
+}
+
+#[test]
+fn spread_fires_react_when_they_start() {
+    // a quarter of a tile's worth of exposure, times 25, is more than the whole tile, but a new
+    // fire still burns its own air before it starts following the turf's
+    let mut sim = Simulation::new(TurfGrid::new(5, 5, 1, Turf::floor(plasma_air(20.0))));
+    sim.hotspot_expose(Position::new(2, 2, 0), 1000.0, 5.0);
+
+    let report = (0..10).map(|_| sim.tick()).find(|report| report.fires.ignited > 0).unwrap();
+    let spread = report.fires.burning.iter().filter(|burning| burning.position != Position::new(2, 2, 0)).collect::<Vec<_>>();
+    assert_eq!(spread.len(), report.fires.ignited);
+    for burning in &spread {
+        assert!(!burning.bypassing);
+        assert!(burning.temperature >= num::FIRE_MINIMUM_TEMPERATURE_TO_EXIST);
+        assert!(sim.grid[burning.position].air.get_moles(&gases::CO2).unwrap() > 0.0);
+        // their air already got processed this cycle, so they don't sit the next one out
+        assert_eq!(sim.linda().state(&sim.grid, burning.position).unwrap().current_cycle, report.cycle);
+        assert!(!sim.hotspots().get(&sim.grid, burning.position).unwrap().just_spawned());
+    }
+}
+
+#[test]
+fn spread_fires_keep_burning() {
+    let mut sim = Simulation::new(TurfGrid::new(5, 5, 1, Turf::floor(plasma_air(20.0))));
+    sim.hotspot_expose(Position::new(2, 2, 0), 1000.0, 5.0);
+
+    let report = (0..10).map(|_| sim.tick()).find(|report| report.fires.ignited > 0).unwrap();
+    let spread = report.fires.burning.iter().map(|burning| burning.position).filter(|pos| *pos != Position::new(2, 2, 0)).collect::<Vec<_>>();
+    let next = sim.tick();
+    assert_eq!(next.fires.extinguished, 0);
+    for pos in spread {
+        assert!(sim.hotspots().is_burning(&sim.grid, pos));
+    }
+}
+
+#[test]
+fn walls_stop_the_spread() {
+    let grid = TurfGrid::from_fn(5, 5, 1, |pos| if pos.x == 3 { Turf::wall() } else { Turf::floor(plasma_air(20.0)) });
+    let mut sim = Simulation::new(grid);
+    sim.hotspot_expose(Position::new(1, 2, 0), 1000.0, 5.0);
+
+    for report in sim.run(40) {
+        assert!(report.fires.burning.iter().all(|burning| burning.position.x < 3));
+    }
+    assert_eq!(sim.grid[Position::new(4, 2, 0)].air.get_moles(&gases::CO2), None);
+}
+
+#[test]
+fn fires_burn_out() {
+    // barely enough plasma to get going
+    let mut sim = Simulation::new(TurfGrid::new(3, 3, 1, Turf::floor(plasma_air(1.0))));
+    assert!(sim.hotspot_expose(Position::new(1, 1, 0), 1000.0, 5.0));
+
+    let reports = sim.run(100);
+    assert!(reports.iter().map(|report| report.fires.extinguished).sum::<usize>() >= 1);
+    assert!(sim.hotspots().is_empty());
+    assert!(reports.last().unwrap().fires.burning.is_empty());
+}
+
+#[test]
+fn extinguishing() {
+    let mut sim = Simulation::new(TurfGrid::new(3, 3, 1, Turf::floor(plasma_air(20.0))));
+    sim.hotspot_expose(Position::new(1, 1, 0), 1000.0, 5.0);
+    assert!(sim.extinguish(Position::new(1, 1, 0)));
+    assert!(!sim.extinguish(Position::new(1, 1, 0)));
+    assert!(sim.tick().fires.burning.is_empty());
+}
+
+#[test]
+fn burns_under_every_solver() {
+    for solver in [Solver::Linda, Solver::Monstermos] {
+        let mut sim = Simulation::with_solver(TurfGrid::new(5, 5, 1, Turf::floor(plasma_air(20.0))), solver);
+        sim.hotspot_expose(Position::new(2, 2, 0), 1000.0, 5.0);
+        let reports = sim.run(10);
+        assert!(reports.iter().all(|report| !report.fires.burning.is_empty()), "{:?}", solver);
+        assert!(reports.iter().map(|report| report.fires.ignited).sum::<usize>() >= 4, "{:?}", solver);
+    }
+
+    // under ZAS, whatever the fire heats up gets spread over the whole zone, which puts it out
+    let mut sim = Simulation::with_solver(TurfGrid::new(5, 5, 1, Turf::floor(plasma_air(20.0))), Solver::Zas);
+    sim.hotspot_expose(Position::new(2, 2, 0), 1000.0, 5.0);
+    let reports = sim.run(10);
+    assert!(!reports[0].fires.burning.is_empty());
+    assert!(sim.hotspots().is_empty());
+    for (_, turf) in sim.grid.iter() {
+        assert!(turf.air.get_moles(&gases::CO2).unwrap() > 0.0);
+        assert!(turf.air.temperature > num::T20C);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::environmental::*,
+    byond::Real,
+};
+use super::fixtures::*;
+
+#[test]
+fn settled_room_stays_asleep() {
+    let mut sim = Simulation::new(TurfGrid::new(4, 4, 1, Turf::floor(station_air())));
+    sim.activate_all();
+    let before = sim.grid.clone();
+
+    let report = sim.tick();
+    assert_eq!(report.linda.processed, 16);
+    assert_eq!(report.active_turfs, 0);
+    assert_eq!(sim.grid, before);
+}
+
+#[test]
+fn equalises_and_conserves() {
+    let mut grid = TurfGrid::new(3, 3, 1, Turf::floor(vacuum()));
+    grid[Position::new(1, 1, 0)].air = GasMixture::from_vecs(vec![&gases::PLASMA], vec![900.0], num::T20C, num::CELL_VOLUME).unwrap();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(1, 1, 0));
+
+    let first = sim.tick();
+    // everything the middle shares with gets processed the same cycle, and so on outwards
+    assert_eq!(first.linda.processed, 9);
+    assert_eq!(first.active_turfs, 9);
+    assert!(sim.grid[Position::new(0, 0, 0)].air.total_moles() > 0.0);
+
+    sim.run(500);
+    assert!(sim.active_turfs().is_empty());
+    assert!((sim.grid.total_moles() - 900.0).abs() < 0.001);
+    for (_, turf) in sim.grid.iter() {
+        assert!((turf.air.total_moles() - 100.0).abs() < 1.0);
+    }
+}
+
+#[test]
+fn woken_turfs_get_processed_the_same_cycle() {
+    let mut grid = TurfGrid::new(10, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = station_air();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+
+    let report = sim.tick();
+    assert!(report.linda.processed > 1);
+    assert_eq!(sim.linda().state(&sim.grid, Position::new(1, 0, 0)).unwrap().current_cycle, 1);
+}
+
+#[test]
+fn walls_block_air() {
+    // a wall down the middle of a 3x1 corridor
+    let mut grid = TurfGrid::new(3, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = station_air();
+    grid.replace(Position::new(1, 0, 0), Turf::wall()).unwrap();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+    sim.run(100);
+
+    assert!(sim.grid[Position::new(2, 0, 0)].air.is_empty());
+    assert!(!sim.activate(Position::new(1, 0, 0)));
+
+    // knock the wall down and it all flows through
+    sim.replace(Position::new(1, 0, 0), Turf::floor(vacuum())).unwrap();
+    assert!(sim.is_active(Position::new(0, 0, 0)));
+    sim.run(500);
+    assert!(sim.grid[Position::new(2, 0, 0)].air.total_moles() > 30.0);
+}
+
+#[test]
+fn vents_to_space() {
+    let mut grid = TurfGrid::from_fn(4, 1, 1, |pos| if pos.x == 3 { Turf::space() } else { Turf::floor(station_air()) });
+    grid[Position::new(0, 0, 0)].air.temperature = 500.0;
+    let mut sim = Simulation::new(grid);
+    sim.activate_all();
+    assert!(!sim.is_active(Position::new(3, 0, 0)));
+
+    sim.run(1000);
+    assert!(sim.grid.total_moles() < num::MOLES_CELLSTANDARD);
+    assert!(sim.grid[Position::new(3, 0, 0)].air.is_empty()); // space never fills up
+    assert!(sim.active_turfs().is_empty());
+}
+
+#[test]
+fn planetary_turfs_recover() {
+    let mut grid = TurfGrid::new(2, 1, 1, Turf::planetary(station_air()));
+    grid[Position::new(0, 0, 0)].air = vacuum();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+    sim.run(500);
+
+    for (_, turf) in sim.grid.iter() {
+        assert!((turf.air.total_moles() - num::MOLES_CELLSTANDARD).abs() < 1.0);
+    }
+}
+
+#[test]
+fn heat_spreads() {
+    let mut grid = TurfGrid::new(2, 1, 1, Turf::floor(station_air()));
+    grid[Position::new(0, 0, 0)].air.temperature = 1000.0;
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+    sim.run(500);
+
+    let (west, east) = (&sim.grid[Position::new(0, 0, 0)].air, &sim.grid[Position::new(1, 0, 0)].air);
+    assert!((west.temperature - east.temperature).abs() <= num::MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND);
+    assert!(east.temperature > 600.0);
+}
+
+#[test]
+fn sharing_turfs_form_a_group() {
+    let mut grid = TurfGrid::new(5, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = station_air();
+    grid[Position::new(4, 0, 0)].air = station_air();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+    sim.activate(Position::new(4, 0, 0));
+
+    sim.tick();
+    // two groups, one at each end
+    assert_eq!(sim.linda().excited_groups().count(), 2);
+    let west = sim.linda().group_of(&sim.grid, Position::new(0, 0, 0)).unwrap();
+    assert_eq!(west.turfs(&sim.grid), vec![Position::new(0, 0, 0), Position::new(1, 0, 0)]);
+    assert_eq!(west.breakdown_cooldown, 1);
+
+    // they meet in the middle and merge
+    sim.tick();
+    sim.tick();
+    assert_eq!(sim.linda().excited_groups().count(), 1);
+    assert_eq!(sim.linda().group_of(&sim.grid, Position::new(2, 0, 0)).unwrap().len(), 5);
+}
+
+#[test]
+fn breakdown_averages_the_group() {
+    let mut grid = TurfGrid::new(6, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![600.0], 500.0, num::CELL_VOLUME).unwrap();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+
+    // big shares keep resetting the cooldown, so it only breaks down once things slow down
+    let broke_down = (0..500).any(|_| sim.tick().linda.breakdowns > 0);
+    assert!(broke_down);
+
+    let group = sim.linda().group_of(&sim.grid, Position::new(0, 0, 0)).unwrap();
+    assert_eq!(group.breakdown_cooldown, 0);
+    let turfs = group.turfs(&sim.grid);
+    let first = &sim.grid[turfs[0]].air;
+    for pos in &turfs {
+        assert_eq!(sim.grid[*pos].air, *first);
+    }
+    assert!((first.total_moles() * turfs.len() as Real - 600.0).abs() < 0.001);
+}
+
+#[test]
+fn dismantles_once_settled() {
+    let mut grid = TurfGrid::new(4, 4, 1, Turf::floor(station_air()));
+    grid[Position::new(2, 2, 0)].air.temperature = 400.0;
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(2, 2, 0));
+
+    let reports = sim.run(500);
+    assert!(reports.iter().any(|report| report.linda.dismantles > 0));
+    assert!(sim.active_turfs().is_empty());
+    assert_eq!(sim.linda().excited_groups().count(), 0);
+    for (pos, _) in sim.grid.iter() {
+        assert_eq!(sim.linda().state(&sim.grid, pos).unwrap().excited_group, None);
+    }
+}
+
+#[test]
+fn activating_breaks_up_the_group() {
+    let mut grid = TurfGrid::new(3, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = station_air();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+    sim.tick();
+    assert!(sim.linda().group_of(&sim.grid, Position::new(1, 0, 0)).is_some());
+
+    sim.activate(Position::new(1, 0, 0));
+    assert!(sim.linda().group_of(&sim.grid, Position::new(0, 0, 0)).is_none());
+    assert!(sim.is_active(Position::new(0, 0, 0)));
+}
@@ -0,0 +1,107 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::environmental::*,
+};
+use super::fixtures::*;
+
+/// A 6x6 room with 1800 mol of nitrogen in one corner and nothing anywhere else.
+fn canister_room<'a>() -> TurfGrid<'a> {
+    let mut grid = TurfGrid::new(6, 6, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![1800.0], num::T20C, num::CELL_VOLUME).unwrap();
+    grid
+}
+
+#[test]
+fn equalizes_in_one_tick() {
+    let mut sim = Simulation::with_solver(canister_room(), Solver::Monstermos);
+    sim.activate(Position::new(0, 0, 0));
+
+    let report = sim.tick();
+    assert_eq!(report.equalization.regions, 1);
+    assert_eq!(report.equalization.equalized, 36);
+    assert_eq!(report.equalization.decompressions, 0);
+    for (_, turf) in sim.grid.iter() {
+        assert!((turf.air.total_moles() - 50.0).abs() < 0.01);
+    }
+    assert!((sim.grid.total_moles() - 1800.0).abs() < 0.001);
+
+    // once it's even, there's nothing left for either of them to do
+    sim.run(100);
+    assert!(sim.active_turfs().is_empty());
+}
+
+#[test]
+fn faster_than_linda() {
+    let far_corner = Position::new(5, 5, 0);
+    let mut linda = Simulation::new(canister_room());
+    let mut monstermos = Simulation::with_solver(canister_room(), Solver::Monstermos);
+    for sim in [&mut linda, &mut monstermos] {
+        sim.activate(Position::new(0, 0, 0));
+        sim.tick();
+    }
+    assert_eq!(linda.solver(), Solver::Linda);
+    assert_eq!(linda.grid[far_corner].air.total_moles(), 0.0);
+    assert!(monstermos.grid[far_corner].air.total_moles() > 49.0);
+}
+
+#[test]
+fn walls_keep_regions_apart() {
+    // two 3x3 rooms side by side, with a wall between them
+    let mut grid = TurfGrid::from_fn(7, 3, 1, |pos| if pos.x == 3 { Turf::wall() } else { Turf::floor(vacuum()) });
+    grid[Position::new(0, 1, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![900.0], num::T20C, num::CELL_VOLUME).unwrap();
+    let mut sim = Simulation::with_solver(grid, Solver::Monstermos);
+    sim.activate(Position::new(0, 1, 0));
+
+    let report = sim.tick();
+    assert_eq!(report.equalization.equalized, 9);
+    assert!((sim.grid[Position::new(2, 2, 0)].air.total_moles() - 100.0).abs() < 0.01);
+    assert_eq!(sim.grid[Position::new(4, 1, 0)].air.total_moles(), 0.0);
+}
+
+#[test]
+fn moves_heat_with_the_gas() {
+    let mut grid = TurfGrid::new(2, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 500.0, num::CELL_VOLUME).unwrap();
+    grid[Position::new(1, 0, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![50.0], 200.0, num::CELL_VOLUME).unwrap();
+    let energy_before = grid.iter().map(|(_, turf)| turf.air.thermal_energy()).sum::<crate::byond::Real>();
+    let mut sim = Simulation::with_solver(grid, Solver::Monstermos);
+    sim.activate(Position::new(0, 0, 0));
+
+    let report = sim.tick();
+    assert!((report.equalization.moles_moved - 25.0).abs() < 0.01);
+    // 25 mol of hot nitrogen went east, which gets it to 300 K before LINDA shares any heat
+    assert!(sim.grid[Position::new(1, 0, 0)].air.temperature > 299.0);
+    let energy_after = sim.grid.iter().map(|(_, turf)| turf.air.thermal_energy()).sum::<crate::byond::Real>();
+    assert!((energy_after - energy_before).abs() / energy_before < 0.01);
+}
+
+#[test]
+fn breach_empties_the_room() {
+    // a 4x4 room of station air, with space along the east side behind a window
+    let mut grid = TurfGrid::from_fn(5, 4, 1, |pos| if pos.x == 4 { Turf::space() } else { Turf::floor(station_air()) });
+    for y in 0..4 {
+        grid.set_blocked(Position::new(3, y, 0), Direction::East, true).unwrap();
+    }
+    let room_moles = grid.total_moles();
+    let mut sim = Simulation::with_solver(grid, Solver::Monstermos);
+
+    // knock out one window pane
+    sim.grid.set_blocked(Position::new(3, 2, 0), Direction::East, false).unwrap();
+    sim.activate(Position::new(3, 2, 0));
+    let report = sim.tick();
+    assert_eq!(report.equalization.decompressions, 1);
+    assert_eq!(report.equalization.decompressed, 16);
+    assert!((report.equalization.moles_lost - room_moles).abs() < 0.01);
+    assert_eq!(sim.grid.total_moles(), 0.0);
+
+    // LINDA alone only gets a fraction of that out in the same time
+    let mut grid = TurfGrid::from_fn(5, 4, 1, |pos| if pos.x == 4 { Turf::space() } else { Turf::floor(station_air()) });
+    for y in [0, 1, 3] {
+        grid.set_blocked(Position::new(3, y, 0), Direction::East, true).unwrap();
+    }
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(3, 2, 0));
+    sim.tick();
+    assert!(sim.grid.total_moles() > room_moles * 0.9);
+}
@@ -0,0 +1,123 @@
+use crate::{
+    constants::*,
+    atmospherics::environmental::*,
+};
+use super::fixtures::*;
+
+/// Two 3x3 decks, linked. The upper one is pressurized, with a hole at (1, 1); the lower one's
+/// a vacuum.
+fn two_decks<'a>() -> TurfGrid<'a> {
+    let mut grid = TurfGrid::from_fn(3, 3, 2, |pos| match (pos.x, pos.y, pos.z) {
+        (1, 1, 1) => Turf::open_space(station_air()),
+        (_, _, 1) => Turf::floor(station_air()),
+        _ => Turf::floor(vacuum())
+    });
+    grid.set_z_linked(0, true).unwrap();
+    grid
+}
+
+#[test]
+fn open_space_connects_downwards() {
+    let grid = two_decks();
+    let (hole, below) = (Position::new(1, 1, 1), Position::new(1, 1, 0));
+    assert_eq!(grid.neighbours(below).len(), 5);
+    assert!(grid.adjacent(below).contains(&(Direction::Up, hole)));
+    assert!(grid.adjacent(hole).contains(&(Direction::Down, below)));
+    // floors above floors are ceilings
+    assert!(grid.adjacent(Position::new(0, 0, 0)).iter().all(|(direction, _)| !direction.is_vertical()));
+    assert!(grid.adjacent(Position::new(0, 0, 1)).iter().all(|(direction, _)| !direction.is_vertical()));
+}
+
+#[test]
+fn z_links_are_configurable() {
+    let mut grid = two_decks();
+    let (hole, below) = (Position::new(1, 1, 1), Position::new(1, 1, 0));
+    assert!(grid.is_z_linked(0));
+    assert!(!grid.is_z_linked(1));
+
+    let revision = grid.revision();
+    grid.set_z_linked(0, false).unwrap();
+    assert!(grid.revision() > revision);
+    assert_eq!(grid.step(below, Direction::Up), None);
+    assert!(grid.adjacent(hole).iter().all(|(direction, _)| !direction.is_vertical()));
+
+    // a hatch over the hole
+    grid.set_z_linked(0, true).unwrap();
+    grid.set_blocked(hole, Direction::Down, true).unwrap();
+    assert_eq!(grid.step(below, Direction::Up), Some(hole));
+    assert!(!grid.adjacent(below).contains(&(Direction::Up, hole)));
+
+    assert!(grid.set_z_linked(1, true).is_err());
+    assert!(!grid.is_z_linked(5));
+}
+
+#[test]
+fn air_falls_through_open_space() {
+    for solver in [Solver::Linda, Solver::Monstermos, Solver::Zas] {
+        let mut sim = Simulation::with_solver(two_decks(), solver);
+        let moles = sim.grid.total_moles();
+        sim.activate(Position::new(1, 1, 1));
+        sim.run(30);
+
+        for x in 0..3 {
+            for y in 0..3 {
+                assert!(sim.grid[Position::new(x, y, 0)].air.total_moles() > 0.0, "{:?} left ({}, {}, 0) empty", solver, x, y);
+            }
+        }
+        assert!((sim.grid.total_moles() - moles).abs() < 0.01 * moles, "{:?} lost air", solver);
+    }
+}
+
+#[test]
+fn unlinked_decks_stay_apart() {
+    for solver in [Solver::Linda, Solver::Monstermos, Solver::Zas] {
+        let mut grid = two_decks();
+        grid.set_z_linked(0, false).unwrap();
+        let mut sim = Simulation::with_solver(grid, solver);
+        sim.activate_all();
+        sim.run(10);
+
+        assert!(sim.grid.positions().filter(|pos| pos.z == 0).all(|pos| sim.grid[pos].air.total_moles() == 0.0));
+    }
+}
+
+#[test]
+fn zas_zones_span_decks() {
+    let sim = Simulation::with_solver(two_decks(), Solver::Zas);
+    let zas = sim.zas().unwrap();
+    let upper = zas.zone_of(&sim.grid, Position::new(0, 0, 1)).unwrap();
+    assert_eq!(zas.zone_of(&sim.grid, Position::new(2, 2, 0)), Some(upper));
+    assert_eq!(zas.zones().count(), 1);
+}
+
+#[test]
+fn fire_spreads_through_open_space() {
+    // lit on the upper deck, next to the hole
+    let mut grid = TurfGrid::from_fn(5, 5, 2, |pos| match (pos.x, pos.y, pos.z) {
+        (2, 2, 1) => Turf::open_space(plasma_air(20.0)),
+        _ => Turf::floor(plasma_air(20.0))
+    });
+    grid.set_z_linked(0, true).unwrap();
+    let mut sim = Simulation::new(grid);
+    sim.hotspot_expose(Position::new(1, 2, 1), 1000.0, 5.0);
+
+    let reports = sim.run(30);
+    assert!(reports.iter().any(|report| report.fires.burning.iter().any(|burning| burning.position.z == 0)));
+    assert!(sim.grid[Position::new(2, 2, 0)].air.get_moles(&gases::CO2).unwrap() > 0.0);
+}
+
+#[test]
+fn heat_doesnt_conduct_between_decks() {
+    // a hot wall under a cold one
+    let mut grid = TurfGrid::from_fn(1, 1, 2, |_| {
+        let mut wall = Turf::wall();
+        wall.thermal_conductivity = 0.05;
+        wall
+    });
+    grid.set_z_linked(0, true).unwrap();
+    grid[Position::new(0, 0, 0)].temperature = 1000.0;
+    let mut sim = Simulation::new(grid);
+    sim.run(5);
+
+    assert_eq!(sim.grid[Position::new(0, 0, 1)].temperature, num::T20C);
+}
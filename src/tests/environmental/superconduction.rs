@@ -0,0 +1,155 @@
+use crate::{
+    constants::*,
+    atmospherics::environmental::*,
+};
+use super::fixtures::*;
+
+fn conductive_wall<'a>() -> Turf<'a> {
+    let mut wall = Turf::wall();
+    wall.thermal_conductivity = 0.05;
+    wall
+}
+
+/// Two 3x3 rooms with `wall` between them, the west one at 1500 K.
+fn engine_room<'a>(wall: Turf<'a>) -> TurfGrid<'a> {
+    TurfGrid::from_fn(7, 3, 1, |pos| match pos.x {
+        0..=2 => Turf::floor(station_air_at(1500.0)),
+        3 => wall.clone(),
+        _ => Turf::floor(station_air_at(num::T20C))
+    })
+}
+
+#[test]
+fn heats_through_walls() {
+    let next_door = Position::new(4, 1, 0);
+    let mut sim = Simulation::new(engine_room(conductive_wall()));
+    sim.activate_all();
+    let reports = sim.run(200);
+
+    assert!(reports.iter().any(|report| report.superconduction.conducted > 0));
+    assert!(reports.iter().any(|report| report.superconduction.touched.contains(&next_door)));
+    assert!(sim.grid[Position::new(3, 1, 0)].temperature > num::MINIMUM_TEMPERATURE_FOR_SUPERCONDUCTION - 1.0);
+    assert!(sim.grid[next_door].air.temperature > num::T20C + 4.0);
+
+    // and once everything's cooled down, it all stops
+    assert!(sim.superconductivity().is_empty());
+    assert!(sim.active_turfs().is_empty());
+}
+
+#[test]
+fn plain_walls_insulate() {
+    let mut sim = Simulation::new(engine_room(Turf::wall()));
+    sim.activate_all();
+    sim.run(200);
+    assert_eq!(sim.grid[Position::new(3, 1, 0)].temperature, num::T20C);
+    for x in 4..7 {
+        assert_eq!(sim.grid[Position::new(x, 1, 0)].air.temperature, num::T20C);
+    }
+}
+
+#[test]
+fn heats_through_windows() {
+    // a window between (0, 0) and (1, 0); air can't get through, but heat can
+    let grid = TurfGrid::from_fn(2, 1, 1, |pos| match pos.x {
+        0 => Turf::floor(station_air_at(1500.0)).blocked(Direction::East),
+        _ => Turf::floor(station_air_at(num::T20C)).blocked(Direction::West)
+    });
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(0, 0, 0));
+    let report = sim.tick();
+    assert!(sim.superconductivity().is_superconducting(&sim.grid, Position::new(0, 0, 0)));
+    assert_eq!(report.superconduction.touched, vec![Position::new(1, 0, 0), Position::new(0, 0, 0)]);
+
+    let before = sim.grid.total_moles();
+    sim.run(20);
+    assert!(sim.grid[Position::new(1, 0, 0)].air.temperature > 400.0);
+    assert_eq!(sim.grid.total_moles(), before); // no air got through
+}
+
+#[test]
+fn walls_lose_heat_to_space() {
+    // two hot walls, one with space next to it and one with an insulating wall
+    let mut grid = TurfGrid::from_fn(2, 2, 1, |pos| match (pos.x, pos.y) {
+        (0, _) => conductive_wall(),
+        (1, 0) => Turf::space(),
+        _ => Turf::wall()
+    });
+    for y in 0..2 {
+        grid[Position::new(0, y, 0)].temperature = 1000.0;
+    }
+    let mut superconductivity = superconduction::Superconductivity::new(&grid);
+    for y in 0..2 {
+        assert!(superconductivity.consider(&grid, Position::new(0, y, 0), true));
+    }
+    assert!(!superconductivity.consider(&grid, Position::new(1, 0, 0), true)); // space never does
+
+    superconductivity.process(&mut grid, 1);
+    let (spaced, insulated) = (grid[Position::new(0, 0, 0)].temperature, grid[Position::new(0, 1, 0)].temperature);
+    assert!(spaced < insulated);
+    assert!(insulated < 1000.0); // radiating to space happens either way
+    assert_eq!(grid[Position::new(1, 0, 0)].temperature, num::TCMB);
+}
+
+#[test]
+fn walls_conduct_at_their_own_conductivity() {
+    // a hot wall that barely conducts next to a cold one that conducts well
+    let mut grid = TurfGrid::from_fn(2, 1, 1, |pos| match pos.x {
+        0 => conductive_wall(),
+        _ => { let mut wall = Turf::wall(); wall.thermal_conductivity = 0.5; wall }
+    });
+    grid[Position::new(0, 0, 0)].temperature = 1000.0;
+    let mut superconductivity = superconduction::Superconductivity::new(&grid);
+    assert!(superconductivity.consider(&grid, Position::new(0, 0, 0), true));
+
+    superconductivity.process(&mut grid, 1);
+    // same heat capacities, so the cold wall gets half of the hot wall's conductivity's worth
+    let expected = num::T20C + 0.05 * (1000.0 - num::T20C) / 2.0;
+    assert!((grid[Position::new(1, 0, 0)].temperature - expected).abs() < 1e-3);
+}
+
+#[test]
+fn air_conducts_into_walls_at_the_floors_conductivity() {
+    // hot air on a floor next to a wall that conducts well
+    let mut grid = TurfGrid::from_fn(2, 1, 1, |pos| match pos.x {
+        0 => Turf::floor(station_air_at(1000.0)),
+        _ => { let mut wall = Turf::wall(); wall.thermal_conductivity = 0.5; wall }
+    });
+    let air_heat_capacity = grid[Position::new(0, 0, 0)].air.heat_capacity();
+    let (floor_conductivity, wall_heat_capacity) = (grid[Position::new(0, 0, 0)].thermal_conductivity, grid[Position::new(1, 0, 0)].heat_capacity);
+    let mut superconductivity = superconduction::Superconductivity::new(&grid);
+    assert!(superconductivity.consider(&grid, Position::new(0, 0, 0), true));
+
+    superconductivity.process(&mut grid, 1);
+    let heat = floor_conductivity * (1000.0 - num::T20C) * (air_heat_capacity * wall_heat_capacity / (air_heat_capacity + wall_heat_capacity));
+    let expected = num::T20C + heat / wall_heat_capacity;
+    assert!((grid[Position::new(1, 0, 0)].temperature - expected).abs() < 1e-3);
+}
+
+#[test]
+fn heats_through_walls_under_every_solver() {
+    for solver in [Solver::Monstermos, Solver::Zas] {
+        let mut sim = Simulation::with_solver(engine_room(conductive_wall()), solver);
+        sim.activate_all();
+        sim.run(200);
+        assert!(sim.grid[Position::new(4, 1, 0)].air.temperature > num::T20C, "{:?}", solver);
+    }
+}
+
+#[test]
+fn hot_rooms_still_break_down() {
+    // superconduction wakes hot air up every cycle, but that mustn't break up its group
+    let grid = TurfGrid::from_fn(6, 1, 1, |pos| {
+        let mut air = station_air_at(800.0);
+        if pos.x < 3 {
+            air.merge(station_air_at(800.0));
+        }
+        Turf::floor(air)
+    });
+    let mut sim = Simulation::new(grid);
+    sim.activate_all();
+
+    let first = sim.tick();
+    assert!(sim.linda().excited_groups().count() > 0);
+    let breakdowns = first.linda.breakdowns + sim.run(30).iter().map(|report| report.linda.breakdowns).sum::<usize>();
+    assert!(breakdowns > 0);
+}
@@ -0,0 +1,167 @@
+use crate::{
+    constants::*,
+    atmospherics::environmental::*,
+    byond::Real,
+};
+use super::fixtures::*;
+
+/// A 12x1 corridor full of station air, with space at the east end.
+fn breached_corridor<'a>() -> TurfGrid<'a> {
+    TurfGrid::from_fn(12, 1, 1, |pos| if pos.x == 11 { Turf::space() } else { Turf::floor(station_air()) })
+}
+
+#[test]
+fn linda_blows_from_high_to_low() {
+    let mut grid = TurfGrid::new(3, 1, 1, Turf::floor(vacuum()));
+    grid[Position::new(1, 0, 0)].air = station_air();
+    let mut sim = Simulation::new(grid);
+    sim.activate(Position::new(1, 0, 0));
+
+    let report = sim.tick();
+    // the middle turf shared both ways, and only the bigger push counts
+    assert_eq!(report.wind.len(), 1);
+    let delta = report.wind[0];
+    assert_eq!(delta.position, Position::new(1, 0, 0));
+    assert_eq!(delta.direction, Direction::East);
+    assert!(delta.difference > 0.0);
+    assert_eq!(delta.target, None);
+    assert_eq!(sim.wind().get(&sim.grid, Position::new(1, 0, 0)), Some(&delta));
+    assert_eq!(delta.vector(), (delta.difference, 0.0, 0.0));
+
+    // it's forgotten next tick once everything's calmed down
+    sim.run(100);
+    assert!(sim.wind().is_empty());
+}
+
+#[test]
+fn decompression_sucks_towards_space() {
+    let mut sim = Simulation::with_solver(breached_corridor(), Solver::Monstermos);
+    sim.activate(Position::new(10, 0, 0));
+    let report = sim.tick();
+    assert_eq!(report.equalization.decompressions, 1);
+
+    // every turf points east at the breach, with more air rushing past the closer it is
+    let mut last = 0.0;
+    for x in 0..11 {
+        let delta = *sim.wind().get(&sim.grid, Position::new(x, 0, 0)).unwrap();
+        assert_eq!(delta.direction, Direction::East);
+        assert_eq!(delta.target, Some(Position::new(11, 0, 0)));
+        assert!(delta.difference > last);
+        last = delta.difference;
+    }
+    assert!((last - report.equalization.moles_lost).abs() < 0.001);
+}
+
+#[test]
+fn what_gets_thrown() {
+    let mut sim = Simulation::with_solver(breached_corridor(), Solver::Monstermos);
+    sim.activate(Position::new(10, 0, 0));
+    sim.tick();
+    let next_to_breach = Position::new(10, 0, 0);
+
+    // a crowbar goes flying into space
+    let crowbar = sim.experience_pressure_difference(next_to_breach, &Movable::default()).unwrap();
+    assert_eq!(crowbar.probability, 1.0);
+    assert!(crowbar.force > num::PRESSURE_THROW_FORCE);
+    match crowbar.movement {
+        Movement::Thrown { target, landing, range, speed } => {
+            assert_eq!(target, Position::new(11, 0, 0));
+            assert_eq!(landing, Position::new(11, 0, 0)); // the edge of the grid
+            assert!(range > 1.0);
+            assert_eq!(speed, range.floor().clamp(1.0, 10.0));
+        },
+        movement => panic!("expected a throw, got {:?}", movement)
+    }
+
+    // bolted down or immovable things stay put
+    let machine = Movable { anchored: true, ..Movable::default() };
+    assert_eq!(sim.experience_pressure_difference(next_to_breach, &machine), None);
+    let statue = Movable::new(num::MOVABLE_PRESSURE_RESISTANCE, Real::INFINITY, false);
+    assert_eq!(sim.experience_pressure_difference(next_to_breach, &statue), None);
+    // and so does anything on a turf the air didn't leave
+    assert_eq!(sim.experience_pressure_difference(Position::new(11, 0, 0), &Movable::default()), None);
+}
+
+#[test]
+fn heavier_things_take_more() {
+    let grid = TurfGrid::new(3, 1, 1, Turf::floor(vacuum()));
+    let mut wind = SpaceWind::new(&grid);
+    let pos = Position::new(1, 0, 0);
+    wind.consider_pressure_difference(&grid, pos, Direction::East, 50.0);
+
+    let light = wind.experience_pressure_difference(&grid, pos, &Movable::default()).unwrap();
+    assert_eq!(light.movement, Movement::Step(Position::new(2, 0, 0)));
+    assert_eq!(light.probability, 1.0);
+
+    // harder to budge, so it's only a chance
+    let locker = Movable::new(50.0, num::MOVE_RESIST_DEFAULT, false);
+    let push = wind.experience_pressure_difference(&grid, pos, &locker).unwrap();
+    assert!(push.probability > 0.0 && push.probability < 1.0);
+    assert!(push.force < light.force);
+
+    // far too heavy for 50 kPa
+    let heavy = Movable::new(10.0, 2000.0, false);
+    assert_eq!(wind.experience_pressure_difference(&grid, pos, &heavy), None);
+    let huge = Movable::new(200.0, num::MOVE_RESIST_DEFAULT, false);
+    assert_eq!(wind.experience_pressure_difference(&grid, pos, &huge), None);
+}
+
+#[test]
+fn walls_get_in_the_way() {
+    let grid = TurfGrid::from_fn(10, 1, 1, |pos| if pos.x == 5 { Turf::wall() } else { Turf::floor(vacuum()) });
+    let mut wind = SpaceWind::new(&grid);
+    wind.consider_pressure_difference(&grid, Position::new(4, 0, 0), Direction::East, 50.0);
+    wind.consider_pressure_difference(&grid, Position::new(1, 0, 0), Direction::East, 10_000.0);
+
+    // nowhere to step
+    assert_eq!(wind.experience_pressure_difference(&grid, Position::new(4, 0, 0), &Movable::default()), None);
+    // thrown at the far edge of the map, but the wall stops it first
+    let throw = wind.experience_pressure_difference(&grid, Position::new(1, 0, 0), &Movable::default()).unwrap();
+    assert_eq!(throw.movement, Movement::Thrown {
+        target: Position::new(9, 0, 0),
+        landing: Position::new(4, 0, 0),
+        range: throw.force / 3000.0,
+        speed: 6.0
+    });
+}
+
+#[test]
+fn throws_aim_by_force() {
+    let grid = TurfGrid::new(20, 1, 1, Turf::floor(vacuum()));
+    let mut wind = SpaceWind::new(&grid);
+    let pos = Position::new(1, 0, 0);
+    // sqrt(2500) * 1000 / 5 = 10000 force, so 5 tiles out, thrown 3.33 tiles at speed 3
+    wind.consider_pressure_difference(&grid, pos, Direction::East, 2500.0);
+    let throw = wind.experience_pressure_difference(&grid, pos, &Movable::default()).unwrap();
+    assert_eq!(throw.force, 10_000.0);
+    assert_eq!(throw.movement, Movement::Thrown {
+        target: Position::new(6, 0, 0),
+        landing: Position::new(4, 0, 0),
+        range: 10_000.0 / 3000.0,
+        speed: 3.0
+    });
+
+    // a space turf behind it doesn't count
+    wind.set_target(&grid, pos, Position::new(0, 0, 0));
+    match wind.experience_pressure_difference(&grid, pos, &Movable::default()).unwrap().movement {
+        Movement::Thrown { target, .. } => assert_eq!(target, Position::new(6, 0, 0)),
+        movement => panic!("expected a throw, got {:?}", movement)
+    }
+    // but one off at an angle ahead does
+    let grid = TurfGrid::new(20, 3, 1, Turf::floor(vacuum()));
+    let mut wind = SpaceWind::new(&grid);
+    wind.consider_pressure_difference(&grid, pos, Direction::East, 2500.0);
+    wind.set_target(&grid, pos, Position::new(9, 2, 0));
+    match wind.experience_pressure_difference(&grid, pos, &Movable::default()).unwrap().movement {
+        Movement::Thrown { target, .. } => assert_eq!(target, Position::new(9, 2, 0)),
+        movement => panic!("expected a throw, got {:?}", movement)
+    }
+}
+
+#[test]
+fn no_wind_under_zas() {
+    let mut sim = Simulation::with_solver(breached_corridor(), Solver::Zas);
+    sim.activate(Position::new(0, 0, 0));
+    let reports = sim.run(10);
+    assert!(reports.iter().all(|report| report.wind.is_empty()));
+}
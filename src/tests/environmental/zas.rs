@@ -0,0 +1,133 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::environmental::*,
+};
+use super::fixtures::*;
+
+/// Two 3x3 rooms, west and east, with a one-tile doorway between them at (3, 1).
+fn two_rooms<'a>() -> TurfGrid<'a> {
+    TurfGrid::from_fn(7, 3, 1, |pos| match (pos.x, pos.y) {
+        (3, 1) => Turf::floor(vacuum()).zone_blocking(),
+        (3, _) => Turf::wall(),
+        _ => Turf::floor(vacuum())
+    })
+}
+
+#[test]
+fn zones_and_edges() {
+    let sim = Simulation::with_solver(two_rooms(), Solver::Zas);
+    let zas = sim.zas().unwrap();
+    assert_eq!(zas.zones().count(), 3);
+    assert_eq!(zas.zone_of(&sim.grid, Position::new(3, 0, 0)), None); // a wall
+    let west = zas.zone_of(&sim.grid, Position::new(0, 0, 0)).unwrap();
+    let door = zas.zone_of(&sim.grid, Position::new(3, 1, 0)).unwrap();
+    let east = zas.zone_of(&sim.grid, Position::new(6, 2, 0)).unwrap();
+    assert_eq!(zas.zone(west).unwrap().len(), 9);
+    assert_eq!(zas.zone(door).unwrap().len(), 1);
+    assert_eq!(zas.edges(), &[
+        ConnectionEdge { zone: west, target: EdgeTarget::Zone(door), connecting_tiles: 1, sleeping: false },
+        ConnectionEdge { zone: east, target: EdgeTarget::Zone(door), connecting_tiles: 1, sleeping: false }
+    ]);
+
+    // no LINDA, so the LINDA solvers don't build any zones
+    assert!(Simulation::new(two_rooms()).zas().is_none());
+}
+
+#[test]
+fn zones_share_air_instantly() {
+    let mut sim = Simulation::with_solver(TurfGrid::new(4, 4, 1, Turf::floor(vacuum())), Solver::Zas);
+    sim.run(10);
+    assert!(sim.active_turfs().is_empty());
+
+    sim.grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::PLASMA], vec![160.0], num::T20C, num::CELL_VOLUME).unwrap();
+    sim.activate(Position::new(0, 0, 0));
+    let report = sim.tick();
+    assert_eq!(report.zas.updated, 1);
+    for (_, turf) in sim.grid.iter() {
+        assert!((turf.air.total_moles() - 10.0).abs() < 0.0001);
+    }
+}
+
+#[test]
+fn edges_share_by_the_lookup_table() {
+    // a room, a doorway and another room, one tile each
+    let mut grid = TurfGrid::from_fn(3, 1, 1, |pos| if pos.x == 1 { Turf::floor(vacuum()).zone_blocking() } else { Turf::floor(vacuum()) });
+    grid[Position::new(0, 0, 0)].air = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], num::T20C, num::CELL_VOLUME).unwrap();
+    let mut sim = Simulation::with_solver(grid, Solver::Zas);
+
+    let report = sim.tick();
+    assert_eq!(report.zas.shared, 2);
+    // 30% of the way to even through one connecting tile, then the doorway does the same to the east
+    assert!((sim.grid[Position::new(0, 0, 0)].air.total_moles() - 85.0).abs() < 0.0001);
+    assert!((sim.grid[Position::new(1, 0, 0)].air.total_moles() - 12.75).abs() < 0.0001);
+    assert!((sim.grid[Position::new(2, 0, 0)].air.total_moles() - 2.25).abs() < 0.0001);
+}
+
+#[test]
+fn rooms_even_out_and_sleep() {
+    let mut grid = two_rooms();
+    for y in 0..3 {
+        for x in 0..3 {
+            grid[Position::new(x, y, 0)].air = station_air();
+        }
+    }
+    let total = grid.total_moles();
+    let mut sim = Simulation::with_solver(grid, Solver::Zas);
+    let reports = sim.run(500);
+    assert!(reports.len() < 500);
+    assert!(sim.active_turfs().is_empty());
+    assert!(sim.zas().unwrap().edges().iter().all(|edge| edge.sleeping));
+
+    assert!((sim.grid.total_moles() - total).abs() < 0.01);
+    // each edge only sleeps once its two sides are about the same, so the rooms end up close, not equal
+    let (west, east) = (&sim.grid[Position::new(0, 0, 0)].air, &sim.grid[Position::new(6, 0, 0)].air);
+    for gas in [&gases::O2, &gases::N2] {
+        assert!((west.get_moles(gas).unwrap() - east.get_moles(gas).unwrap()).abs() < num::MINIMUM_AIR_TO_SUSPEND);
+    }
+}
+
+#[test]
+fn leaks_into_space() {
+    // a 3x3 room with a hole in the east wall
+    let grid = TurfGrid::from_fn(4, 3, 1, |pos| match (pos.x, pos.y) {
+        (3, 1) => Turf::space(),
+        (3, _) => Turf::wall(),
+        _ => Turf::floor(station_air())
+    });
+    let total = grid.total_moles();
+    let mut sim = Simulation::with_solver(grid, Solver::Zas);
+    assert_eq!(sim.zas().unwrap().edges()[0].target, EdgeTarget::Space);
+
+    sim.tick();
+    let after_one = sim.grid.total_moles();
+    assert!(after_one < total && after_one > total * 0.5);
+
+    // it keeps leaking until there's too little left to bother with, then gets sucked out
+    let reports = sim.run(500);
+    assert!(reports.len() < 500);
+    assert_eq!(sim.grid.total_moles(), 0.0);
+}
+
+#[test]
+fn rebuilds_when_the_layout_changes() {
+    let mut grid = two_rooms();
+    grid[Position::new(0, 1, 0)].air = station_air();
+    let mut sim = Simulation::with_solver(grid, Solver::Zas);
+    sim.run(500);
+
+    // close the door
+    sim.replace(Position::new(3, 1, 0), Turf::wall()).unwrap();
+    assert!(sim.zas().unwrap().is_stale(&sim.grid));
+    let report = sim.tick();
+    assert!(report.zas.rebuilt);
+    assert_eq!(report.zas.zones, 2);
+    assert_eq!(report.zas.edges, 0);
+
+    // gas dumped in the west room stays there
+    sim.grid[Position::new(0, 0, 0)].air.merge(GasMixture::from_vecs(vec![&gases::CO2], vec![90.0], num::T20C, num::CELL_VOLUME).unwrap());
+    sim.activate(Position::new(0, 0, 0));
+    sim.run(10);
+    assert!((sim.grid[Position::new(2, 2, 0)].air.get_moles(&gases::CO2).unwrap() - 10.0).abs() < 0.0001);
+    assert_eq!(sim.grid[Position::new(4, 0, 0)].air.get_moles(&gases::CO2), None);
+}
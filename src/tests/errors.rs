@@ -0,0 +1,87 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    errors::AtmosError,
+    byond::Real,
+};
+use std::collections::HashMap;
+
+fn assert_send_sync_static<T: Send + Sync + 'static>() {}
+
+#[test]
+fn send_sync_static() {
+    assert_send_sync_static::<AtmosError>();
+
+    // and it boxes into a std error without a lifetime hanging off of it
+    let error: Box<dyn std::error::Error + Send + Sync> = Box::new(AtmosError::UnknownGasId { id: "phlogiston".to_string() });
+    assert!(error.to_string().contains("phlogiston"));
+}
+
+#[test]
+fn outlives_mixture() {
+    let error = {
+        let mut mix = GasMixture::from_empty(293.15, 70);
+        mix.change_moles(&gases::O2, 10.0).unwrap_err()
+    };
+    assert_eq!(error, AtmosError::GasNotFound { gas: "o2".to_string() });
+}
+
+#[test]
+fn vector_length_mismatch() {
+    let error = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![1.0], 293.15, 70).unwrap_err();
+    assert_eq!(error, AtmosError::VectorLengthMismatch { gas_length: 2, mole_length: 1 });
+}
+
+#[test]
+fn invalid_temperature() {
+    for temperature in [Real::NAN, Real::INFINITY, -1.0].iter() {
+        match GasMixture::from_vecs(vec![&gases::O2], vec![1.0], *temperature, 70) {
+            Err(AtmosError::InvalidTemperature { .. }) => {},
+            other => panic!("expected InvalidTemperature, got {:?}", other)
+        }
+    }
+}
+
+#[test]
+fn invalid_volume() {
+    assert_eq!(GasMixture::from_vecs(vec![&gases::O2], vec![1.0], 293.15, 0).unwrap_err(),
+               AtmosError::InvalidVolume { volume: 0.0 });
+
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![1.0], 293.15, 70).unwrap();
+    let mut other = mix.clone();
+    assert_eq!(mix.transfer_volume_to(&mut other, -5.0).unwrap_err(), AtmosError::InvalidVolume { volume: -5.0 });
+}
+
+#[test]
+fn negative_moles() {
+    assert_eq!(GasMixture::from_vecs(vec![&gases::O2], vec![-1.0], 293.15, 70).unwrap_err(),
+               AtmosError::NegativeMoles { gas: "o2".to_string(), moles: -1.0 });
+
+    let mut mix = GasMixture::new(HashMap::new(), 293.15, 70);
+    mix.assert_gas(&gases::N2);
+    assert!(mix.change_moles(&gases::N2, -0.1).is_err());
+    assert!(mix.change_moles(&gases::N2, Real::NAN).is_err());
+}
+
+#[test]
+fn unknown_gas_id() {
+    assert_eq!(gases::from_id("phlogiston").unwrap_err(), AtmosError::UnknownGasId { id: "phlogiston".to_string() });
+    assert_eq!(gases::from_id("o2").unwrap(), &gases::O2);
+}
+
+#[test]
+fn parse() {
+    let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::PLASMA], vec![21.5, 3.25], 500.0, 70).unwrap();
+    assert_eq!(mix.to_string().parse::<GasMixture>().unwrap(), mix);
+
+    for bad in ["", "t:abc,v:70,g:", "t:293.15,g:o2:1,", "t:293.15,v:70,g:o2=1,", "t:293.15,v:70,g:o2:lots,"].iter() {
+        match bad.parse::<GasMixture>() {
+            Err(AtmosError::Parse { .. }) => {},
+            other => panic!("expected Parse error for '{}', got {:?}", bad, other)
+        }
+    }
+    match "t:293.15,v:70,g:phlogiston:1,".parse::<GasMixture>() {
+        Err(AtmosError::UnknownGasId { .. }) => {},
+        other => panic!("expected UnknownGasId, got {:?}", other)
+    }
+}
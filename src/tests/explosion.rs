@@ -0,0 +1,44 @@
+use crate::{
+    constants::*,
+    atmospherics::explosion::*,
+};
+
+#[test]
+fn tank_ranges() {
+    // range 4.9 -> round(1.225), round(2.45), round(4.9), round(7.35)
+    let ranges = ExplosionRanges::from_range(4.9);
+    assert_eq!(ranges, ExplosionRanges { devastation: 1.0, heavy_impact: 2.0, light_impact: 4.0, flash: 7.0 });
+    assert_eq!(ranges.max_distance(), 7.0);
+}
+
+#[test]
+fn default_cap() {
+    let cap = BombCap::default();
+    assert_eq!(cap, BombCap { devastation: 3.0, heavy_impact: 7.0, light_impact: 14.0, flash: 14.0 });
+}
+
+#[test]
+fn capped() {
+    let explosion = Explosion::from_range(100.0, &BombCap::default());
+    assert_eq!(explosion.theoretical, ExplosionRanges { devastation: 25.0, heavy_impact: 50.0, light_impact: 100.0, flash: 150.0 });
+    assert_eq!(explosion.actual, ExplosionRanges { devastation: 3.0, heavy_impact: 7.0, light_impact: 14.0, flash: 14.0 });
+    assert!(explosion.is_capped());
+    assert_eq!(format!("{}", explosion), "(3, 7, 14, 14) (theoretical (25, 50, 100, 150))");
+
+    let uncapped = Explosion::from_range(100.0, &BombCap::uncapped());
+    assert_eq!(uncapped.actual, uncapped.theoretical);
+    assert!(!uncapped.is_capped());
+}
+
+#[test]
+fn scaled_cap() {
+    let explosion = Explosion::from_range(40.0, &BombCap::default().scaled(2.0));
+    assert_eq!(explosion.actual, ExplosionRanges { devastation: 6.0, heavy_impact: 14.0, light_impact: 28.0, flash: 28.0 });
+}
+
+#[test]
+fn from_pressure() {
+    let explosion = Explosion::from_pressure(num::TANK_FRAGMENT_PRESSURE + num::TANK_FRAGMENT_SCALE * 8.0, &BombCap::default());
+    assert!((explosion.range - 8.0).abs() < 1e-3);
+    assert_eq!(explosion.actual.light_impact, 8.0);
+}
@@ -21,16 +21,16 @@ fn gas_exists() {
 fn is_empty() {
     let mix = GasMixture::from_empty(100.0, 100); // well, duh, now its empty
 
-    assert_eq!(mix.is_empty(), true);
+    assert!(mix.is_empty());
 }
 
 #[test]
 fn assert_gas() {
     // Mix contains just O2, at 69.42 mol
     let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![69.42], 273.15, 70).unwrap();
-    assert_eq!(mix.gas_exists(&gases::N2), false); // Doesn't exist in mix  
+    assert!(!mix.gas_exists(&gases::N2)); // Doesn't exist in mix  
     mix.assert_gas(&gases::N2);
-    assert_eq!(mix.gas_exists(&gases::N2), false); // Even though we asserted, its still only at 0 mols so gas_exists fails
+    assert!(!mix.gas_exists(&gases::N2)); // Even though we asserted, its still only at 0 mols so gas_exists fails
 }
 
 #[test]
@@ -45,12 +45,20 @@ fn change_moles() {
 
 #[test]
 fn total_moles() {
+    let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![20.5, 79.5], 293.15, 100).unwrap();
 
+    assert_eq!(mix.total_moles(), 100.0);
+    assert_eq!(GasMixture::from_empty(293.15, 100).total_moles(), 0.0);
 }
 
 #[test]
 fn return_pressure() {
+    let mix = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 300.0, 100).unwrap();
 
+    assert!((mix.return_pressure() - 2493.0).abs() < 0.01); // (100 * 8.31 * 300) / 100
+    assert_eq!(GasMixture::from_empty(293.15, 100).return_pressure(), 0.0); // vacuum
+    let no_volume = GasMixture::new(vec![(&gases::N2, 100.0)].into_iter().collect(), 300.0, 0);
+    assert_eq!(no_volume.return_pressure(), 0.0);
 }
 
 #[test]
@@ -59,7 +67,7 @@ fn heat_capacity() {
     let mole_vec = vec![50.0, 100.0];
     let mix = GasMixture::from_vecs(gas_vec, mole_vec, 100.0, 70).unwrap();
 
-    assert_eq!(mix.heat_capacity().unwrap(), 3000.0); // (50 * 20) + (100 * 20) = 3000
+    assert_eq!(mix.heat_capacity(), 3000.0); // (50 * 20) + (100 * 20) = 3000
 }
 
 #[test]
@@ -68,7 +76,7 @@ fn thermal_energy() {
     let mole_vec = vec![50.0, 100.0];
     let mix = GasMixture::from_vecs(gas_vec, mole_vec, 100.0, 70).unwrap();
 
-    assert_eq!(mix.thermal_energy().unwrap(), 300_000.0)
+    assert_eq!(mix.thermal_energy(), 300_000.0)
 }
 
 #[test]
@@ -78,6 +86,288 @@ fn merge() {
     let giver = GasMixture::from_vecs(gas_vec, mole_vec, 100.0, 70).unwrap();
     let mut taker = giver.clone();
 
-    taker.merge(giver).unwrap();
-    assert_eq!(taker.get_moles(&gases::O2).unwrap(), 300.0); // added up
+    taker.merge(giver);
+    assert_eq!(taker.get_moles(&gases::BZ).unwrap(), 100.0); // added up
+    assert_eq!(taker.get_moles(&gases::MIASMA).unwrap(), 200.0);
+}
+
+#[test]
+fn mole_fraction() {
+    let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![25.0, 75.0], 293.15, 100).unwrap();
+
+    assert_eq!(mix.mole_fraction(&gases::N2), 0.75);
+    assert_eq!(mix.percentage(&gases::O2), 25.0);
+    assert_eq!(mix.mole_fraction(&gases::PLASMA), 0.0); // not in the mix at all
+    assert_eq!(GasMixture::from_empty(293.15, 100).mole_fraction(&gases::O2), 0.0); // empty is just 0
+}
+
+#[test]
+fn partial_pressure() {
+    let mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![25.0, 75.0], 293.15, 100).unwrap();
+    let total = mix.partial_pressure(&gases::O2) + mix.partial_pressure(&gases::N2);
+
+    assert!((total - mix.return_pressure()).abs() < 1e-3); // Dalton's law
+}
+
+#[test]
+fn molar_mass_and_density() {
+    let mix = GasMixture::from_vecs(vec![&gases::CO2], vec![10.0], 293.15, 20).unwrap();
+
+    assert_eq!(mix.molar_mass(), 44.0);
+    assert_eq!(mix.density(), 22.0); // 440 g in 20 L
+}
+
+#[test]
+fn composition() {
+    let mix = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2, &gases::N2], vec![10.0, 60.0, 30.0], 293.15, 100).unwrap();
+    let composition = mix.composition();
+    let order = composition.iter().map(|c| c.gas.id).collect::<Vec<_>>();
+
+    assert_eq!(order, vec!["o2", "n2", "plasma"]);
+    assert_eq!(composition[2].fraction, 0.1);
+}
+
+#[test]
+fn is_breathable_for() {
+    let air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![21.8, 82.1], 293.15, 2500).unwrap();
+    let plasma_air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2, &gases::PLASMA], vec![21.8, 82.1, 1.0], 293.15, 2500).unwrap();
+    let thin_air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![5.0, 82.1], 293.15, 2500).unwrap();
+
+    assert!(air.is_breathable_for(&species::HUMAN));
+    assert!(!plasma_air.is_breathable_for(&species::HUMAN)); // 0.97 kPa of plasma is way over 0.05
+    assert!(!thin_air.is_breathable_for(&species::LIZARD));
+    assert!(!GasMixture::from_empty(293.15, 2500).is_breathable_for(&species::HUMAN));
+}
+
+#[test]
+fn transfer_to() {
+    let mut source = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![20.0, 80.0], 293.15, 100).unwrap();
+    let mut target = GasMixture::from_vecs(vec![&gases::CO2], vec![5.0], 293.15, 100).unwrap();
+    source.transfer_to(&mut target, 50.0).unwrap();
+
+    assert_eq!(target.get_moles(&gases::O2).unwrap(), 10.0);
+    assert_eq!(target.get_moles(&gases::N2).unwrap(), 40.0);
+    assert_eq!(source.total_moles(), 50.0);
+}
+
+#[test]
+fn transfer_ratio_to() {
+    let mut source = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 100).unwrap();
+    let mut target = GasMixture::from_vecs(vec![&gases::O2], vec![1.0], 293.15, 100).unwrap();
+    source.transfer_ratio_to(&mut target, 0.1).unwrap();
+
+    assert_eq!(source.get_moles(&gases::O2).unwrap(), 90.0);
+    assert_eq!(target.get_moles(&gases::O2).unwrap(), 11.0);
+}
+
+#[test]
+fn transfer_volume_to() {
+    let mut source = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 1000).unwrap();
+    let mut target = GasMixture::from_vecs(vec![&gases::O2], vec![1.0], 293.15, 70).unwrap();
+    source.transfer_volume_to(&mut target, 200.0).unwrap();
+
+    assert_eq!(source.get_moles(&gases::O2).unwrap(), 80.0);
+    assert!(source.transfer_volume_to(&mut target, -1.0).is_err());
+}
+
+#[test]
+fn pump_gas_to() {
+    let mut canister = GasMixture::from_vecs(vec![&gases::PLASMA], vec![1000.0], 293.15, 1000).unwrap();
+    let mut tank = GasMixture::from_vecs(vec![&gases::PLASMA], vec![1.0], 293.15, 70).unwrap();
+
+    assert!(canister.pump_gas_to(&mut tank, 1000.0));
+    assert!((tank.return_pressure() - 1000.0).abs() < 0.01);
+    assert!(!canister.pump_gas_to(&mut tank, 500.0)); // already over target
+}
+
+#[test]
+fn release_gas_to() {
+    let mut canister = GasMixture::from_vecs(vec![&gases::N2], vec![1000.0], 293.15, 1000).unwrap();
+    let mut room = GasMixture::from_vecs(vec![&gases::N2], vec![1.0], 293.15, 2500).unwrap();
+    let room_start = room.return_pressure();
+
+    assert!(canister.release_gas_to(&mut room, room_start + 50.0));
+    assert!((room.return_pressure() - (room_start + 50.0)).abs() < 0.01);
+
+    // within 10 kPa of the source, nothing moves
+    let mut equal = GasMixture::from_vecs(vec![&gases::N2], vec![995.0], 293.15, 1000).unwrap();
+    assert!(!canister.release_gas_to(&mut equal, 5000.0));
+}
+
+#[test]
+fn adjust_thermal_energy() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 300.0, 100).unwrap(); // 2000 J/K
+    let before = mix.thermal_energy();
+
+    assert_eq!(mix.adjust_thermal_energy(-40_000.0), -40_000.0);
+    assert_eq!(mix.temperature, 280.0);
+    assert!((mix.thermal_energy() - (before - 40_000.0)).abs() < 1e-3);
+
+    // can't cool below the cosmic background
+    let removed = mix.adjust_thermal_energy(-1e9);
+    assert_eq!(mix.temperature, num::TCMB);
+    assert!((removed + (280.0 - num::TCMB) * 2000.0).abs() < 1e-3);
+}
+
+#[test]
+fn adjust_thermal_energy_no_heat_capacity() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![0.0], 300.0, 100).unwrap();
+
+    assert_eq!(mix.adjust_thermal_energy(1000.0), 0.0);
+    assert_eq!(mix.heat_to(500.0, 1000.0, 1.0), 0.0);
+    assert_eq!(mix.set_temperature_preserving_energy(500.0), 0.0);
+    assert_eq!(mix.temperature, 500.0);
+}
+
+#[test]
+fn heat_to() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 300.0, 100).unwrap();
+
+    assert_eq!(mix.heat_to(200.0, 50_000.0, 1.0), -50_000.0); // freezer, power limited
+    assert_eq!(mix.temperature, 275.0);
+    assert_eq!(mix.heat_to(270.0, 50_000.0, 1.0), -10_000.0); // stops at target
+    assert_eq!(mix.temperature, 270.0);
+}
+
+#[test]
+fn empty_is_zero() {
+    let vacuum = GasMixture::from_empty(293.15, 1000);
+
+    assert_eq!(vacuum.heat_capacity(), 0.0);
+    assert_eq!(vacuum.thermal_energy(), 0.0);
+    assert_eq!(vacuum.molar_mass(), 0.0);
+    assert_eq!(vacuum.density(), 0.0);
+    assert!(vacuum.composition().is_empty());
+}
+
+#[test]
+fn merge_into_empty() {
+    let giver = GasMixture::from_vecs(vec![&gases::O2], vec![50.0], 500.0, 70).unwrap();
+    let mut vacuum = GasMixture::from_empty(293.15, 1000);
+    vacuum.merge(giver);
+
+    assert_eq!(vacuum.get_moles(&gases::O2).unwrap(), 50.0);
+    assert_eq!(vacuum.temperature, 500.0); // vacuum has no heat capacity, so it takes the giver's temperature
+
+    // and the other way round does nothing
+    let before = vacuum.clone();
+    vacuum.merge(GasMixture::from_empty(100.0, 70));
+    assert_eq!(vacuum, before);
+}
+
+#[test]
+fn remove_from_empty() {
+    let mut vacuum = GasMixture::from_empty(293.15, 1000);
+
+    assert!(vacuum.remove(10.0).unwrap().is_empty());
+    assert!(vacuum.remove_ratio(0.5).unwrap().is_empty());
+    assert!(vacuum.remove(-1.0).is_err());
+}
+
+#[test]
+fn pump_into_vacuum() {
+    let mut canister = GasMixture::from_vecs(vec![&gases::N2], vec![1000.0], 293.15, 1000).unwrap();
+    let mut pipe = GasMixture::from_empty(293.15, 200);
+
+    assert!(canister.pump_gas_to(&mut pipe, 300.0));
+    assert!((pipe.return_pressure() - 300.0).abs() < 0.01);
+}
+
+#[test]
+fn iteration_order() {
+    let mix = GasMixture::from_vecs(vec![&gases::MIASMA, &gases::N2, &gases::PLASMA, &gases::O2], vec![1.0, 2.0, 3.0, 4.0], 293.15, 70).unwrap();
+    let ids = mix.iter().map(|(gas, _)| gas.id).collect::<Vec<_>>();
+
+    assert_eq!(ids, vec!["o2", "n2", "plasma", "miasma"]);
+}
+
+#[test]
+fn display_is_stable() {
+    let a = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2, &gases::N2], vec![3.0, 1.0, 2.0], 293.15, 70).unwrap();
+    let b = GasMixture::from_vecs(vec![&gases::N2, &gases::PLASMA, &gases::O2], vec![2.0, 3.0, 1.0], 293.15, 70).unwrap();
+
+    assert_eq!(a.to_string(), "t:293.15,v:70,g:o2:1,n2:2,plasma:3,");
+    assert_eq!(a.to_string(), b.to_string());
+}
+
+#[test]
+fn sums_are_order_independent() {
+    // same gases, built in different orders, have to add up to the exact same bits
+    let moles = vec![0.1, 0.2, 0.3, 1e-7, 12345.678];
+    let gas_vec = vec![&gases::O2, &gases::N2, &gases::CO2, &gases::PLASMA, &gases::BZ];
+    let a = GasMixture::from_vecs(gas_vec.clone(), moles.clone(), 293.15, 70).unwrap();
+    let b = GasMixture::from_vecs(gas_vec.into_iter().rev().collect(), moles.into_iter().rev().collect(), 293.15, 70).unwrap();
+
+    assert_eq!(a.total_moles().to_bits(), b.total_moles().to_bits());
+    assert_eq!(a.heat_capacity().to_bits(), b.heat_capacity().to_bits());
+}
+
+#[test]
+fn share_moves_heat_with_gas() {
+    let mut hot = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 1000.0, 2500).unwrap();
+    let mut cold = GasMixture::from_vecs(vec![&gases::O2], vec![50.0], 100.0, 2500).unwrap();
+    let energy = hot.thermal_energy() + cold.thermal_energy();
+    hot.archive();
+    cold.archive();
+
+    let difference = hot.share(&mut cold, 4);
+    // a fifth of each difference moves
+    assert_eq!(hot.get_moles(&gases::N2).unwrap(), 80.0);
+    assert_eq!(hot.get_moles(&gases::O2).unwrap(), 10.0);
+    assert_eq!(cold.get_moles(&gases::N2).unwrap(), 20.0);
+    assert_eq!(cold.get_moles(&gases::O2).unwrap(), 40.0);
+    assert_eq!(hot.last_share(), 30.0);
+    assert!(hot.temperature < 1000.0 && cold.temperature > 100.0);
+    assert!((hot.thermal_energy() + cold.thermal_energy() - energy).abs() < 0.01);
+    assert!(difference > 0.0);
+}
+
+#[test]
+fn even_swaps_make_no_wind() {
+    // as much oxygen goes one way as nitrogen goes the other
+    let mut oxygen = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 2500).unwrap();
+    let mut nitrogen = GasMixture::from_vecs(vec![&gases::N2], vec![100.0], 293.15, 2500).unwrap();
+    oxygen.archive();
+    nitrogen.archive();
+
+    assert_eq!(oxygen.share(&mut nitrogen, 1), 0.0);
+    assert_eq!(oxygen.get_moles(&gases::N2).unwrap(), 50.0);
+    assert_eq!(oxygen.last_share(), 100.0);
+}
+
+#[test]
+fn share_needs_an_archive() {
+    let mut full = GasMixture::from_vecs(vec![&gases::O2], vec![100.0], 293.15, 2500).unwrap();
+    let mut empty = GasMixture::from_empty(293.15, 2500);
+
+    assert_eq!(full.share(&mut empty, 1), 0.0);
+    assert_eq!(full.get_moles(&gases::O2).unwrap(), 100.0);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn compare_thresholds() {
+    let air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![num::MOLES_O2STANDARD, num::MOLES_N2STANDARD], num::T20C, 2500).unwrap();
+    let mut other = air.clone();
+    other.change_moles(&gases::N2, num::MOLES_N2STANDARD + num::MINIMUM_MOLES_DELTA_TO_MOVE / 2.0).unwrap();
+    other.temperature += num::MINIMUM_TEMPERATURE_DELTA_TO_SUSPEND / 2.0;
+    assert_eq!(air.compare(&other), None);
+
+    other.change_moles(&gases::N2, num::MOLES_N2STANDARD + 1.0).unwrap();
+    assert_eq!(air.compare(&other), Some(MixtureDifference::Gas(&gases::N2)));
+
+    // temperature doesn't count without enough air to care about
+    let mut trace = GasMixture::from_vecs(vec![&gases::O2], vec![0.01], 293.15, 2500).unwrap();
+    let hot_trace = GasMixture::from_vecs(vec![&gases::O2], vec![0.01], 1000.0, 2500).unwrap();
+    assert_eq!(trace.compare(&hot_trace), None);
+    trace.change_moles(&gases::O2, 10.0).unwrap();
+    assert_eq!(trace.compare(&GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 1000.0, 2500).unwrap()), Some(MixtureDifference::Temperature));
+}
+
+#[test]
+fn archive_isnt_part_of_equality() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 293.15, 70).unwrap();
+    let copy = mix.clone();
+    mix.archive();
+    assert_eq!(mix, copy);
 }
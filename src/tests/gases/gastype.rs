@@ -0,0 +1,35 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+};
+
+static UNOBTAINIUM: GasType = GasType {
+    id: "unobtainium",
+    name: "Unobtainium",
+    specific_heat: 1,
+    fusion_power: 0,
+    molar_mass: 1.0
+};
+
+#[test]
+fn registry_order() {
+    let mut sorted = gases::ALL.to_vec();
+    sorted.reverse();
+    sorted.sort();
+
+    assert_eq!(sorted, gases::ALL.to_vec());
+    assert_eq!(gases::PLASMA.registry_index(), Some(3));
+}
+
+#[test]
+fn unregistered_gases_come_last() {
+    assert_eq!(UNOBTAINIUM.registry_index(), None);
+    assert!(UNOBTAINIUM > gases::MIASMA);
+    assert!(gases::O2 < UNOBTAINIUM);
+}
+
+#[test]
+fn equality_by_id() {
+    let fake_oxygen = GasType { id: "o2", name: "Not Oxygen", specific_heat: 0, fusion_power: 0, molar_mass: 0.0 };
+    assert_eq!(fake_oxygen, gases::O2);
+}
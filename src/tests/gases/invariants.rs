@@ -0,0 +1,74 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    errors::AtmosError,
+    byond::Real,
+};
+
+#[test]
+fn validate() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 293.15, 70).unwrap();
+    assert!(mix.validate().is_ok());
+
+    let broken = GasMixture::new(vec![(&gases::O2, -1.0)].into_iter().collect(), 293.15, 70);
+    assert!(broken.validate().is_err());
+
+    mix.temperature = Real::INFINITY;
+    assert!(mix.validate().is_err());
+}
+
+#[test]
+fn remove_everything_leaves_nothing_negative() {
+    // thirds don't quantize cleanly, which is what used to leave -0.00000005 mol behind
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2, &gases::N2, &gases::CO2], vec![1.0 / 3.0, 2.0 / 3.0, 10.0 / 3.0], 293.15, 70).unwrap();
+    let before = mix.clone();
+    let removed = mix.remove(mix.total_moles()).unwrap();
+
+    assert!(mix.validate().is_ok());
+    assert!(invariants::check_conservation("remove", &[&before], &[&mix, &removed], 0.0).is_ok());
+}
+
+#[test]
+fn merge_conserves() {
+    let hot = GasMixture::from_vecs(vec![&gases::PLASMA], vec![10.0], 1000.0, 70).unwrap();
+    let cold = GasMixture::from_vecs(vec![&gases::O2], vec![50.0], 100.0, 70).unwrap();
+    let mut merged = cold.clone();
+    merged.merge(hot.clone());
+
+    assert!(invariants::check_conservation("merge", &[&cold, &hot], &[&merged], 0.0).is_ok());
+}
+
+#[test]
+fn conservation_violation_has_context() {
+    let before = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 293.15, 70).unwrap();
+    let after = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 500.0, 70).unwrap();
+
+    match invariants::check_conservation("test", &[&before], &[&after], 0.0) {
+        Err(AtmosError::InvariantViolated { operation, details }) => {
+            assert_eq!(operation, "test");
+            assert!(details.contains("thermal energy"));
+            assert!(details.contains("t:500")); // says which mixtures were involved
+        },
+        other => panic!("expected InvariantViolated, got {:?}", other)
+    }
+}
+
+#[test]
+fn reaction_balance() {
+    let before = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 293.15, 70).unwrap();
+    let mut after = before.clone();
+    after.adjust_thermal_energy(-100.0);
+
+    assert!(invariants::check_reaction("endothermic", &before, &after, -100.0).is_ok());
+    assert!(invariants::check_reaction("endothermic", &before, &after, 100.0).is_err());
+}
+
+#[cfg(feature = "invariants")]
+#[test]
+#[should_panic(expected = "Invariant broken by merge")]
+fn enforced_on_merge() {
+    let mut mix = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], 293.15, 70).unwrap();
+    let mut broken = GasMixture::from_vecs(vec![&gases::N2], vec![5.0], 293.15, 70).unwrap();
+    broken.temperature = Real::NAN;
+    mix.merge(broken);
+}
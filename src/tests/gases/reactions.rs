@@ -0,0 +1,115 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::gases::reactions::ReactionResults,
+    byond::Real,
+};
+
+#[test]
+fn sorted_by_priority() {
+    let all = crate::constants::reactions::ALL;
+    for pair in all.windows(2) {
+        assert!(pair[0].priority > pair[1].priority, "{} should come after {}", pair[0].id, pair[1].id);
+    }
+}
+
+#[test]
+fn room_air_does_nothing() {
+    let mut air = GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![21.8, 82.1], num::T20C, 2500).unwrap();
+    let before = air.clone();
+
+    assert_eq!(air.react(), ReactionResults::default());
+    assert_eq!(air, before);
+    assert!(!GasMixture::from_empty(num::T20C, 70).react().reacted());
+}
+
+#[test]
+fn plasma_fire() {
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![10.0, 100.0], 1000.0, 70).unwrap();
+    let energy = air.thermal_energy();
+    let results = air.react();
+
+    assert_eq!(results.fired, vec!["plasmafire"]);
+    assert!(results.fire > 0.0);
+    assert!(air.get_moles(&gases::PLASMA).unwrap() < 10.0);
+    assert!(air.get_moles(&gases::CO2).unwrap() > 0.0);
+    assert!((air.thermal_energy() - energy - results.energy_released).abs() < 1.0);
+    assert!(air.temperature > 1000.0);
+}
+
+#[test]
+fn plasma_fire_supersaturation() {
+    // way more than SUPER_SATURATION_THRESHOLD times as much oxygen as plasma makes tritium
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![1.0, 200.0], 1000.0, 70).unwrap();
+    air.react();
+
+    assert!(air.get_moles(&gases::TRIT).unwrap() > 0.0);
+    assert_eq!(air.get_moles(&gases::CO2), None);
+}
+
+#[test]
+fn too_cold_to_burn() {
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![10.0, 100.0], num::T20C, 70).unwrap();
+    assert!(!air.react().reacted());
+}
+
+#[test]
+fn tritium_fire() {
+    let mut air = GasMixture::from_vecs(vec![&gases::TRIT, &gases::O2], vec![10.0, 100.0], 500.0, 70).unwrap();
+    let results = air.react();
+
+    assert!(results.has_fired("tritfire"));
+    assert!(air.get_moles(&gases::H2O).unwrap() > 0.0);
+    assert!(air.temperature > 500.0);
+}
+
+#[test]
+fn noblium_suppresses() {
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2, &gases::HYPERNOB], vec![10.0, 100.0, 10.0], 1000.0, 70).unwrap();
+    let before = air.clone();
+    let results = air.react();
+
+    assert_eq!(results.fired, vec!["nobstop"]);
+    assert_eq!(air, before);
+}
+
+#[test]
+fn bz_formation() {
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::N2O], vec![20.0, 20.0], num::T20C, 2500).unwrap();
+    let results = air.react();
+
+    assert!(results.has_fired("bzformation"));
+    assert!(air.get_moles(&gases::BZ).unwrap() > 0.0);
+    assert!(results.research > 0.0);
+}
+
+#[test]
+fn bz_research_isnt_capped() {
+    // a huge, thin mix is about as efficient as BZ formation gets
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::N2O], vec![200.0, 200.0], num::T20C, 2_000_000).unwrap();
+    let results = air.react();
+
+    assert!(results.has_fired("bzformation"));
+    assert!(results.research > num::BZ_RESEARCH_MAX_AMOUNT as Real);
+}
+
+#[test]
+fn noblium_formation_research() {
+    // 3 mol of noblium formed, at NOBLIUM_RESEARCH_AMOUNT each
+    let mut air = GasMixture::from_vecs(vec![&gases::N2, &gases::TRIT], vec![200.0, 100.0], 10_000_000.0, 2500).unwrap();
+    let results = air.react();
+
+    assert!(results.has_fired("nobformation"));
+    assert!((air.get_moles(&gases::HYPERNOB).unwrap() - 3.0).abs() < 0.001);
+    assert!((results.research - 3.0 * num::NOBLIUM_RESEARCH_AMOUNT as Real).abs() < 1.0);
+}
+
+#[test]
+fn zero_gases_collected() {
+    // burns every last bit of plasma
+    let mut air = GasMixture::from_vecs(vec![&gases::PLASMA, &gases::O2], vec![0.02, 100.0], 3000.0, 70).unwrap();
+    air.react();
+    air.react();
+
+    assert!(air.iter().all(|(_, moles)| moles > 0.0));
+}
@@ -0,0 +1,78 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::machinery::analyzer::*,
+    byond::Real,
+};
+
+fn station_air() -> GasMixture<'static> {
+    GasMixture::from_vecs(vec![&gases::O2, &gases::N2], vec![num::MOLES_O2STANDARD, num::MOLES_N2STANDARD], num::T20C, num::CELL_VOLUME).unwrap()
+}
+
+#[test]
+fn station_air_is_fine() {
+    let report = AnalyzerReport::new(&station_air());
+
+    assert!(!report.pressure_alert);
+    assert!(!report.is_dangerous());
+    let labels = report.gases.iter().map(|line| line.label).collect::<Vec<_>>();
+    assert_eq!(labels, vec!["Nitrogen", "Oxygen", "CO2", "Plasma"]);
+    assert_eq!(report.gases[1].percentage, 21.0);
+}
+
+fn mix(gases: Vec<&'static GasType>, moles: Vec<Real>) -> GasMixture<'static> {
+    GasMixture::from_vecs(gases, moles, num::T20C, num::CELL_VOLUME).unwrap()
+}
+
+#[test]
+fn text() {
+    let air = mix(vec![&gases::N2, &gases::O2, &gases::PLASMA], vec![75.0, 20.0, 5.0]);
+    let text = AnalyzerReport::new(&air).to_string();
+
+    assert_eq!(text, "Results:
+Pressure: 97.44 kPa
+Volume: 2500 L
+Moles: 100 mol
+Nitrogen: 75 % (75 mol)
+Oxygen: 20 % (20 mol)
+CO2: 0 % (0 mol)
+(!) Plasma: 5 % (5 mol)
+Temperature: 20 °C (293.15 K)");
+}
+
+#[test]
+fn html() {
+    let air = mix(vec![&gases::N2, &gases::O2, &gases::BZ], vec![79.0, 20.0, 1.0]);
+    let html = AnalyzerReport::new(&air).to_html();
+
+    assert!(html.starts_with("<span class='info'><B>Results:</B></span><br>"));
+    assert!(html.contains("<span class='info'>Pressure: 97.44 kPa</span>"));
+    assert!(html.contains("<span class='alert'>BZ: 1 % (1 mol)</span>"));
+    assert!(html.ends_with("<span class='info'>Temperature: 20 &deg;C (293.15 K)</span>"));
+}
+
+#[test]
+fn threshold() {
+    let mut air = station_air();
+    air.assert_gas(&gases::BZ);
+    air.change_moles(&gases::BZ, 0.1).unwrap();
+
+    assert_eq!(AnalyzerReport::new(&air).gases.len(), 4);
+    assert_eq!(AnalyzerReport::with_threshold(&air, 0.0).gases.len(), 5);
+
+    // anything you could actually see still shows up
+    air.change_moles(&gases::BZ, num::MOLES_GAS_VISIBLE).unwrap();
+    assert_eq!(AnalyzerReport::new(&air).gases.len(), 5);
+}
+
+#[test]
+fn empty() {
+    let report = AnalyzerReport::new(&GasMixture::from_empty(num::T20C, 70));
+
+    assert!(report.gases.is_empty());
+    assert!(report.pressure_alert);
+    assert_eq!(report.to_string(), "Results:
+(!) Pressure: 0 kPa
+Volume: 70 L
+Moles: 0 mol");
+}
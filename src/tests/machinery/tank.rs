@@ -0,0 +1,103 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::machinery::tank::*,
+    byond::Real,
+};
+
+/// Tank of O2 at roughly `pressure` kPa
+fn tank_at(pressure: Real) -> Tank<'static> {
+    let moles = pressure * 70.0 / (num::R_IDEAL_GAS_EQUATION * num::T20C);
+    Tank::new(GasMixture::from_vecs(vec![&gases::O2], vec![moles], num::T20C, 70).unwrap())
+}
+
+#[test]
+fn stable() {
+    let mut tank = tank_at(num::ONE_ATMOSPHERE * 10.0);
+
+    assert_eq!(tank.check_status(), TankStatus::Stable);
+    assert_eq!(tank.integrity, TANK_MAX_INTEGRITY);
+    assert_eq!(tank.volume(), 70);
+}
+
+#[test]
+fn leak() {
+    let mut tank = tank_at(num::TANK_LEAK_PRESSURE + 10.0);
+    for _ in 0..TANK_MAX_INTEGRITY {
+        assert_eq!(tank.check_status(), TankStatus::Stable); // losing integrity first
+    }
+    assert_eq!(tank.integrity, 0);
+
+    let moles = tank.air_contents.total_moles();
+    match tank.check_status() {
+        TankStatus::Leaking(leaked) => assert!((leaked.total_moles() - moles * 0.25).abs() < 1e-4),
+        other => panic!("expected a leak, got {:?}", other)
+    }
+    assert!(!tank.is_destroyed());
+}
+
+#[test]
+fn integrity_recovers() {
+    let mut tank = tank_at(num::TANK_LEAK_PRESSURE + 10.0);
+    tank.check_status();
+    tank.check_status();
+    assert_eq!(tank.integrity, 1);
+
+    tank.air_contents.remove_ratio(0.5).unwrap(); // back under leak pressure
+    tank.check_status();
+    assert_eq!(tank.integrity, 2);
+}
+
+#[test]
+fn rupture() {
+    let mut tank = tank_at(num::TANK_RUPTURE_PRESSURE + 10.0);
+    let moles = tank.air_contents.total_moles();
+    for _ in 0..TANK_MAX_INTEGRITY {
+        tank.check_status();
+    }
+
+    match tank.check_status() {
+        TankStatus::Ruptured(released) => assert!((released.total_moles() - moles).abs() < 1e-4),
+        other => panic!("expected a rupture, got {:?}", other)
+    }
+    assert!(tank.is_destroyed());
+    assert_eq!(tank.air_contents.total_moles(), 0.0);
+    assert_eq!(tank.check_status(), TankStatus::Stable); // nothing left to happen
+}
+
+#[test]
+fn melt() {
+    let mut tank = tank_at(0.5); // little enough gas that it stays under leak pressure when this hot
+    tank.air_contents.temperature = num::TANK_MELT_TEMPERATURE as Real + 1.0;
+    for _ in 0..TANK_MAX_INTEGRITY {
+        tank.check_status();
+    }
+
+    match tank.check_status() {
+        TankStatus::Ruptured(_) => {},
+        other => panic!("expected the tank to melt open, got {:?}", other)
+    }
+}
+
+#[test]
+fn melt_needs_to_be_over_the_limit() {
+    let mut tank = tank_at(0.5);
+    tank.air_contents.temperature = num::TANK_MELT_TEMPERATURE as Real;
+
+    assert_eq!(tank.check_status(), TankStatus::Stable);
+    assert_eq!(tank.integrity, TANK_MAX_INTEGRITY);
+}
+
+#[test]
+fn explode() {
+    let mut tank = tank_at(num::TANK_FRAGMENT_PRESSURE + num::TANK_FRAGMENT_SCALE * 4.0);
+
+    match tank.check_status() {
+        TankStatus::Exploded(explosion) => {
+            assert!((explosion.range - 4.0).abs() < 1e-3);
+            assert!(!explosion.is_capped());
+        },
+        other => panic!("expected an explosion, got {:?}", other)
+    }
+    assert!(tank.is_destroyed());
+}
@@ -0,0 +1,66 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::machinery::tank::*,
+    atmospherics::machinery::ttv::*,
+    byond::Real,
+};
+
+fn ttv(plasma: Real, plasma_temperature: Real, oxygen: Real) -> TransferValve<'static> {
+    let one = GasMixture::from_vecs(vec![&gases::PLASMA], vec![plasma], plasma_temperature, 70).unwrap();
+    let two = GasMixture::from_vecs(vec![&gases::O2], vec![oxygen], num::T20C, 70).unwrap();
+    TransferValve::new(Tank::new(one), Tank::new(two))
+}
+
+#[test]
+fn open_merges_into_tank_two() {
+    let mut valve = ttv(20.0, 500.0, 60.0);
+    valve.open();
+
+    assert!(valve.is_open());
+    assert!(valve.tank_one.air_contents.total_moles() == 0.0);
+    assert_eq!(valve.tank_two.volume(), 140);
+    assert!((valve.tank_two.air_contents.total_moles() - 80.0).abs() < 1e-4);
+
+    valve.open(); // already open, nothing happens
+    assert_eq!(valve.tank_two.volume(), 140);
+}
+
+#[test]
+fn explodes() {
+    let report = ttv(25.0, 1000.0, 75.0).simulate(TTV_DEFAULT_MAX_TICKS);
+    let explosion = report.explosion().expect("should have exploded");
+
+    assert!(explosion.theoretical.light_impact >= 1.0);
+    assert_eq!(report.timeline.len(), report.timeline.last().unwrap().tick + 1);
+    assert!(report.peak_pressure() > num::TANK_FRAGMENT_PRESSURE);
+    assert!(report.timeline.iter().any(|tick| tick.reactions.contains(&"plasmafire")));
+}
+
+#[test]
+fn cold_mix_stabilises() {
+    let report = ttv(25.0, num::T20C, 75.0).simulate(TTV_DEFAULT_MAX_TICKS);
+
+    assert_eq!(report.outcome, TtvOutcome::Stabilised);
+    assert_eq!(report.timeline.len(), 1);
+    assert!(report.timeline[0].reactions.is_empty());
+    assert_eq!(report.explosion(), None);
+}
+
+#[test]
+fn times_out() {
+    // over leak pressure with nothing reacting, so it'd keep going for a while
+    let report = ttv(0.0, num::T20C, 200.0).simulate(2);
+    assert_eq!(report.outcome, TtvOutcome::TimedOut);
+    assert_eq!(report.timeline.len(), 2);
+}
+
+#[test]
+fn slow_burn() {
+    // a cooler mix takes a few ticks of burning to get there
+    let report = ttv(10.0, 500.0, 40.0).simulate(TTV_DEFAULT_MAX_TICKS);
+
+    assert!(report.explosion().is_some());
+    assert!(report.timeline.len() > 1);
+    assert!(report.timeline[1].temperature > report.timeline[0].temperature);
+}
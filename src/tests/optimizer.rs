@@ -0,0 +1,88 @@
+use crate::{
+    constants::*,
+    atmospherics::optimizer::*,
+    errors::AtmosError,
+};
+
+fn quick(goal: Goal<'static>, threads: usize) -> Optimizer<'static> {
+    let mut optimizer = Optimizer::new(BombConstraints::default(), goal);
+    optimizer.starts = 16;
+    optimizer.seeds = 2;
+    optimizer.max_evaluations = 300;
+    optimizer.threads = threads;
+    optimizer
+}
+
+#[test]
+fn deterministic() {
+    let single = quick(Goal::MaxRange, 1).run().unwrap();
+    let parallel = quick(Goal::MaxRange, 4).run().unwrap();
+
+    assert_eq!(single, parallel);
+}
+
+#[test]
+fn stays_in_constraints() {
+    let result = quick(Goal::MaxRange, 2).run().unwrap();
+    let constraints = BombConstraints::default();
+
+    assert!(result.evaluations <= 300);
+    for tank in &[&result.best.tank_one, &result.best.tank_two] {
+        assert!(tank.return_pressure() <= constraints.max_pressure + 1e-6);
+        assert!(tank.temperature >= constraints.min_temperature && tank.temperature <= constraints.max_temperature);
+        assert_eq!(tank.volume, constraints.tank_volume);
+    }
+    assert_eq!(result.best.tank_one.get_moles(&gases::O2), None); // plasma only
+}
+
+#[test]
+fn max_range_explodes() {
+    let result = quick(Goal::MaxRange, 2).run().unwrap();
+    let explosion = result.best.explosion().expect("should have found a working bomb");
+
+    assert!(explosion.range > 0.0);
+    assert_eq!(result.best.score, explosion.range);
+}
+
+#[test]
+fn reach_cap_with_least_plasma() {
+    let result = quick(Goal::ReachCapWithLeast(&gases::PLASMA), 2).run().unwrap();
+    let explosion = result.best.explosion().expect("should have found a working bomb");
+    let cap = BombConstraints::default().bombcap;
+
+    assert_eq!(explosion.actual.devastation, cap.devastation);
+    assert_eq!(explosion.actual.light_impact, cap.light_impact);
+    // the biggest bomb is way past the cap, so it should take more plasma
+    let biggest = quick(Goal::MaxRange, 2).run().unwrap();
+    assert!(result.best.moles_of(&gases::PLASMA) < biggest.best.moles_of(&gases::PLASMA));
+}
+
+#[test]
+fn small_budgets_cap_the_starts() {
+    let mut optimizer = quick(Goal::MaxRange, 2);
+    optimizer.max_evaluations = 5;
+    let result = optimizer.run().unwrap();
+    assert!(result.evaluations <= 5);
+}
+
+#[test]
+fn rejects_nonsense_constraints() {
+    // a freezer down at absolute zero would fill the tanks with infinite mols
+    let mut optimizer = quick(Goal::MaxRange, 2);
+    optimizer.constraints.min_temperature = 0.0;
+    assert_eq!(optimizer.run().unwrap_err(), AtmosError::InvalidTemperature { temperature: 0.0 });
+
+    // nor can the freezer be hotter than the heater
+    let mut optimizer = quick(Goal::MaxRange, 2);
+    optimizer.constraints.min_temperature = 500.0;
+    optimizer.constraints.max_temperature = 400.0;
+    assert_eq!(optimizer.run().unwrap_err(), AtmosError::InvalidTemperature { temperature: 500.0 });
+
+    let mut optimizer = quick(Goal::MaxRange, 2);
+    optimizer.constraints.max_pressure = -1.0;
+    assert_eq!(optimizer.run().unwrap_err(), AtmosError::LessThanZero { value: -1.0 });
+
+    let mut optimizer = quick(Goal::MaxRange, 2);
+    optimizer.constraints.tank_volume = 0;
+    assert_eq!(optimizer.run().unwrap_err(), AtmosError::InvalidVolume { volume: 0.0 });
+}
@@ -0,0 +1,50 @@
+use crate::{
+    constants::*,
+    atmospherics::explosion::*,
+    atmospherics::research::*,
+    byond::Real,
+};
+
+#[test]
+fn scoring_curve() {
+    assert_eq!(bomb_points(0.0), 0.0);
+    assert_eq!(bomb_points(9.0), 0.0);
+    assert!((bomb_points(10.0) - 83300.0 * 10.0 / 3010.0).abs() < 0.001);
+    // just under the cap where the curve hands over
+    assert!(bomb_points(4499.0) < num::TECHWEB_BOMB_POINTCAP as Real);
+    assert_eq!(bomb_points(4500.0), num::TECHWEB_BOMB_POINTCAP as Real);
+
+    let mut last = 0.0;
+    for light in 10..4500 {
+        let points = bomb_points(light as Real);
+        assert!(points > last);
+        last = points;
+    }
+}
+
+#[test]
+fn scored_before_the_cap() {
+    // a 100 range bomb gets capped to 14 light impact, but gets scored on its 100
+    let mut doppler = DopplerArray::default();
+    let explosion = Explosion::from_range(100.0, &BombCap::default());
+
+    assert_eq!(explosion.actual.light_impact, 14.0);
+    assert_eq!(doppler.sense_explosion(&explosion), bomb_points(100.0));
+}
+
+#[test]
+fn only_bigger_bombs_pay() {
+    let mut doppler = DopplerArray::default();
+    let small = Explosion::from_range(50.0, &BombCap::default());
+    let big = Explosion::from_range(200.0, &BombCap::default());
+
+    assert_eq!(doppler.sense_explosion(&small), bomb_points(50.0));
+    assert_eq!(doppler.sense_explosion(&big), bomb_points(200.0) - bomb_points(50.0));
+    assert_eq!(doppler.sense_explosion(&small), 0.0);
+    assert_eq!(doppler.largest_bomb_value, bomb_points(200.0));
+
+    // too small to count at all
+    let dud = Explosion::from_range(5.0, &BombCap::default());
+    assert_eq!(DopplerArray::default().sense_explosion(&dud), 0.0);
+    assert_eq!(DopplerArray::new(bomb_points(200.0)).sense_explosion(&big), 0.0);
+}
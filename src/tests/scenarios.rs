@@ -0,0 +1,81 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    atmospherics::machinery::tank::*,
+    atmospherics::scenarios::*,
+    byond::Real,
+};
+
+#[test]
+fn heated_tank_fragments() {
+    let air = GasMixture::from_vecs(vec![&gases::N2], vec![57.5], num::T20C, 70).unwrap();
+    let heater = HeatSource { temperature: 3000.0, power: 50_000.0 };
+    let report = heat_tank(Tank::new(air), Some(heater), 1000);
+
+    assert!(report.explosion().is_some());
+    let last = report.timeline.last().unwrap();
+    assert!(last.pressure > num::TANK_FRAGMENT_PRESSURE);
+    assert_eq!(report.time_to_failure, Some(last.time));
+    // heated too fast to leak on the way
+    assert_eq!(report.released.total_moles(), 0.0);
+}
+
+#[test]
+fn slowly_heated_tank_leaks() {
+    let air = GasMixture::from_vecs(vec![&gases::N2], vec![57.5], num::T20C, 70).unwrap();
+    let heater = HeatSource { temperature: 3000.0, power: 5_000.0 };
+    let report = heat_tank(Tank::new(air), Some(heater), 1000);
+
+    // leaks itself back under the leak pressure instead of ever bursting
+    assert_eq!(report.failure, None);
+    assert!(report.time_to_failure.is_some());
+    assert!(report.released.total_moles() > 0.0);
+    assert_eq!(report.timeline.last().unwrap().temperature, 3000.0);
+}
+
+#[test]
+fn gentle_heater_settles() {
+    let air = GasMixture::from_vecs(vec![&gases::O2], vec![10.0], num::T20C, 70).unwrap();
+    let heater = HeatSource { temperature: 400.0, power: 5_000.0 };
+    let report = heat_tank(Tank::new(air), Some(heater), 1000);
+
+    assert_eq!(report.failure, None);
+    assert_eq!(report.time_to_failure, None);
+    assert_eq!(report.timeline.last().unwrap().temperature, 400.0);
+}
+
+#[test]
+fn melt() {
+    // not much gas, but very hot
+    let air = GasMixture::from_vecs(vec![&gases::N2], vec![0.01], num::T20C, 70).unwrap();
+    let heater = HeatSource { temperature: num::TANK_MELT_TEMPERATURE as Real * 2.0, power: 1e9 };
+    let report = heat_tank(Tank::new(air), Some(heater), 100);
+
+    assert_eq!(report.failure, Some(TankFailure::Melted));
+    assert_eq!(report.time_to_failure, Some(report.timeline.len() as Real * TANK_TICK_SECONDS));
+    assert!((report.released.total_moles() - 0.01).abs() < 1e-6);
+}
+
+#[test]
+fn grenade_in_room() {
+    let payload = GasMixture::from_vecs(vec![&gases::PLASMA], vec![100.0], 1000.0, 70).unwrap();
+    let report = release_grenade(payload, standard_room(9), 1000);
+
+    assert!(report.burned > 0.0);
+    assert!(report.energy_released > 0.0);
+    assert!(report.time_to_hazard.is_some());
+    assert!(report.burn_time() > 0.0);
+    assert!(report.room.get_moles(&gases::CO2).unwrap() > 0.0);
+    assert!(report.timeline.last().unwrap().reactions.is_empty() || report.timeline.len() == 1000);
+}
+
+#[test]
+fn harmless_grenade() {
+    let payload = GasMixture::from_vecs(vec![&gases::N2], vec![10.0], num::T20C, 70).unwrap();
+    let report = release_grenade(payload, standard_room(9), 1000);
+
+    assert_eq!(report.timeline.len(), 1);
+    assert_eq!(report.time_to_hazard, None);
+    assert_eq!(report.burned, 0.0);
+    assert_eq!(report.burn_time(), 0.0);
+}
@@ -0,0 +1,60 @@
+use crate::{
+    constants::*,
+    atmospherics::gases::*,
+    units::*,
+    byond::Real,
+};
+
+fn close(a: Real, b: Real) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+#[test]
+fn temperature_conversions() {
+    assert!(close(Kelvin::from(Celsius(20.0)).value(), num::T20C));
+    assert!(close(Celsius::from(Kelvin(num::T0C)).value(), 0.0));
+    assert!(close(Fahrenheit::from(Celsius(100.0)).value(), 212.0));
+    assert!(close(Kelvin::from(Fahrenheit(32.0)).value(), num::T0C));
+    assert!(close(Fahrenheit::from(Kelvin(0.0)).value(), -459.67));
+}
+
+#[test]
+fn pressure_conversions() {
+    assert!(close(KiloPascal::from(Atmospheres(1.0)).value(), num::ONE_ATMOSPHERE));
+    assert!(close(Atmospheres::from(KiloPascal::from(Atmospheres(30.0))).value(), 30.0));
+    assert!((Psi::from(Atmospheres(1.0)).value() - 14.6959).abs() < 0.0001);
+    assert!(close(KiloPascal::from(Psi::from(KiloPascal(500.0))).value(), 500.0));
+}
+
+#[test]
+fn energy_arithmetic() {
+    let heat_capacity = JoulesPerKelvin(2000.0);
+    let energy = heat_capacity * Kelvin(100.0);
+    assert_eq!(energy, Joules(200_000.0));
+    assert_eq!(energy / heat_capacity, Kelvin(100.0));
+    assert_eq!(energy / Kelvin(100.0), heat_capacity);
+    assert_eq!(Moles(5.0) + Moles(2.5) - Moles(0.5), Moles(7.0));
+    assert_eq!(Liters(70.0) * 2.0, Liters(140.0));
+}
+
+#[test]
+fn display() {
+    assert_eq!(format!("{}", KiloPascal(101.5)), "101.5 kPa");
+    assert_eq!(format!("{}", JoulesPerKelvin(20.0)), "20 J/K");
+}
+
+#[test]
+fn gasmixture_typed() {
+    let mut mix = GasMixture::from_units(vec![&gases::O2, &gases::N2], vec![Moles(50.0), Moles(50.0)], Kelvin(100.0), Liters(100.0)).unwrap();
+    assert_eq!(mix.liters(), Liters(100.0));
+    assert_eq!(mix.moles(&gases::O2), Some(Moles(50.0)));
+    assert_eq!(mix.total_mol(), Moles(100.0));
+    assert_eq!(mix.heat_capacity_per_kelvin(), JoulesPerKelvin(2000.0));
+    assert_eq!(mix.thermal_joules(), Joules(200_000.0));
+
+    mix.set_moles(&gases::O2, Moles(25.0)).unwrap();
+    assert_eq!(mix.get_moles(&gases::O2).unwrap(), 25.0);
+
+    mix.set_kelvin(Celsius(0.0));
+    assert!(close(mix.temperature, num::T0C));
+}
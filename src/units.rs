@@ -0,0 +1,220 @@
+use crate::byond::Real;
+use crate::constants::num;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Kilopascals in one pound per square inch. Written out in full as an f64, the `byond` build
+/// narrows it to the closest f32.
+const KPA_PER_PSI: Real = 6.894_757_293_168_f64 as Real;
+
+// Generates a unit newtype over Real along with the boring stuff every unit needs:
+// display with its symbol, adding/subtracting two of the same unit and scaling by
+// a plain Real.
+macro_rules! unit {
+    ($(#[$meta:meta])* $name:ident, $symbol:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+        pub struct $name(pub Real);
+
+        impl $name {
+            /// Returns the raw `Real` value, without a unit.
+            pub fn value(self) -> Real {
+                self.0
+            }
+        }
+
+        impl From<$name> for Real {
+            fn from(unit: $name) -> Real {
+                unit.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{} {}", self.0, $symbol)
+            }
+        }
+
+        impl Add for $name {
+            type Output = $name;
+            fn add(self, rhs: $name) -> $name {
+                $name(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+            fn sub(self, rhs: $name) -> $name {
+                $name(self.0 - rhs.0)
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+            fn neg(self) -> $name {
+                $name(-self.0)
+            }
+        }
+
+        impl Mul<Real> for $name {
+            type Output = $name;
+            fn mul(self, rhs: Real) -> $name {
+                $name(self.0 * rhs)
+            }
+        }
+
+        impl Div<Real> for $name {
+            type Output = $name;
+            fn div(self, rhs: Real) -> $name {
+                $name(self.0 / rhs)
+            }
+        }
+    };
+}
+
+unit!(
+    /// Absolute temperature in kelvin. What `GasMixture::temperature` is stored in.
+    Kelvin, "K"
+);
+unit!(
+    /// Temperature in degrees celsius.
+    Celsius, "°C"
+);
+unit!(
+    /// Temperature in degrees fahrenheit.
+    Fahrenheit, "°F"
+);
+unit!(
+    /// Pressure in kilopascals. What `GasMixture::return_pressure()` returns.
+    KiloPascal, "kPa"
+);
+unit!(
+    /// Pressure in standard atmospheres (1 atm = 101.325 kPa).
+    Atmospheres, "atm"
+);
+unit!(
+    /// Pressure in pounds per square inch.
+    Psi, "psi"
+);
+unit!(
+    /// Amount of gas in mols.
+    Moles, "mol"
+);
+unit!(
+    /// Volume in litres. What `GasMixture::volume` is stored in.
+    Liters, "L"
+);
+unit!(
+    /// Energy in joules.
+    Joules, "J"
+);
+unit!(
+    /// Heat capacity in joules per kelvin.
+    JoulesPerKelvin, "J/K"
+);
+
+// Temperature conversions
+
+impl From<Celsius> for Kelvin {
+    fn from(c: Celsius) -> Kelvin {
+        Kelvin(c.0 + num::T0C)
+    }
+}
+
+impl From<Kelvin> for Celsius {
+    fn from(k: Kelvin) -> Celsius {
+        Celsius(k.0 - num::T0C)
+    }
+}
+
+impl From<Fahrenheit> for Celsius {
+    fn from(f: Fahrenheit) -> Celsius {
+        Celsius((f.0 - 32.0) * 5.0 / 9.0)
+    }
+}
+
+impl From<Celsius> for Fahrenheit {
+    fn from(c: Celsius) -> Fahrenheit {
+        Fahrenheit(c.0 * 9.0 / 5.0 + 32.0)
+    }
+}
+
+impl From<Fahrenheit> for Kelvin {
+    fn from(f: Fahrenheit) -> Kelvin {
+        Kelvin::from(Celsius::from(f))
+    }
+}
+
+impl From<Kelvin> for Fahrenheit {
+    fn from(k: Kelvin) -> Fahrenheit {
+        Fahrenheit::from(Celsius::from(k))
+    }
+}
+
+// Pressure conversions
+
+impl From<Atmospheres> for KiloPascal {
+    fn from(atm: Atmospheres) -> KiloPascal {
+        KiloPascal(atm.0 * num::ONE_ATMOSPHERE)
+    }
+}
+
+impl From<KiloPascal> for Atmospheres {
+    fn from(kpa: KiloPascal) -> Atmospheres {
+        Atmospheres(kpa.0 / num::ONE_ATMOSPHERE)
+    }
+}
+
+impl From<Psi> for KiloPascal {
+    fn from(psi: Psi) -> KiloPascal {
+        KiloPascal(psi.0 * KPA_PER_PSI)
+    }
+}
+
+impl From<KiloPascal> for Psi {
+    fn from(kpa: KiloPascal) -> Psi {
+        Psi(kpa.0 / KPA_PER_PSI)
+    }
+}
+
+impl From<Atmospheres> for Psi {
+    fn from(atm: Atmospheres) -> Psi {
+        Psi::from(KiloPascal::from(atm))
+    }
+}
+
+impl From<Psi> for Atmospheres {
+    fn from(psi: Psi) -> Atmospheres {
+        Atmospheres::from(KiloPascal::from(psi))
+    }
+}
+
+// Energy <-> heat capacity <-> temperature
+
+impl Mul<Kelvin> for JoulesPerKelvin {
+    type Output = Joules;
+    fn mul(self, rhs: Kelvin) -> Joules {
+        Joules(self.0 * rhs.0)
+    }
+}
+
+impl Mul<JoulesPerKelvin> for Kelvin {
+    type Output = Joules;
+    fn mul(self, rhs: JoulesPerKelvin) -> Joules {
+        Joules(self.0 * rhs.0)
+    }
+}
+
+impl Div<JoulesPerKelvin> for Joules {
+    type Output = Kelvin;
+    fn div(self, rhs: JoulesPerKelvin) -> Kelvin {
+        Kelvin(self.0 / rhs.0)
+    }
+}
+
+impl Div<Kelvin> for Joules {
+    type Output = JoulesPerKelvin;
+    fn div(self, rhs: Kelvin) -> JoulesPerKelvin {
+        JoulesPerKelvin(self.0 / rhs.0)
+    }
+}